@@ -1,6 +1,8 @@
 use std::path::Path;
 
-use disk::{raw::RawImage, sectorsize::SectorSize, volume::Volume, Disk};
+use disk::{
+    compressed::CompressedImage, raw::RawImage, sectorsize::SectorSize, volume::Volume, Disk,
+};
 use error::BuildError;
 use filesystem::{fat12::Fat12, FileSystem};
 use operatingsystem::{vendor::OsVendor, OperatingSystem};
@@ -18,18 +20,29 @@ impl Builder {
         Builder { planner }
     }
 
-    pub fn build(&mut self, path: &Path) -> Result<(), BuildError> {
-        let os = self.planner.os();
+    /// Extension that selects the sparse/compressed output format over a flat
+    /// [`RawImage`]. Anything else (including no extension at all) writes a raw image.
+    const COMPRESSED_EXTENSION: &'static str = "ciso";
 
-        let (mut disk, sector_count) = {
-            if let Some(floppy_type) = self.planner.hwspec().floppy_type() {
-                let sector_count = floppy_type.sector_count();
-                let disk = RawImage::new_floppy(path, floppy_type)?;
-                (disk, sector_count)
-            } else {
-                return Err(BuildError::CanBuildOnlyFloppiesForNow);
-            }
+    pub fn build(&mut self, path: &Path) -> Result<(), BuildError> {
+        let Some(floppy_type) = self.planner.hwspec().floppy_type() else {
+            return Err(BuildError::CanBuildOnlyFloppiesForNow);
         };
+        let sector_count = floppy_type.sector_count();
+
+        if path.extension().and_then(|ext| ext.to_str()) == Some(Self::COMPRESSED_EXTENSION) {
+            let disk = CompressedImage::new(path, SectorSize::S512, sector_count)?;
+            self.build_on_disk(disk, sector_count)
+        } else {
+            let disk = RawImage::new(path, SectorSize::S512, sector_count)?;
+            self.build_on_disk(disk, sector_count)
+        }
+    }
+
+    /// Assembles the boot sector and filesystem onto an already-created disk, the
+    /// same way regardless of which container format backs it.
+    fn build_on_disk<D: Disk>(&mut self, mut disk: D, sector_count: u64) -> Result<(), BuildError> {
+        let os = self.planner.os();
 
         // Do the IBM thing if we're dealing with their equipment
         if os.vendor() == OsVendor::IBM {
@@ -53,21 +66,23 @@ impl Builder {
             .map_err(BuildError::DiskIoError)
     }
 
+    /// Sectors consumed by the reserved area, FAT copies, and root directory
+    /// before the data region (and its clusters) begins. This mirrors
+    /// `Fat12`'s own built-in defaults (1 reserved sector, 112 root entries)
+    /// closely enough to size the allocation table without constructing a
+    /// full `BiosParameterBlock` here first.
+    const NON_DATA_SECTORS: usize = 8;
+
     fn create_filesystem<'a, D: Disk>(
         &self,
         volume: &'a mut Volume<'a, D>,
     ) -> Result<Fat12<'a, D>, BuildError> {
-        Fat12::new(
-            SectorSize::S512,
-            1,
-            313,
-            volume,
-            operatingsystem::OperatingSystem::from_osshortname(
-                &operatingsystem::OsShortName::IBMDOS100,
-            ),
-            None,
-        )
-        .map_err(|_| BuildError::FileSystemError)
+        let cluster_size = 1;
+        let data_sectors = (volume.sector_count() as usize).saturating_sub(Self::NON_DATA_SECTORS);
+        let cluster_count = data_sectors / cluster_size;
+
+        Fat12::new(SectorSize::S512, cluster_size, cluster_count, volume)
+            .map_err(|_| BuildError::FileSystemError)
     }
 
     fn write_sysfiles<D: Disk>(
@@ -1,10 +1,10 @@
 use std::{fmt, str::FromStr};
 
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::CommonError;
 
-#[derive(Copy, Clone, Debug, Deserialize, PartialEq)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum Floppy {
     F525_160,
     F525_320,
@@ -36,6 +36,142 @@ impl Floppy {
     pub fn sector_size(&self) -> u64 {
         512
     }
+
+    /// Bytes actually available for file data once the reserved sector, both
+    /// FAT copies, and the root directory region are accounted for.
+    pub fn usable_capacity_bytes(&self) -> u64 {
+        const DIRENTRY_SIZE: u64 = 32;
+        const RESERVED_SECTORS: u64 = 1;
+        const FAT_COUNT: u64 = 2;
+
+        let geometry = self.geometry();
+        let sector_size = self.sector_size();
+        let root_dir_sectors =
+            (geometry.root_dir_entries as u64 * DIRENTRY_SIZE).div_ceil(sector_size);
+        let overhead_sectors =
+            RESERVED_SECTORS + FAT_COUNT * geometry.sectors_per_fat as u64 + root_dir_sectors;
+        let data_sectors = self.sector_count().saturating_sub(overhead_sectors);
+
+        data_sectors * sector_size
+    }
+
+    /// Number of cylinders in this format's CHS geometry.
+    pub fn cylinders(&self) -> usize {
+        self.geometry().cylinders
+    }
+
+    /// Number of heads (sides) in this format's CHS geometry.
+    pub fn heads(&self) -> usize {
+        self.geometry().heads
+    }
+
+    /// Number of sectors per track in this format's CHS geometry.
+    pub fn sectors_per_track(&self) -> usize {
+        self.geometry().sectors_per_track
+    }
+
+    /// The DOS media descriptor byte (BPB `BS_Media` / boot sector byte 0x15) for
+    /// this format, e.g. `0xF0` for a 1.44MB/2.88MB 3.5" disk.
+    pub fn media_descriptor(&self) -> u8 {
+        self.geometry().media_descriptor
+    }
+
+    /// The full physical layout this format implies: everything needed to build a
+    /// BIOS Parameter Block and CHS geometry for a freshly formatted image.
+    ///
+    /// These are the classic DOS-documented values for each format; a drive
+    /// capable of low-level formatting to other layouts (e.g. a 360K drive
+    /// formatted for 320K) isn't represented here.
+    pub fn geometry(&self) -> FloppyGeometry {
+        match self {
+            Floppy::F525_160 => FloppyGeometry {
+                sectors_per_track: 8,
+                heads: 1,
+                cylinders: 40,
+                media_descriptor: 0xFE,
+                root_dir_entries: 64,
+                sectors_per_cluster: 1,
+                sectors_per_fat: 1,
+            },
+            Floppy::F525_180 => FloppyGeometry {
+                sectors_per_track: 9,
+                heads: 1,
+                cylinders: 40,
+                media_descriptor: 0xFC,
+                root_dir_entries: 64,
+                sectors_per_cluster: 1,
+                sectors_per_fat: 1,
+            },
+            Floppy::F525_320 => FloppyGeometry {
+                sectors_per_track: 8,
+                heads: 2,
+                cylinders: 40,
+                media_descriptor: 0xFF,
+                root_dir_entries: 112,
+                sectors_per_cluster: 2,
+                sectors_per_fat: 2,
+            },
+            Floppy::F525_360 => FloppyGeometry {
+                sectors_per_track: 9,
+                heads: 2,
+                cylinders: 40,
+                media_descriptor: 0xFD,
+                root_dir_entries: 112,
+                sectors_per_cluster: 2,
+                sectors_per_fat: 2,
+            },
+            Floppy::F525_1200 => FloppyGeometry {
+                sectors_per_track: 15,
+                heads: 2,
+                cylinders: 80,
+                media_descriptor: 0xF9,
+                root_dir_entries: 224,
+                sectors_per_cluster: 1,
+                sectors_per_fat: 7,
+            },
+            Floppy::F35_720 => FloppyGeometry {
+                sectors_per_track: 9,
+                heads: 2,
+                cylinders: 80,
+                media_descriptor: 0xF9,
+                root_dir_entries: 112,
+                sectors_per_cluster: 2,
+                sectors_per_fat: 3,
+            },
+            Floppy::F35_1440 => FloppyGeometry {
+                sectors_per_track: 18,
+                heads: 2,
+                cylinders: 80,
+                media_descriptor: 0xF0,
+                root_dir_entries: 224,
+                sectors_per_cluster: 1,
+                sectors_per_fat: 9,
+            },
+            Floppy::F35_2880 => FloppyGeometry {
+                sectors_per_track: 36,
+                heads: 2,
+                cylinders: 80,
+                media_descriptor: 0xF0,
+                root_dir_entries: 240,
+                sectors_per_cluster: 2,
+                sectors_per_fat: 9,
+            },
+        }
+    }
+}
+
+/// The physical parameters a `Floppy` format implies, independent of any
+/// particular BIOS Parameter Block or CHS geometry representation, so crates
+/// without a dependency on disk geometry types can still consult them.
+#[derive(Copy, Clone, Debug, PartialEq)]
+pub struct FloppyGeometry {
+    pub sectors_per_track: usize,
+    pub heads: usize,
+    pub cylinders: usize,
+    pub media_descriptor: u8,
+    pub root_dir_entries: usize,
+    pub sectors_per_cluster: usize,
+    pub sectors_per_fat: usize,
 }
 
 impl FromStr for Floppy {
@@ -74,4 +210,4 @@ impl fmt::Display for Floppy {
         };
         write!(f, "{}", label)
     }
-}
\ No newline at end of file
+}
@@ -0,0 +1,494 @@
+//! A sparse, compressed disk-image backend modeled on the CISO/WBFS formats used by
+//! GameCube/Wii tooling: a fixed header, a group table recording which groups are
+//! stored and where, and compressed group payloads appended after the table.
+//!
+//! Groups are compressed with zstd rather than bzip2 -- it's already a dependency
+//! elsewhere in the workspace, decompresses far faster, and at these image sizes
+//! bzip2's usual size edge doesn't outweigh that.
+
+use std::collections::BTreeMap;
+use std::fs::{File, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+
+use crate::error::DiskError;
+use crate::sectorsize::SectorSize;
+use crate::Disk;
+
+/// Number of sectors bundled together into a single compressible group.
+///
+/// Grouping sectors together (rather than compressing one sector at a time) gives zstd
+/// enough context to actually find redundancy, at the cost of having to decompress a
+/// few extra sectors' worth of data whenever only one of them is actually needed.
+pub const SECTORS_PER_GROUP: usize = 64;
+
+/// The group is entirely zero bytes. Nothing is stored on disk for it; `file_offset`
+/// and `stored_len` are meaningless and should be read back as zero.
+const GROUP_FLAG_SPARSE: u8 = 0x01;
+
+/// The group is stored verbatim (not zstd-compressed) because compression would have
+/// made it larger than the raw bytes.
+const GROUP_FLAG_UNCOMPRESSED: u8 = 0x02;
+
+/// Tag written as the first four bytes of the file, so [`crate::detect::detect_image_type`]
+/// can recognize an existing file as this format before anything else has to be parsed.
+pub(crate) const MAGIC: &[u8; 4] = b"DCIZ";
+
+/// Size in bytes of the fixed header: magic, sector size, sector count, group count.
+const HEADER_LEN: u64 = 32;
+
+/// Size in bytes of one [`GroupTableEntry`] as persisted on disk.
+const GROUP_ENTRY_LEN: u64 = 24;
+
+/// One entry in a [`CompressedImage`]'s group table.
+///
+/// Indexed by group number, this tells a reader exactly where to find the bytes for a
+/// group and how to interpret them, without having to touch any other group.
+#[derive(Clone, Copy, Debug, Default, PartialEq)]
+struct GroupTableEntry {
+    /// Size in bytes of the group once decompressed (`SECTORS_PER_GROUP * sector_size`,
+    /// except for a truncated final group; zero for a group never written since `new`).
+    uncompressed_len: u32,
+    /// Size in bytes of the group as it is actually stored on disk.
+    stored_len: u32,
+    /// Byte offset into the container file where the stored bytes begin.
+    file_offset: u64,
+    /// Bitfield of `GROUP_FLAG_*` values.
+    flags: u8,
+}
+
+impl GroupTableEntry {
+    fn is_sparse(&self) -> bool {
+        self.flags & GROUP_FLAG_SPARSE != 0
+    }
+
+    fn is_uncompressed(&self) -> bool {
+        self.flags & GROUP_FLAG_UNCOMPRESSED != 0
+    }
+}
+
+/// A sparse, zstd-compressed disk image implementing the [`Disk`] trait.
+///
+/// The image is split into fixed-size groups of [`SECTORS_PER_GROUP`] sectors. Each
+/// group is compressed independently, so a single sector can be read back by
+/// decompressing only the one group that contains it: `group = sector_index /
+/// SECTORS_PER_GROUP`. All-zero groups are recorded as sparse and occupy zero bytes on
+/// disk; groups that wouldn't shrink under compression are stored verbatim instead.
+///
+/// This format shrinks the mostly-empty FAT12 images this tool produces considerably
+/// compared to a flat [`RawImage`](crate::raw::RawImage).
+#[derive(Debug)]
+pub struct CompressedImage {
+    file: File,
+    sector_size: SectorSize,
+    sector_count: u64,
+    group_table: Vec<GroupTableEntry>,
+}
+
+impl CompressedImage {
+    /// Creates a new, empty compressed image backed by `path`.
+    ///
+    /// The image starts out fully sparse: no group has been written yet, so reading
+    /// any sector returns zeroes until it is written to. The header and an all-sparse
+    /// group table are written up front, so the file is immediately re-openable via
+    /// [`CompressedImage::open_existing`] even before anything else is written to it.
+    pub fn new(path: &Path, sector_size: SectorSize, sector_count: u64) -> Result<Self, DiskError> {
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map_err(|_| DiskError::FileAlreadyExists)?;
+
+        let group_count = (sector_count as usize).div_ceil(SECTORS_PER_GROUP);
+
+        let mut image = Self {
+            file,
+            sector_size,
+            sector_count,
+            group_table: vec![
+                GroupTableEntry {
+                    uncompressed_len: 0,
+                    stored_len: 0,
+                    file_offset: 0,
+                    flags: GROUP_FLAG_SPARSE,
+                };
+                group_count
+            ],
+        };
+
+        image.write_header()?;
+        for group in 0..group_count {
+            image.persist_entry(group)?;
+        }
+
+        Ok(image)
+    }
+
+    /// Opens an existing compressed image previously created by [`CompressedImage::new`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::UnsupportedImageFormat` if the file doesn't start with
+    /// [`MAGIC`], or `DiskError::GroupTableCorrupt` if the persisted group count
+    /// doesn't match what `sector_count` implies. Returns `DiskError::UnknownGroupFlag`
+    /// if a persisted entry sets a flag bit this version of the format doesn't know
+    /// about.
+    pub fn open_existing(path: &Path) -> Result<Self, DiskError> {
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|_| DiskError::FileOpenFailed)?;
+
+        let mut header = [0u8; HEADER_LEN as usize];
+        file.seek(SeekFrom::Start(0))
+            .map_err(|_| DiskError::SeekFailed)?;
+        file.read_exact(&mut header)
+            .map_err(|_| DiskError::ReadFailed)?;
+
+        if &header[0..4] != MAGIC {
+            return Err(DiskError::UnsupportedImageFormat);
+        }
+
+        let sector_size = u16::from_le_bytes(header[4..6].try_into().unwrap());
+        let sector_size = SectorSize::try_from(sector_size as usize)?;
+        let sector_count = u64::from_le_bytes(header[6..14].try_into().unwrap());
+        let group_count = u32::from_le_bytes(header[14..18].try_into().unwrap()) as usize;
+
+        if group_count != (sector_count as usize).div_ceil(SECTORS_PER_GROUP) {
+            return Err(DiskError::GroupTableCorrupt);
+        }
+
+        let mut group_table = Vec::with_capacity(group_count);
+        for group in 0..group_count {
+            let mut entry_bytes = [0u8; GROUP_ENTRY_LEN as usize];
+            file.seek(SeekFrom::Start(Self::table_offset(group)))
+                .map_err(|_| DiskError::SeekFailed)?;
+            file.read_exact(&mut entry_bytes)
+                .map_err(|_| DiskError::ReadFailed)?;
+
+            let flags = entry_bytes[16];
+            if flags & !(GROUP_FLAG_SPARSE | GROUP_FLAG_UNCOMPRESSED) != 0 {
+                return Err(DiskError::UnknownGroupFlag);
+            }
+
+            group_table.push(GroupTableEntry {
+                uncompressed_len: u32::from_le_bytes(entry_bytes[0..4].try_into().unwrap()),
+                stored_len: u32::from_le_bytes(entry_bytes[4..8].try_into().unwrap()),
+                file_offset: u64::from_le_bytes(entry_bytes[8..16].try_into().unwrap()),
+                flags,
+            });
+        }
+
+        Ok(Self {
+            file,
+            sector_size,
+            sector_count,
+            group_table,
+        })
+    }
+
+    /// Byte offset into the container file of `group`'s persisted table entry.
+    fn table_offset(group: usize) -> u64 {
+        HEADER_LEN + group as u64 * GROUP_ENTRY_LEN
+    }
+
+    /// Writes the fixed header (magic, sector size, sector count, group count) to the
+    /// start of the file.
+    fn write_header(&mut self) -> Result<(), DiskError> {
+        let mut header = [0u8; HEADER_LEN as usize];
+        header[0..4].copy_from_slice(MAGIC);
+        header[4..6].copy_from_slice(&(self.sector_size.as_usize() as u16).to_le_bytes());
+        header[6..14].copy_from_slice(&self.sector_count.to_le_bytes());
+        header[14..18].copy_from_slice(&(self.group_table.len() as u32).to_le_bytes());
+
+        self.file
+            .seek(SeekFrom::Start(0))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file
+            .write_all(&header)
+            .map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)
+    }
+
+    /// Writes `group`'s current table entry to its reserved slot in the file.
+    fn persist_entry(&mut self, group: usize) -> Result<(), DiskError> {
+        let entry = self.group_table[group];
+        let mut bytes = [0u8; GROUP_ENTRY_LEN as usize];
+        bytes[0..4].copy_from_slice(&entry.uncompressed_len.to_le_bytes());
+        bytes[4..8].copy_from_slice(&entry.stored_len.to_le_bytes());
+        bytes[8..16].copy_from_slice(&entry.file_offset.to_le_bytes());
+        bytes[16] = entry.flags;
+
+        self.file
+            .seek(SeekFrom::Start(Self::table_offset(group)))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file
+            .write_all(&bytes)
+            .map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)
+    }
+
+    /// Returns the group number that contains the given sector index.
+    fn group_for_sector(&self, sector_index: u64) -> usize {
+        (sector_index as usize) / SECTORS_PER_GROUP
+    }
+
+    /// Returns the byte size a fully-populated group occupies once decompressed.
+    fn group_byte_len(&self) -> usize {
+        SECTORS_PER_GROUP * self.sector_size.as_usize()
+    }
+
+    /// Returns the number of sectors that fall within `group`, accounting for a final
+    /// group that may be shorter than [`SECTORS_PER_GROUP`].
+    fn sectors_in_group(&self, group: usize) -> usize {
+        let first_sector = group * SECTORS_PER_GROUP;
+        usize::min(
+            SECTORS_PER_GROUP,
+            (self.sector_count as usize) - first_sector,
+        )
+    }
+
+    /// Reads and decompresses the raw bytes of `group` into memory.
+    ///
+    /// Returns a zero-filled buffer for a sparse group without touching the file.
+    fn read_group(&mut self, group: usize) -> Result<Vec<u8>, DiskError> {
+        let entry = self.group_table[group];
+        let uncompressed_len = self.sectors_in_group(group) * self.sector_size.as_usize();
+
+        if entry.is_sparse() {
+            return Ok(vec![0u8; uncompressed_len]);
+        }
+
+        self.file
+            .seek(SeekFrom::Start(entry.file_offset))
+            .map_err(|_| DiskError::SeekFailed)?;
+        let mut stored = vec![0u8; entry.stored_len as usize];
+        self.file
+            .read_exact(&mut stored)
+            .map_err(|_| DiskError::ReadFailed)?;
+
+        if entry.is_uncompressed() {
+            Ok(stored)
+        } else {
+            zstd::stream::decode_all(&stored[..]).map_err(|_| DiskError::DecompressionFailed)
+        }
+    }
+
+    /// Compresses `data` and appends it to the end of the container file, updating the
+    /// group table entry for `group` to point at the new storage.
+    ///
+    /// Storage for a rewritten group is always appended rather than reused in place,
+    /// since a compressed group's size can shrink or grow between writes.
+    fn write_group(&mut self, group: usize, data: &[u8]) -> Result<(), DiskError> {
+        if data.iter().all(|&b| b == 0) {
+            self.group_table[group] = GroupTableEntry {
+                uncompressed_len: data.len() as u32,
+                stored_len: 0,
+                file_offset: 0,
+                flags: GROUP_FLAG_SPARSE,
+            };
+            return self.persist_entry(group);
+        }
+
+        let compressed =
+            zstd::stream::encode_all(data, 0).map_err(|_| DiskError::CompressionFailed)?;
+
+        let (stored, flags) = if compressed.len() < data.len() {
+            (compressed, 0u8)
+        } else {
+            (data.to_vec(), GROUP_FLAG_UNCOMPRESSED)
+        };
+
+        let file_offset = self
+            .file
+            .seek(SeekFrom::End(0))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file
+            .write_all(&stored)
+            .map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)?;
+
+        self.group_table[group] = GroupTableEntry {
+            uncompressed_len: data.len() as u32,
+            stored_len: stored.len() as u32,
+            file_offset,
+            flags,
+        };
+
+        self.persist_entry(group)
+    }
+}
+
+impl Disk for CompressedImage {
+    fn read_sector(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+
+        if buf.len() < sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba >= self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        let group = self.group_for_sector(lba);
+        let group_bytes = self.read_group(group)?;
+
+        let offset_in_group = (lba as usize % SECTORS_PER_GROUP) * sector_size;
+        buf[..sector_size]
+            .copy_from_slice(&group_bytes[offset_in_group..offset_in_group + sector_size]);
+
+        Ok(())
+    }
+
+    fn write_sector(&mut self, lba: u64, buf: &[u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+
+        if buf.len() < sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba >= self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        let group = self.group_for_sector(lba);
+        let mut group_bytes = self.read_group(group)?;
+
+        let offset_in_group = (lba as usize % SECTORS_PER_GROUP) * sector_size;
+        group_bytes[offset_in_group..offset_in_group + sector_size]
+            .copy_from_slice(&buf[..sector_size]);
+
+        self.write_group(group, &group_bytes)
+    }
+
+    fn ibmwipe(&mut self) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+        let ibm_empty_sector = vec![0xF6u8; sector_size];
+        for sector in 0..self.sector_count {
+            self.write_sector(sector, &ibm_empty_sector)?;
+        }
+        Ok(())
+    }
+
+    fn sector_count(&self) -> u64 {
+        self.sector_count
+    }
+
+    fn sector_size(&self) -> SectorSize {
+        self.sector_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn unwritten_sectors_read_back_as_zero() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut image = CompressedImage::new(&path, SectorSize::S512, 256).unwrap();
+
+        let mut buf = [0xAAu8; 512];
+        image.read_sector(10, &mut buf).unwrap();
+        assert_eq!(buf, [0u8; 512]);
+    }
+
+    #[test]
+    fn write_and_read_sector_roundtrip() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut image = CompressedImage::new(&path, SectorSize::S512, 256).unwrap();
+
+        let data = [0xAB; 512];
+        image.write_sector(130, &data).unwrap();
+
+        let mut readback = [0u8; 512];
+        image.read_sector(130, &mut readback).unwrap();
+        assert_eq!(readback, data);
+
+        // A neighbouring sector in the same group must remain untouched (zero).
+        let mut neighbour = [0xFFu8; 512];
+        image.read_sector(129, &mut neighbour).unwrap();
+        assert_eq!(neighbour, [0u8; 512]);
+    }
+
+    #[test]
+    fn incompressible_group_is_stored_uncompressed() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut image = CompressedImage::new(&path, SectorSize::S512, 64).unwrap();
+
+        // Pseudo-random bytes filling the whole group won't compress, exercising the
+        // uncompressed fallback.
+        let mut state: u32 = 0x1234_5678;
+        let mut sectors = vec![[0u8; 512]; SECTORS_PER_GROUP];
+        for sector in sectors.iter_mut() {
+            for byte in sector.iter_mut() {
+                state ^= state << 13;
+                state ^= state >> 17;
+                state ^= state << 5;
+                *byte = (state >> 24) as u8;
+            }
+        }
+        for (lba, sector) in sectors.iter().enumerate() {
+            image.write_sector(lba as u64, sector).unwrap();
+        }
+
+        assert!(image.group_table[0].is_uncompressed());
+
+        let mut readback = [0u8; 512];
+        image.read_sector(0, &mut readback).unwrap();
+        assert_eq!(readback, sectors[0]);
+    }
+
+    #[test]
+    fn out_of_bounds_sector_fails() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut image = CompressedImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        let mut buf = [0u8; 512];
+        assert!(matches!(
+            image.read_sector(10, &mut buf),
+            Err(DiskError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn reopened_image_reads_back_written_and_sparse_sectors() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        {
+            let mut image = CompressedImage::new(&path, SectorSize::S512, 256).unwrap();
+            image.write_sector(130, &[0xAB; 512]).unwrap();
+        }
+
+        let mut reopened = CompressedImage::open_existing(&path).unwrap();
+        assert_eq!(reopened.sector_size(), SectorSize::S512);
+        assert_eq!(reopened.sector_count(), 256);
+
+        let mut written = [0u8; 512];
+        reopened.read_sector(130, &mut written).unwrap();
+        assert_eq!(written, [0xAB; 512]);
+
+        let mut sparse = [0xFFu8; 512];
+        reopened.read_sector(129, &mut sparse).unwrap();
+        assert_eq!(sparse, [0u8; 512]);
+    }
+
+    #[test]
+    fn open_existing_rejects_file_without_magic() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        std::fs::write(&path, [0u8; HEADER_LEN as usize]).unwrap();
+
+        assert!(matches!(
+            CompressedImage::open_existing(&path),
+            Err(DiskError::UnsupportedImageFormat)
+        ));
+    }
+}
@@ -0,0 +1,119 @@
+use crate::error::DiskError;
+use crate::sector::Sector;
+use crate::Disk;
+
+/// A stateful cursor over a [`Disk`]'s blocks (its native-sized sectors).
+///
+/// Where `read_sector`/`write_sector` address a disk by explicit LBA, `BlockCursor`
+/// tracks a current `block_index` and advances it on every successful read or write,
+/// yielding or consuming a [`Sector`] of the disk's native size. This is the
+/// complement to `Sector::from_slice`/`TryFrom<&[u8]>`: those build a `Sector` out of
+/// an in-memory buffer, this one streams them off (and onto) an actual disk.
+pub struct BlockCursor<'a, D: Disk + ?Sized> {
+    disk: &'a mut D,
+    block_index: u64,
+}
+
+impl<'a, D: Disk + ?Sized> BlockCursor<'a, D> {
+    /// Creates a cursor positioned at block 0.
+    pub fn new(disk: &'a mut D) -> Self {
+        Self {
+            disk,
+            block_index: 0,
+        }
+    }
+
+    /// Total number of blocks on the underlying disk.
+    pub fn block_count(&self) -> u64 {
+        self.disk.sector_count()
+    }
+
+    /// The block index the next `read_block`/`write_block` call will act on.
+    pub fn block_index(&self) -> u64 {
+        self.block_index
+    }
+
+    /// Repositions the cursor to `block_index`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::EndOfDisk` if `block_index` is at or past `block_count()`.
+    pub fn seek(&mut self, block_index: u64) -> Result<(), DiskError> {
+        if block_index >= self.block_count() {
+            return Err(DiskError::EndOfDisk);
+        }
+        self.block_index = block_index;
+        Ok(())
+    }
+
+    /// Reads the block at the current cursor position and advances the cursor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::EndOfDisk` if the cursor is already past the last block.
+    pub fn read_block(&mut self) -> Result<Sector, DiskError> {
+        if self.block_index >= self.block_count() {
+            return Err(DiskError::EndOfDisk);
+        }
+
+        let mut buf = vec![0u8; self.disk.sector_size().as_usize()];
+        self.disk.read_sector(self.block_index, &mut buf)?;
+        let sector = Sector::try_from(buf.as_slice())?;
+        self.block_index += 1;
+        Ok(sector)
+    }
+
+    /// Writes `sector` at the current cursor position and advances the cursor.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::EndOfDisk` if the cursor is already past the last block.
+    pub fn write_block(&mut self, sector: &Sector) -> Result<(), DiskError> {
+        if self.block_index >= self.block_count() {
+            return Err(DiskError::EndOfDisk);
+        }
+
+        self.disk.write_sector(self.block_index, sector.data())?;
+        self.block_index += 1;
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::RawImage;
+    use crate::sectorsize::SectorSize;
+    use tempfile::tempdir;
+
+    #[test]
+    fn cursor_advances_and_hits_end_of_disk() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 2).unwrap();
+
+        let mut cursor = BlockCursor::new(&mut disk);
+        assert_eq!(cursor.block_count(), 2);
+
+        let sector = Sector::new_standard([0xAB; 512]);
+        cursor.write_block(&sector).unwrap();
+        assert_eq!(cursor.block_index(), 1);
+        cursor.write_block(&sector).unwrap();
+        assert_eq!(cursor.block_index(), 2);
+
+        assert!(matches!(
+            cursor.write_block(&sector),
+            Err(DiskError::EndOfDisk)
+        ));
+    }
+
+    #[test]
+    fn seek_past_end_of_disk_fails() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 1).unwrap();
+
+        let mut cursor = BlockCursor::new(&mut disk);
+        assert!(matches!(cursor.seek(1), Err(DiskError::EndOfDisk)));
+    }
+}
@@ -0,0 +1,206 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::path::Path;
+
+use crate::compressed::{CompressedImage, MAGIC as COMPRESSED_MAGIC};
+use crate::error::DiskError;
+use crate::raw::RawImage;
+use crate::vhd::VhdImage;
+use crate::Disk;
+
+/// Size of the buffer used to probe a candidate image file for a format's magic
+/// bytes. Kept as a multiple of the largest logical sector size this crate supports
+/// ([`crate::sectorsize::SectorSize::S4096`]) so the same probe works unmodified
+/// against files opened with `O_DIRECT` against a real block device, which refuses
+/// reads that aren't sector-aligned in both size and buffer address.
+const PROBE_BUFFER_LEN: usize = 4096;
+
+/// The `QFI\xFB` magic QCOW images store as a big-endian `u32` at offset 0.
+const QCOW_MAGIC: u32 = 0x5146_49FB;
+
+/// The signature VHDX images store as the first 8 bytes of the file.
+const VHDX_SIGNATURE: &[u8; 8] = b"vhdxfile";
+
+/// The cookie a VHD footer stores as its first 8 bytes. For a VHD image the footer
+/// is the *last* 512 bytes of the file, which the probe reads as the tail of the last
+/// [`PROBE_BUFFER_LEN`]-sized (sector-aligned) region.
+const VHD_COOKIE: &[u8; 8] = b"conectix";
+
+/// The disk image formats this crate can recognize on probe.
+///
+/// [`ImageType::Raw`], [`ImageType::Vhd`], and [`ImageType::Compressed`] currently
+/// have a working [`Disk`] backend; [`ImageType::Vhdx`] and [`ImageType::Qcow`] are
+/// detected so callers get an accurate diagnosis of what they're looking at, even
+/// though `open_disk` can't open them yet.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ImageType {
+    Raw,
+    Vhd,
+    Vhdx,
+    Qcow,
+    Compressed,
+}
+
+/// Probes `path` and identifies which disk image format it holds.
+///
+/// Both probe reads are done through a [`PROBE_BUFFER_LEN`]-sized buffer, sized and
+/// implicitly aligned to a multiple of the logical sector size, so the same code
+/// works unmodified on files opened against real block devices under `O_DIRECT`.
+///
+/// Detection checks, in order: QCOW's big-endian magic at offset 0, VHDX's
+/// `vhdxfile` signature at offset 0, this crate's own compressed-image tag at offset
+/// 0, then VHD's `conectix` footer cookie in the last sector-aligned region of the
+/// file. Anything that matches none of these is assumed to be a flat
+/// [`ImageType::Raw`] image.
+pub fn detect_image_type(path: &Path) -> Result<ImageType, DiskError> {
+    let mut file = File::open(path).map_err(|_| DiskError::FileOpenFailed)?;
+    let len = file
+        .metadata()
+        .map_err(|_| DiskError::FileMetadataFailed)?
+        .len();
+
+    let mut header = [0u8; PROBE_BUFFER_LEN];
+    let header_read = file.read(&mut header).map_err(|_| DiskError::ReadFailed)?;
+
+    if header_read >= 4 && u32::from_be_bytes(header[0..4].try_into().unwrap()) == QCOW_MAGIC {
+        return Ok(ImageType::Qcow);
+    }
+    if header_read >= 8 && &header[0..8] == VHDX_SIGNATURE {
+        return Ok(ImageType::Vhdx);
+    }
+    if header_read >= 4 && &header[0..4] == COMPRESSED_MAGIC {
+        return Ok(ImageType::Compressed);
+    }
+
+    if len >= PROBE_BUFFER_LEN as u64 {
+        let mut footer = [0u8; PROBE_BUFFER_LEN];
+        file.seek(SeekFrom::Start(len - PROBE_BUFFER_LEN as u64))
+            .map_err(|_| DiskError::SeekFailed)?;
+        file.read_exact(&mut footer)
+            .map_err(|_| DiskError::ReadFailed)?;
+
+        // The VHD footer cookie is the first 8 bytes of its 512-byte footer, which
+        // sits at the very end of the file; within our sector-aligned probe buffer
+        // that means the last 512 bytes.
+        let footer_start = PROBE_BUFFER_LEN - 512;
+        if &footer[footer_start..footer_start + 8] == VHD_COOKIE {
+            return Ok(ImageType::Vhd);
+        }
+    }
+
+    Ok(ImageType::Raw)
+}
+
+/// Opens `path`, auto-detecting its image format and dispatching to the matching
+/// [`Disk`] backend.
+///
+/// This is the front door callers should reach for instead of constructing a
+/// specific backend directly, unless the format is already known out of band.
+///
+/// # Errors
+///
+/// Returns `DiskError::UnsupportedImageFormat` if the file is recognized as VHDX or
+/// QCOW, since this crate doesn't yet implement a backend for either.
+pub fn open_disk(path: &Path) -> Result<Box<dyn Disk>, DiskError> {
+    match detect_image_type(path)? {
+        ImageType::Raw => Ok(Box::new(RawImage::open_existing(path)?)),
+        ImageType::Vhd => Ok(Box::new(VhdImage::open_existing(path)?)),
+        ImageType::Compressed => Ok(Box::new(CompressedImage::open_existing(path)?)),
+        ImageType::Vhdx | ImageType::Qcow => Err(DiskError::UnsupportedImageFormat),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::geometry::Geometry;
+    use crate::sectorsize::SectorSize;
+    use tempfile::tempdir;
+
+    #[test]
+    fn detects_raw_image() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        RawImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        assert_eq!(detect_image_type(&path).unwrap(), ImageType::Raw);
+    }
+
+    #[test]
+    fn detects_vhd_image() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        VhdImage::create_fixed(&path, geometry, SectorSize::S512).unwrap();
+
+        assert_eq!(detect_image_type(&path).unwrap(), ImageType::Vhd);
+    }
+
+    #[test]
+    fn detects_qcow_image() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.qcow2");
+
+        let mut bytes = vec![0u8; PROBE_BUFFER_LEN];
+        bytes[0..4].copy_from_slice(&QCOW_MAGIC.to_be_bytes());
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert_eq!(detect_image_type(&path).unwrap(), ImageType::Qcow);
+    }
+
+    #[test]
+    fn detects_vhdx_image() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhdx");
+
+        let mut bytes = vec![0u8; PROBE_BUFFER_LEN];
+        bytes[0..8].copy_from_slice(VHDX_SIGNATURE);
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert_eq!(detect_image_type(&path).unwrap(), ImageType::Vhdx);
+    }
+
+    #[test]
+    fn detects_compressed_image() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.ciso");
+        CompressedImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        assert_eq!(detect_image_type(&path).unwrap(), ImageType::Compressed);
+    }
+
+    #[test]
+    fn open_disk_dispatches_to_compressed_backend() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.ciso");
+        CompressedImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        let disk = open_disk(&path).unwrap();
+        assert_eq!(disk.sector_count(), 4);
+    }
+
+    #[test]
+    fn open_disk_dispatches_to_raw_backend() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        RawImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        let disk = open_disk(&path).unwrap();
+        assert_eq!(disk.sector_count(), 4);
+    }
+
+    #[test]
+    fn open_disk_rejects_unsupported_format() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhdx");
+
+        let mut bytes = vec![0u8; PROBE_BUFFER_LEN];
+        bytes[0..8].copy_from_slice(VHDX_SIGNATURE);
+        std::fs::write(&path, &bytes).unwrap();
+
+        assert!(matches!(
+            open_disk(&path),
+            Err(DiskError::UnsupportedImageFormat)
+        ));
+    }
+}
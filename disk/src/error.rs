@@ -1,16 +1,28 @@
 #[derive(Debug, PartialEq)]
 pub enum DiskError {
     BufferTooSmall,
+    CompressionFailed,
+    CylinderOutOfRange,
+    DecompressionFailed,
+    EndOfDisk,
     FileAlreadyExists,
     FileMetadataFailed,
     FileOpenFailed,
     FlushFailed,
+    GroupTableCorrupt,
+    HeadOutOfRange,
+    IntegrityManifestMissing,
+    IntegrityMismatch,
     InvalidArgument,
     InvalidFileSize,
     InvalidSectorSize,
     IoError,
+    LbaOutOfRange,
     OutOfBounds,
     ReadFailed,
+    SectorOutOfRange,
     SeekFailed,
+    UnknownGroupFlag,
+    UnsupportedImageFormat,
     WriteFailed,
 }
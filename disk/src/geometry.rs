@@ -237,6 +237,70 @@ impl Geometry {
         self.heads
     }
 
+    /// Converts a linear LBA into the cylinder/head/sector triple this geometry
+    /// would encode it as.
+    ///
+    /// This is the inverse of `chs_to_lba`, and is what lets a caller translate a
+    /// sector address as used by `RawImage::read_sector` into the CHS values an
+    /// MBR partition entry actually stores, so the partition table can be verified
+    /// against the real on-disk layout.
+    ///
+    /// The standard formula is used: with `spt` the sectors per track and `hpc` the
+    /// heads per cylinder, `cylinder = lba / (hpc * spt)`, `temp = lba % (hpc * spt)`,
+    /// `head = temp / spt`, and `sector = (temp % spt) + 1` (sectors are 1-based).
+    ///
+    /// # Returns
+    /// - `Ok((cylinder, head, sector))`: The CHS triple addressed by `lba`.
+    /// - `Err(DiskError::LbaOutOfRange)`: If `lba` is at or beyond
+    ///   `cylinders * heads * sectors`, the total addressable CHS space.
+    pub fn lba_to_chs(&self, lba: u64) -> Result<(usize, usize, usize), DiskError> {
+        let hpc = self.heads as u64;
+        let spt = self.sectors as u64;
+        let total_sectors = self.cylinders as u64 * hpc * spt;
+
+        if lba >= total_sectors {
+            return Err(DiskError::LbaOutOfRange);
+        }
+
+        let cylinder = lba / (hpc * spt);
+        let temp = lba % (hpc * spt);
+        let head = temp / spt;
+        let sector = (temp % spt) + 1;
+
+        Ok((cylinder as usize, head as usize, sector as usize))
+    }
+
+    /// Converts a cylinder/head/sector triple into its linear LBA equivalent under
+    /// this geometry.
+    ///
+    /// This is the inverse of `lba_to_chs`. The formula used is
+    /// `lba = (cylinder * hpc + head) * spt + (sector - 1)`, where `hpc` is the
+    /// heads per cylinder and `spt` is the sectors per track from this geometry.
+    ///
+    /// # Returns
+    /// - `Ok(lba)`: The linear LBA addressed by `(cylinder, head, sector)`.
+    /// - `Err(DiskError::SectorOutOfRange)`: If `sector` is `0` or greater than
+    ///   `self.sectors()` (sectors are 1-based).
+    /// - `Err(DiskError::LbaOutOfRange)`: If the resulting LBA would fall outside
+    ///   `cylinders * heads * sectors`, the total addressable CHS space.
+    pub fn chs_to_lba(&self, cylinder: usize, head: usize, sector: usize) -> Result<u64, DiskError> {
+        if sector == 0 || sector > self.sectors {
+            return Err(DiskError::SectorOutOfRange);
+        }
+
+        let hpc = self.heads as u64;
+        let spt = self.sectors as u64;
+        let total_sectors = self.cylinders as u64 * hpc * spt;
+
+        let lba = (cylinder as u64 * hpc + head as u64) * spt + (sector as u64 - 1);
+
+        if lba >= total_sectors {
+            return Err(DiskError::LbaOutOfRange);
+        }
+
+        Ok(lba)
+    }
+
     /// Get the number of sectors per track on the disk.
     ///
     /// This method returns the number of sectors on a single track of the disk. A sector is
@@ -252,7 +316,7 @@ impl Geometry {
 
 #[cfg(test)]
 mod tests {
-    use super::Geometry;
+    use super::{DiskError, Geometry};
 
     /// Test that the conversion of a `Geometry` instance to MBR bytes and back to a `Geometry`
     /// instance works correctly, ensuring data integrity during the roundtrip.
@@ -282,4 +346,41 @@ mod tests {
         // Verify that the converted Geometry instance matches the original byte array when converted back to MBR bytes
         assert_eq!(newgeom.unwrap().to_mbr_bytes().unwrap(), [15, 191, 28]);
     }
+
+    /// Test that converting an LBA to CHS and back yields the original LBA.
+    #[test]
+    fn lba_chs_roundtrip() {
+        let geometry = Geometry::new(40, 2, 12).unwrap();
+
+        let (cylinder, head, sector) = geometry.lba_to_chs(29).unwrap();
+        assert_eq!((cylinder, head, sector), (1, 0, 6));
+
+        let lba = geometry.chs_to_lba(cylinder, head, sector).unwrap();
+        assert_eq!(lba, 29);
+    }
+
+    /// Test that an LBA beyond the addressable CHS space is rejected.
+    #[test]
+    fn lba_to_chs_out_of_range() {
+        let geometry = Geometry::new(40, 2, 12).unwrap();
+        let total_sectors = 40 * 2 * 12;
+        assert_eq!(
+            geometry.lba_to_chs(total_sectors),
+            Err(DiskError::LbaOutOfRange)
+        );
+    }
+
+    /// Test that a zero or too-large sector value is rejected by `chs_to_lba`.
+    #[test]
+    fn chs_to_lba_rejects_invalid_sector() {
+        let geometry = Geometry::new(40, 2, 12).unwrap();
+        assert_eq!(
+            geometry.chs_to_lba(0, 0, 0),
+            Err(DiskError::SectorOutOfRange)
+        );
+        assert_eq!(
+            geometry.chs_to_lba(0, 0, 13),
+            Err(DiskError::SectorOutOfRange)
+        );
+    }
 }
@@ -0,0 +1,478 @@
+use crate::{error::DiskError, mbr::Mbr, volume::Volume, Disk};
+use uuid::Uuid;
+
+/// The 8-byte "EFI PART" signature every GPT header opens with.
+const SIGNATURE: [u8; 8] = *b"EFI PART";
+
+/// GPT revision 1.0, the only revision this crate writes or expects to read.
+const REVISION: u32 = 0x0001_0000;
+
+/// Size in bytes of the on-disk header structure, not counting the zero
+/// padding out to the end of its sector.
+const HEADER_SIZE: u32 = 92;
+
+/// Size in bytes of a single partition entry.
+const ENTRY_SIZE: u32 = 128;
+
+/// Number of partition entries in the array, matching the 128-entry default
+/// every mainstream GPT implementation (Windows, `parted`, `gptman`) writes.
+const ENTRY_COUNT: u32 = 128;
+
+/// Length in bytes of a partition entry's UTF-16LE name field (36 code units).
+const ENTRY_NAME_LEN: usize = 72;
+
+/// LBA the primary header lives at; LBA 0 is the protective MBR.
+const PRIMARY_HEADER_LBA: u64 = 1;
+
+/// One partition described by a [`PartitionTable`], as handed back by
+/// [`PartitionTable::partitions`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionInfo {
+    pub partition_type: Uuid,
+    pub unique_guid: Uuid,
+    pub first_lba: u64,
+    pub last_lba: u64,
+    pub name: String,
+}
+
+/// A single 128-byte GPT partition entry, kept in its raw on-disk field
+/// layout so `to_bytes`/`from_bytes` are a direct transcription.
+#[derive(Debug, Clone, PartialEq)]
+struct GptPartitionEntry {
+    partition_type: Uuid,
+    unique_guid: Uuid,
+    first_lba: u64,
+    last_lba: u64,
+    attributes: u64,
+    name: String,
+}
+
+impl Default for GptPartitionEntry {
+    fn default() -> Self {
+        GptPartitionEntry {
+            partition_type: Uuid::nil(),
+            unique_guid: Uuid::nil(),
+            first_lba: 0,
+            last_lba: 0,
+            attributes: 0,
+            name: String::new(),
+        }
+    }
+}
+
+impl GptPartitionEntry {
+    fn is_used(&self) -> bool {
+        self.partition_type != Uuid::nil()
+    }
+
+    fn to_bytes(&self) -> [u8; ENTRY_SIZE as usize] {
+        let mut bytes = [0u8; ENTRY_SIZE as usize];
+        bytes[0..16].copy_from_slice(&self.partition_type.to_bytes_le());
+        bytes[16..32].copy_from_slice(&self.unique_guid.to_bytes_le());
+        bytes[32..40].copy_from_slice(&self.first_lba.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.last_lba.to_le_bytes());
+        bytes[48..56].copy_from_slice(&self.attributes.to_le_bytes());
+
+        let name_units: Vec<u16> = self.name.encode_utf16().collect();
+        for (i, unit) in name_units.iter().take(ENTRY_NAME_LEN / 2).enumerate() {
+            bytes[56 + i * 2..56 + i * 2 + 2].copy_from_slice(&unit.to_le_bytes());
+        }
+
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        let partition_type =
+            Uuid::from_bytes_le(bytes[0..16].try_into().unwrap());
+        let unique_guid = Uuid::from_bytes_le(bytes[16..32].try_into().unwrap());
+        let first_lba = u64::from_le_bytes(bytes[32..40].try_into().unwrap());
+        let last_lba = u64::from_le_bytes(bytes[40..48].try_into().unwrap());
+        let attributes = u64::from_le_bytes(bytes[48..56].try_into().unwrap());
+
+        let name_units: Vec<u16> = bytes[56..56 + ENTRY_NAME_LEN]
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .take_while(|&unit| unit != 0)
+            .collect();
+        let name = String::from_utf16_lossy(&name_units);
+
+        GptPartitionEntry {
+            partition_type,
+            unique_guid,
+            first_lba,
+            last_lba,
+            attributes,
+            name,
+        }
+    }
+}
+
+/// A GUID Partition Table: a protective MBR at LBA 0, a primary header and
+/// partition-entry array near the start of the disk, and a mirror-image
+/// backup entry array and header at its very end, the way the UEFI
+/// specification lays them out.
+///
+/// Kept in memory as the disk GUID plus a fixed-size array of
+/// [`GptPartitionEntry`] (unused slots carry a nil type GUID), the same
+/// fixed-array convention [`crate::mbr::Mbr`] uses for its four entries.
+#[derive(Debug, Clone, PartialEq)]
+pub struct PartitionTable {
+    disk_guid: Uuid,
+    entries: Vec<GptPartitionEntry>,
+}
+
+impl Default for PartitionTable {
+    fn default() -> Self {
+        PartitionTable {
+            disk_guid: Uuid::new_v4(),
+            entries: vec![GptPartitionEntry::default(); ENTRY_COUNT as usize],
+        }
+    }
+}
+
+impl PartitionTable {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads and validates both the primary and backup GPT structures from
+    /// `disk`, preferring the primary header but falling back to the backup
+    /// if the primary's signature or CRC32 doesn't check out.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::InvalidSectorSize` if the disk's sector size is
+    /// smaller than 512 bytes, or `DiskError::InvalidArgument`/
+    /// `DiskError::IntegrityMismatch` if neither the primary nor the backup
+    /// header validates.
+    pub fn read(disk: &mut dyn Disk) -> Result<Self, DiskError> {
+        let sector_size = disk.sector_size().as_usize();
+        if sector_size < 512 {
+            return Err(DiskError::InvalidSectorSize);
+        }
+
+        let backup_header_lba = disk.sector_count() - 1;
+
+        let primary = Self::read_at(disk, PRIMARY_HEADER_LBA, sector_size)
+            .and_then(|header| Self::read_entries(disk, &header, sector_size));
+
+        let (disk_guid, entries) = match primary {
+            Ok(result) => result,
+            Err(_) => {
+                let header = Self::read_at(disk, backup_header_lba, sector_size)?;
+                Self::read_entries(disk, &header, sector_size)?
+            }
+        };
+
+        Ok(PartitionTable { disk_guid, entries })
+    }
+
+    fn read_at(disk: &mut dyn Disk, lba: u64, sector_size: usize) -> Result<RawHeader, DiskError> {
+        let mut buf = vec![0u8; sector_size];
+        disk.read_sector(lba, &mut buf)?;
+        RawHeader::parse(&buf)
+    }
+
+    fn read_entries(
+        disk: &mut dyn Disk,
+        header: &RawHeader,
+        sector_size: usize,
+    ) -> Result<(Uuid, Vec<GptPartitionEntry>), DiskError> {
+        // The entries buffer below is always sized for `ENTRY_COUNT` entries, so a
+        // header claiming any other count (including a value large enough to overflow
+        // the multiplication that follows) can't be read against it safely.
+        if header.entry_count != ENTRY_COUNT {
+            return Err(DiskError::InvalidArgument);
+        }
+
+        let entries_bytes = (header.entry_count * ENTRY_SIZE) as usize;
+        let mut buf = vec![0u8; entries_array_sectors(sector_size) as usize * sector_size];
+        disk.read_sectors(
+            header.entries_lba,
+            entries_array_sectors(sector_size) as u32,
+            &mut buf,
+        )?;
+
+        if crc32fast::hash(&buf[..entries_bytes]) != header.entries_crc32 {
+            return Err(DiskError::IntegrityMismatch);
+        }
+
+        let entries = buf[..entries_bytes]
+            .chunks_exact(ENTRY_SIZE as usize)
+            .map(GptPartitionEntry::from_bytes)
+            .collect();
+
+        Ok((header.disk_guid, entries))
+    }
+
+    /// Serializes the protective MBR, primary header and entry array, and
+    /// backup entry array and header, writing all four structures to `disk`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::InvalidSectorSize` if the disk's sector size is
+    /// smaller than 512 bytes.
+    pub fn write(&self, disk: &mut dyn Disk) -> Result<(), DiskError> {
+        let sector_size = disk.sector_size().as_usize();
+        if sector_size < 512 {
+            return Err(DiskError::InvalidSectorSize);
+        }
+
+        let entries_sectors = entries_array_sectors(sector_size);
+        let total_sectors = disk.sector_count();
+        let backup_header_lba = total_sectors - 1;
+        let backup_entries_lba = backup_header_lba - entries_sectors;
+        let primary_entries_lba = PRIMARY_HEADER_LBA + 1;
+        let first_usable_lba = primary_entries_lba + entries_sectors;
+        let last_usable_lba = backup_entries_lba - 1;
+
+        let entries_bytes = self.entries_bytes();
+        let entries_crc32 = crc32fast::hash(&entries_bytes);
+
+        let primary = RawHeader {
+            current_lba: PRIMARY_HEADER_LBA,
+            backup_lba: backup_header_lba,
+            first_usable_lba,
+            last_usable_lba,
+            disk_guid: self.disk_guid,
+            entries_lba: primary_entries_lba,
+            entry_count: ENTRY_COUNT,
+            entries_crc32,
+        };
+        let backup = RawHeader {
+            current_lba: backup_header_lba,
+            backup_lba: PRIMARY_HEADER_LBA,
+            entries_lba: backup_entries_lba,
+            ..primary
+        };
+
+        Mbr::new().write(disk)?;
+        disk.write_sector(PRIMARY_HEADER_LBA, &pad_to_sector(&primary.to_bytes(), sector_size))?;
+        disk.write_sectors(primary_entries_lba, entries_sectors as u32, &entries_bytes)?;
+        disk.write_sectors(backup_entries_lba, entries_sectors as u32, &entries_bytes)?;
+        disk.write_sector(backup_header_lba, &pad_to_sector(&backup.to_bytes(), sector_size))?;
+
+        Ok(())
+    }
+
+    fn entries_bytes(&self) -> Vec<u8> {
+        let mut bytes = Vec::with_capacity(self.entries.len() * ENTRY_SIZE as usize);
+        for entry in &self.entries {
+            bytes.extend_from_slice(&entry.to_bytes());
+        }
+        bytes
+    }
+
+    /// Lists every in-use partition entry.
+    pub fn partitions(&self) -> Vec<PartitionInfo> {
+        self.entries
+            .iter()
+            .filter(|entry| entry.is_used())
+            .map(|entry| PartitionInfo {
+                partition_type: entry.partition_type,
+                unique_guid: entry.unique_guid,
+                first_lba: entry.first_lba,
+                last_lba: entry.last_lba,
+                name: entry.name.clone(),
+            })
+            .collect()
+    }
+
+    /// Adds a partition of `partition_type` spanning `sector_count` sectors
+    /// starting at `start_lba`, writes the resulting protective MBR, primary
+    /// GPT structures, and backup GPT structures to `disk`, and hands back a
+    /// [`Volume`] over the new partition's sectors so existing code (the FAT
+    /// formatter, for instance) can write directly into it.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::GroupTableCorrupt` if all 128 entry slots are
+    /// already in use, or whatever `write` returns for a disk whose sector
+    /// size doesn't support GPT.
+    pub fn add_partition<'a, D: Disk>(
+        &mut self,
+        disk: &'a mut D,
+        start_lba: u64,
+        sector_count: u64,
+        partition_type: Uuid,
+        name: &str,
+    ) -> Result<Volume<'a, D>, DiskError> {
+        let slot = self
+            .entries
+            .iter()
+            .position(|entry| !entry.is_used())
+            .ok_or(DiskError::GroupTableCorrupt)?;
+
+        self.entries[slot] = GptPartitionEntry {
+            partition_type,
+            unique_guid: Uuid::new_v4(),
+            first_lba: start_lba,
+            last_lba: start_lba + sector_count.saturating_sub(1),
+            attributes: 0,
+            name: name.to_string(),
+        };
+
+        self.write(&mut *disk)?;
+
+        Ok(Volume::new(disk, start_lba, sector_count))
+    }
+}
+
+/// The header fields this module actually computes or validates; the
+/// padding out to the sector's end is handled separately since it depends
+/// on the disk's sector size.
+struct RawHeader {
+    current_lba: u64,
+    backup_lba: u64,
+    first_usable_lba: u64,
+    last_usable_lba: u64,
+    disk_guid: Uuid,
+    entries_lba: u64,
+    entry_count: u32,
+    entries_crc32: u32,
+}
+
+impl RawHeader {
+    fn to_bytes(&self) -> [u8; HEADER_SIZE as usize] {
+        let mut bytes = [0u8; HEADER_SIZE as usize];
+        bytes[0..8].copy_from_slice(&SIGNATURE);
+        bytes[8..12].copy_from_slice(&REVISION.to_le_bytes());
+        bytes[12..16].copy_from_slice(&HEADER_SIZE.to_le_bytes());
+        // bytes[16..20] (header CRC32) stays zero until computed below.
+        bytes[24..32].copy_from_slice(&self.current_lba.to_le_bytes());
+        bytes[32..40].copy_from_slice(&self.backup_lba.to_le_bytes());
+        bytes[40..48].copy_from_slice(&self.first_usable_lba.to_le_bytes());
+        bytes[48..56].copy_from_slice(&self.last_usable_lba.to_le_bytes());
+        bytes[56..72].copy_from_slice(&self.disk_guid.to_bytes_le());
+        bytes[72..80].copy_from_slice(&self.entries_lba.to_le_bytes());
+        bytes[80..84].copy_from_slice(&self.entry_count.to_le_bytes());
+        bytes[84..88].copy_from_slice(&ENTRY_SIZE.to_le_bytes());
+        bytes[88..92].copy_from_slice(&self.entries_crc32.to_le_bytes());
+
+        let header_crc32 = crc32fast::hash(&bytes);
+        bytes[16..20].copy_from_slice(&header_crc32.to_le_bytes());
+
+        bytes
+    }
+
+    fn parse(sector: &[u8]) -> Result<Self, DiskError> {
+        if sector[0..8] != SIGNATURE {
+            return Err(DiskError::InvalidArgument);
+        }
+
+        let declared_crc32 = u32::from_le_bytes(sector[16..20].try_into().unwrap());
+        let mut header_only = sector[0..HEADER_SIZE as usize].to_vec();
+        header_only[16..20].copy_from_slice(&[0u8; 4]);
+        if crc32fast::hash(&header_only) != declared_crc32 {
+            return Err(DiskError::IntegrityMismatch);
+        }
+
+        Ok(RawHeader {
+            current_lba: u64::from_le_bytes(sector[24..32].try_into().unwrap()),
+            backup_lba: u64::from_le_bytes(sector[32..40].try_into().unwrap()),
+            first_usable_lba: u64::from_le_bytes(sector[40..48].try_into().unwrap()),
+            last_usable_lba: u64::from_le_bytes(sector[48..56].try_into().unwrap()),
+            disk_guid: Uuid::from_bytes_le(sector[56..72].try_into().unwrap()),
+            entries_lba: u64::from_le_bytes(sector[72..80].try_into().unwrap()),
+            entry_count: u32::from_le_bytes(sector[80..84].try_into().unwrap()),
+            entries_crc32: u32::from_le_bytes(sector[88..92].try_into().unwrap()),
+        })
+    }
+}
+
+/// Number of sectors the 128-entry, 128-byte-per-entry partition array
+/// occupies at a given sector size.
+fn entries_array_sectors(sector_size: usize) -> u64 {
+    ((ENTRY_COUNT * ENTRY_SIZE) as u64).div_ceil(sector_size as u64)
+}
+
+fn pad_to_sector(bytes: &[u8], sector_size: usize) -> Vec<u8> {
+    let mut sector = vec![0u8; sector_size];
+    sector[..bytes.len()].copy_from_slice(bytes);
+    sector
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::RawImage;
+    use crate::sectorsize::SectorSize;
+    use tempfile::tempdir;
+
+    fn test_disk() -> (tempfile::TempDir, RawImage) {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("gpt.img");
+        let disk = RawImage::new(&path, SectorSize::S512, 2048).unwrap();
+        (dir, disk)
+    }
+
+    #[test]
+    fn add_partition_round_trips_through_read() {
+        let (_dir, mut disk) = test_disk();
+        let mut table = PartitionTable::new();
+        let type_guid = Uuid::new_v4();
+
+        {
+            let mut volume = table
+                .add_partition(&mut disk, 100, 500, type_guid, "EFI SYSTEM")
+                .unwrap();
+            volume.write_sector(0, &[0xAB; 512]).unwrap();
+        }
+
+        let reread = PartitionTable::read(&mut disk).unwrap();
+        let partitions = reread.partitions();
+        assert_eq!(partitions.len(), 1);
+        assert_eq!(partitions[0].partition_type, type_guid);
+        assert_eq!(partitions[0].first_lba, 100);
+        assert_eq!(partitions[0].last_lba, 599);
+        assert_eq!(partitions[0].name, "EFI SYSTEM");
+
+        let mut buf = [0u8; 512];
+        disk.read_sector(100, &mut buf).unwrap();
+        assert_eq!(buf, [0xAB; 512]);
+    }
+
+    #[test]
+    fn read_rejects_a_corrupted_primary_by_falling_back_to_the_backup() {
+        let (_dir, mut disk) = test_disk();
+        let mut table = PartitionTable::new();
+        table
+            .add_partition(&mut disk, 100, 500, Uuid::new_v4(), "DATA")
+            .unwrap();
+
+        let corrupt = [0xFFu8; 512];
+        disk.write_sector(PRIMARY_HEADER_LBA, &corrupt).unwrap();
+
+        let reread = PartitionTable::read(&mut disk).unwrap();
+        assert_eq!(reread.partitions().len(), 1);
+    }
+
+    #[test]
+    fn read_entries_rejects_an_unsupported_entry_count_instead_of_panicking() {
+        let (_dir, mut disk) = test_disk();
+        let mut table = PartitionTable::new();
+        table
+            .add_partition(&mut disk, 100, 500, Uuid::new_v4(), "DATA")
+            .unwrap();
+
+        // Corrupt the primary header's `entry_count` field (bytes 80..84) to a value
+        // large enough that, without validation, `entry_count * ENTRY_SIZE` would
+        // overflow and the resulting slice would read out of the entries buffer's
+        // bounds. Recompute the header CRC32 so the corruption is only caught by the
+        // entry-count check, not the header's own checksum.
+        let sector_size = disk.sector_size().as_usize();
+        let mut sector = vec![0u8; sector_size];
+        disk.read_sector(PRIMARY_HEADER_LBA, &mut sector).unwrap();
+        sector[80..84].copy_from_slice(&u32::MAX.to_le_bytes());
+        sector[16..20].copy_from_slice(&[0u8; 4]);
+        let header_crc32 = crc32fast::hash(&sector[0..HEADER_SIZE as usize]);
+        sector[16..20].copy_from_slice(&header_crc32.to_le_bytes());
+        disk.write_sector(PRIMARY_HEADER_LBA, &sector).unwrap();
+
+        let header = PartitionTable::read_at(&mut disk, PRIMARY_HEADER_LBA, sector_size).unwrap();
+        assert_eq!(
+            PartitionTable::read_entries(&mut disk, &header, sector_size),
+            Err(DiskError::InvalidArgument)
+        );
+    }
+}
@@ -0,0 +1,208 @@
+use std::collections::BTreeMap;
+
+use crc32fast::Hasher as Crc32Hasher;
+use md5::{Digest as _, Md5};
+use sha2::{Digest as _, Sha256};
+
+use crate::error::DiskError;
+use crate::sectorsize::SectorSize;
+use crate::Disk;
+
+/// A half-open range of sectors, `[start_sector, start_sector + sector_count)`.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct SectorRange {
+    pub start_sector: u64,
+    pub sector_count: u64,
+}
+
+/// CRC32, MD5, and SHA-256 digests covering a single [`SectorRange`].
+#[derive(Clone, Debug, PartialEq)]
+pub struct RangeDigest {
+    pub range: SectorRange,
+    pub crc32: u32,
+    pub md5: [u8; 16],
+    pub sha256: [u8; 32],
+}
+
+fn digest_sector(lba: u64, data: &[u8]) -> RangeDigest {
+    RangeDigest {
+        range: SectorRange {
+            start_sector: lba,
+            sector_count: 1,
+        },
+        crc32: crc32fast::hash(data),
+        md5: Md5::digest(data).into(),
+        sha256: Sha256::digest(data).into(),
+    }
+}
+
+/// A verification manifest for a fully assembled disk image.
+///
+/// Holds one [`RangeDigest`] per sector, plus CRC32/MD5/SHA-256 digests of the whole
+/// image read in ascending LBA order. [`IntegrityManifest::verify`] rereads the image
+/// and confirms every digest still matches, giving reproducible-build guarantees and
+/// letting CI detect silent corruption of generated images.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct IntegrityManifest {
+    pub sectors: BTreeMap<u64, RangeDigest>,
+    pub whole_image_crc32: u32,
+    pub whole_image_md5: [u8; 16],
+    pub whole_image_sha256: [u8; 32],
+}
+
+impl IntegrityManifest {
+    /// Builds a manifest by rereading every sector of `disk`, in ascending LBA order,
+    /// and hashing it.
+    pub fn build<D: Disk>(disk: &mut D) -> Result<Self, DiskError> {
+        let sector_size = disk.sector_size().as_usize();
+        let sector_count = disk.sector_count();
+
+        let mut whole_crc32 = Crc32Hasher::new();
+        let mut whole_md5 = Md5::new();
+        let mut whole_sha256 = Sha256::new();
+        let mut sectors = BTreeMap::new();
+        let mut buf = vec![0u8; sector_size];
+
+        for lba in 0..sector_count {
+            disk.read_sector(lba, &mut buf)?;
+            whole_crc32.update(&buf);
+            whole_md5.update(&buf);
+            whole_sha256.update(&buf);
+            sectors.insert(lba, digest_sector(lba, &buf));
+        }
+
+        Ok(Self {
+            sectors,
+            whole_image_crc32: whole_crc32.finalize(),
+            whole_image_md5: whole_md5.finalize().into(),
+            whole_image_sha256: whole_sha256.finalize().into(),
+        })
+    }
+
+    /// Rereads `disk` sector-by-sector and confirms every digest in this manifest
+    /// still matches.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::IntegrityMismatch` as soon as a sector or the whole-image
+    /// digest disagrees with what's actually on disk.
+    pub fn verify<D: Disk>(&self, disk: &mut D) -> Result<(), DiskError> {
+        let rebuilt = Self::build(disk)?;
+        if &rebuilt != self {
+            return Err(DiskError::IntegrityMismatch);
+        }
+        Ok(())
+    }
+}
+
+/// Wraps a [`Disk`] and eagerly records a [`RangeDigest`] for every sector written
+/// through it, without waiting for a final verification pass.
+///
+/// This is the "incremental" half of the integrity subsystem: while a caller is busy
+/// writing out a freshly assembled image, `IntegrityTracker` keeps a running per-sector
+/// digest map up to date so that [`IntegrityTracker::finalize`] only has to compute
+/// per-sector digests for sectors that were never written through it. The whole-image
+/// digests are still computed in `finalize` by rereading every sector.
+pub struct IntegrityTracker<'a, D: Disk> {
+    disk: &'a mut D,
+    sectors: BTreeMap<u64, RangeDigest>,
+}
+
+impl<'a, D: Disk> IntegrityTracker<'a, D> {
+    pub fn new(disk: &'a mut D) -> Self {
+        Self {
+            disk,
+            sectors: BTreeMap::new(),
+        }
+    }
+
+    /// Writes `buf` to `lba` through the wrapped disk and records its digest.
+    pub fn write_sector(&mut self, lba: u64, buf: &[u8]) -> Result<(), DiskError> {
+        self.disk.write_sector(lba, buf)?;
+        self.sectors.insert(lba, digest_sector(lba, buf));
+        Ok(())
+    }
+
+    /// Folds the tracked per-sector digests into a complete [`IntegrityManifest`].
+    ///
+    /// Every sector is read back here to feed the whole-image digests. Sectors that
+    /// were never written through this tracker (for example, an image that
+    /// pre-allocates space it hasn't populated yet) also get their per-sector digest
+    /// from that read, so the resulting manifest always covers every sector on the disk.
+    pub fn finalize(mut self) -> Result<IntegrityManifest, DiskError> {
+        let sector_size: SectorSize = self.disk.sector_size();
+        let sector_count = self.disk.sector_count();
+        let mut buf = vec![0u8; sector_size.as_usize()];
+
+        let mut whole_crc32 = Crc32Hasher::new();
+        let mut whole_md5 = Md5::new();
+        let mut whole_sha256 = Sha256::new();
+
+        for lba in 0..sector_count {
+            self.disk.read_sector(lba, &mut buf)?;
+            whole_crc32.update(&buf);
+            whole_md5.update(&buf);
+            whole_sha256.update(&buf);
+            self.sectors
+                .entry(lba)
+                .or_insert_with(|| digest_sector(lba, &buf));
+        }
+
+        Ok(IntegrityManifest {
+            sectors: self.sectors,
+            whole_image_crc32: whole_crc32.finalize(),
+            whole_image_md5: whole_md5.finalize().into(),
+            whole_image_sha256: whole_sha256.finalize().into(),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::RawImage;
+    use tempfile::tempdir;
+
+    #[test]
+    fn manifest_roundtrips_on_unmodified_disk() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 4).unwrap();
+        disk.write_sector(0, &[0xAB; 512]).unwrap();
+
+        let manifest = IntegrityManifest::build(&mut disk).unwrap();
+        assert!(manifest.verify(&mut disk).is_ok());
+    }
+
+    #[test]
+    fn manifest_detects_corruption() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 4).unwrap();
+        disk.write_sector(0, &[0xAB; 512]).unwrap();
+
+        let manifest = IntegrityManifest::build(&mut disk).unwrap();
+        disk.write_sector(0, &[0xCD; 512]).unwrap();
+
+        assert!(matches!(
+            manifest.verify(&mut disk),
+            Err(DiskError::IntegrityMismatch)
+        ));
+    }
+
+    #[test]
+    fn tracker_matches_manifest_built_after_the_fact() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        {
+            let mut tracker = IntegrityTracker::new(&mut disk);
+            tracker.write_sector(0, &[0x11; 512]).unwrap();
+            tracker.write_sector(2, &[0x22; 512]).unwrap();
+            let tracked = tracker.finalize().unwrap();
+            let rebuilt = IntegrityManifest::build(&mut disk).unwrap();
+            assert_eq!(tracked, rebuilt);
+        }
+    }
+}
@@ -1,9 +1,19 @@
 use error::DiskError;
 use sectorsize::SectorSize;
 
+pub mod compressed;
+pub mod cursor;
+pub mod detect;
 pub mod error;
+pub mod geometry;
+pub mod gpt;
+pub mod integrity;
+pub mod mbr;
 pub mod raw;
+pub mod sector;
 pub mod sectorsize;
+pub mod split;
+pub mod vhd;
 pub mod volume;
 
 pub trait Disk {
@@ -12,4 +22,64 @@ pub trait Disk {
     fn ibmwipe(&mut self) -> Result<(), DiskError>;
     fn sector_count(&self) -> u64;
     fn sector_size(&self) -> SectorSize;
+
+    /// Reads `count` consecutive sectors starting at `lba` into `buf` in one call.
+    ///
+    /// The default implementation just loops over `read_sector`. Implementors backed
+    /// by a single seekable file (like `RawImage`) should override this with one seek
+    /// plus a single transfer across the whole span, since sector-by-sector I/O is
+    /// needlessly slow for multi-megabyte reads.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::BufferTooSmall` if `buf` is shorter than
+    /// `count * sector_size()`, or `DiskError::OutOfBounds` if `lba + count` exceeds
+    /// `sector_count()`. Both are checked before any sector is touched.
+    fn read_sectors(&mut self, lba: u64, count: u32, buf: &mut [u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size().as_usize();
+
+        if buf.len() < count as usize * sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba + count as u64 > self.sector_count() {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        for i in 0..count as u64 {
+            let offset = (i as usize) * sector_size;
+            self.read_sector(lba + i, &mut buf[offset..offset + sector_size])?;
+        }
+
+        Ok(())
+    }
+
+    /// Writes `count` consecutive sectors starting at `lba` from `buf` in one call.
+    ///
+    /// The default implementation just loops over `write_sector`. Implementors backed
+    /// by a single seekable file (like `RawImage`) should override this with one seek
+    /// plus a single transfer across the whole span, since sector-by-sector I/O is
+    /// needlessly slow for multi-megabyte writes.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::BufferTooSmall` if `buf` is shorter than
+    /// `count * sector_size()`, or `DiskError::OutOfBounds` if `lba + count` exceeds
+    /// `sector_count()`. Both are checked before any sector is touched.
+    fn write_sectors(&mut self, lba: u64, count: u32, buf: &[u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size().as_usize();
+
+        if buf.len() < count as usize * sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba + count as u64 > self.sector_count() {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        for i in 0..count as u64 {
+            let offset = (i as usize) * sector_size;
+            self.write_sector(lba + i, &buf[offset..offset + sector_size])?;
+        }
+
+        Ok(())
+    }
 }
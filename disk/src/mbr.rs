@@ -0,0 +1,390 @@
+use crate::error::DiskError;
+use crate::geometry::Geometry;
+use crate::Disk;
+
+/// Size in bytes of the bootstrap/boot-loader area preceding the partition table.
+const BOOTSTRAP_LEN: usize = 446;
+
+/// Number of primary partition entries an MBR supports.
+const PARTITION_COUNT: usize = 4;
+
+/// Size in bytes of a single partition table entry.
+const ENTRY_LEN: usize = 16;
+
+/// The two-byte `0x55AA` boot signature, stored little-endian as the last two bytes
+/// of the sector.
+const SIGNATURE: [u8; 2] = [0x55, 0xAA];
+
+/// Legacy CHS addressing tops out at these values; a partition extending beyond them
+/// gets its CHS fields clamped to this maximum rather than wrapping, exactly as real
+/// partitioning tools do (the true location is still recoverable from the LBA fields).
+const MAX_CHS_CYLINDER: usize = 1023;
+const MAX_CHS_HEAD: usize = 254;
+const MAX_CHS_SECTOR: usize = 63;
+
+/// Partition type byte meaning "this entry is unused".
+const TYPE_EMPTY: u8 = 0x00;
+
+/// Boot flag byte meaning "this is the active/bootable partition".
+const BOOT_ACTIVE: u8 = 0x80;
+
+/// One entry in an MBR partition table.
+///
+/// `start_chs`/`end_chs` are the packed 3-byte CHS encodings as produced by
+/// `chs_to_mbr_bytes` (the same bit layout `Geometry::to_mbr_bytes` uses); they are
+/// present purely for legacy BIOS compatibility; `start_lba`/`sector_count` are what
+/// every modern consumer actually uses to locate the partition.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub struct PartitionEntry {
+    pub boot_flag: u8,
+    pub start_chs: [u8; 3],
+    pub partition_type: u8,
+    pub end_chs: [u8; 3],
+    pub start_lba: u32,
+    pub sector_count: u32,
+}
+
+impl PartitionEntry {
+    /// Whether this entry describes an actual partition, as opposed to an unused slot.
+    pub fn is_used(&self) -> bool {
+        self.partition_type != TYPE_EMPTY
+    }
+
+    pub fn is_bootable(&self) -> bool {
+        self.boot_flag == BOOT_ACTIVE
+    }
+
+    fn to_bytes(self) -> [u8; ENTRY_LEN] {
+        let mut bytes = [0u8; ENTRY_LEN];
+        bytes[0] = self.boot_flag;
+        bytes[1..4].copy_from_slice(&self.start_chs);
+        bytes[4] = self.partition_type;
+        bytes[5..8].copy_from_slice(&self.end_chs);
+        bytes[8..12].copy_from_slice(&self.start_lba.to_le_bytes());
+        bytes[12..16].copy_from_slice(&self.sector_count.to_le_bytes());
+        bytes
+    }
+
+    fn from_bytes(bytes: &[u8]) -> Self {
+        PartitionEntry {
+            boot_flag: bytes[0],
+            start_chs: [bytes[1], bytes[2], bytes[3]],
+            partition_type: bytes[4],
+            end_chs: [bytes[5], bytes[6], bytes[7]],
+            start_lba: u32::from_le_bytes(bytes[8..12].try_into().unwrap()),
+            sector_count: u32::from_le_bytes(bytes[12..16].try_into().unwrap()),
+        }
+    }
+}
+
+/// A Master Boot Record: the 446-byte bootstrap area, four primary partition
+/// entries, and the `0x55AA` boot signature, layered over any [`Disk`] at LBA 0.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Mbr {
+    bootstrap: [u8; BOOTSTRAP_LEN],
+    entries: [PartitionEntry; PARTITION_COUNT],
+}
+
+impl Default for Mbr {
+    fn default() -> Self {
+        Mbr {
+            bootstrap: [0u8; BOOTSTRAP_LEN],
+            entries: [PartitionEntry::default(); PARTITION_COUNT],
+        }
+    }
+}
+
+impl Mbr {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Reads and parses the MBR from LBA 0 of `disk`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::InvalidSectorSize` if the disk's sector size is smaller
+    /// than 512 bytes (an MBR can't fit), or `DiskError::InvalidArgument` if the
+    /// trailing `0x55AA` signature is missing.
+    pub fn read(disk: &mut dyn Disk) -> Result<Self, DiskError> {
+        let sector_size = disk.sector_size().as_usize();
+        if sector_size < 512 {
+            return Err(DiskError::InvalidSectorSize);
+        }
+
+        let mut buf = vec![0u8; sector_size];
+        disk.read_sector(0, &mut buf)?;
+
+        if buf[510..512] != SIGNATURE {
+            return Err(DiskError::InvalidArgument);
+        }
+
+        let mut bootstrap = [0u8; BOOTSTRAP_LEN];
+        bootstrap.copy_from_slice(&buf[0..BOOTSTRAP_LEN]);
+
+        let mut entries = [PartitionEntry::default(); PARTITION_COUNT];
+        for (i, entry) in entries.iter_mut().enumerate() {
+            let offset = BOOTSTRAP_LEN + i * ENTRY_LEN;
+            *entry = PartitionEntry::from_bytes(&buf[offset..offset + ENTRY_LEN]);
+        }
+
+        Ok(Mbr { bootstrap, entries })
+    }
+
+    /// Serializes this MBR and writes it to LBA 0 of `disk`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::InvalidSectorSize` if the disk's sector size is smaller
+    /// than 512 bytes.
+    pub fn write(&self, disk: &mut dyn Disk) -> Result<(), DiskError> {
+        let sector_size = disk.sector_size().as_usize();
+        if sector_size < 512 {
+            return Err(DiskError::InvalidSectorSize);
+        }
+
+        let mut buf = vec![0u8; sector_size];
+        buf[0..BOOTSTRAP_LEN].copy_from_slice(&self.bootstrap);
+
+        for (i, entry) in self.entries.iter().enumerate() {
+            let offset = BOOTSTRAP_LEN + i * ENTRY_LEN;
+            buf[offset..offset + ENTRY_LEN].copy_from_slice(&entry.to_bytes());
+        }
+
+        buf[510..512].copy_from_slice(&SIGNATURE);
+
+        disk.write_sector(0, &buf)
+    }
+
+    /// Iterates over the partition entries that are actually in use, paired with
+    /// their slot index (0-3).
+    pub fn partitions(&self) -> impl Iterator<Item = (usize, &PartitionEntry)> {
+        self.entries
+            .iter()
+            .enumerate()
+            .filter(|(_, entry)| entry.is_used())
+    }
+
+    /// Adds a primary partition of `partition_type` spanning `sector_count` sectors
+    /// starting at `start_lba`, in the first free slot.
+    ///
+    /// When `geometry` is supplied, the entry's start/end CHS fields are computed
+    /// from `start_lba` and `sector_count` via `Geometry::lba_to_chs`, clamped to the
+    /// legacy 1023/254/63 maximum for any address CHS can't represent. Without a
+    /// geometry, the CHS fields are left zeroed; LBA-only consumers don't need them.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::GroupTableCorrupt` if all four slots are already in use.
+    pub fn add_partition(
+        &mut self,
+        geometry: Option<&Geometry>,
+        partition_type: u8,
+        start_lba: u32,
+        sector_count: u32,
+        bootable: bool,
+    ) -> Result<usize, DiskError> {
+        let slot = self
+            .entries
+            .iter()
+            .position(|entry| !entry.is_used())
+            .ok_or(DiskError::GroupTableCorrupt)?;
+
+        let end_lba = start_lba + sector_count.saturating_sub(1);
+
+        let (start_chs, end_chs) = match geometry {
+            Some(geometry) => (
+                chs_for_lba(geometry, start_lba as u64),
+                chs_for_lba(geometry, end_lba as u64),
+            ),
+            None => ([0u8; 3], [0u8; 3]),
+        };
+
+        self.entries[slot] = PartitionEntry {
+            boot_flag: if bootable { BOOT_ACTIVE } else { 0x00 },
+            start_chs,
+            partition_type,
+            end_chs,
+            start_lba,
+            sector_count,
+        };
+
+        Ok(slot)
+    }
+
+    /// Adds the first primary partition at the conventional one-track offset:
+    /// DOS `FDISK` and every period-correct partitioning tool leave the first
+    /// track of the disk (`geometry.sectors()` sectors) unused before the
+    /// partition starts, rather than butting it up against LBA 0. A thin
+    /// convenience over [`Mbr::add_partition`] for that common case.
+    ///
+    /// # Errors
+    ///
+    /// Same as `add_partition`.
+    pub fn add_first_partition(
+        &mut self,
+        geometry: &Geometry,
+        partition_type: u8,
+        sector_count: u32,
+        bootable: bool,
+    ) -> Result<usize, DiskError> {
+        let start_lba = geometry.sectors() as u32;
+        self.add_partition(
+            Some(geometry),
+            partition_type,
+            start_lba,
+            sector_count,
+            bootable,
+        )
+    }
+
+    /// Clears the partition entry in `slot` (0-3), freeing it for reuse.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::OutOfBounds` if `slot` isn't a valid partition index.
+    pub fn delete_partition(&mut self, slot: usize) -> Result<(), DiskError> {
+        self.entries
+            .get_mut(slot)
+            .ok_or(DiskError::OutOfBounds)?
+            .clone_from(&PartitionEntry::default());
+        Ok(())
+    }
+}
+
+/// Converts an LBA into its packed 3-byte MBR CHS encoding under `geometry`,
+/// clamping to the legacy 1023/254/63 maximum when the LBA falls outside what CHS
+/// addressing can represent.
+fn chs_for_lba(geometry: &Geometry, lba: u64) -> [u8; 3] {
+    let (cylinder, head, sector) = geometry
+        .lba_to_chs(lba)
+        .unwrap_or((MAX_CHS_CYLINDER, MAX_CHS_HEAD, MAX_CHS_SECTOR));
+
+    let (cylinder, head, sector) = if cylinder > MAX_CHS_CYLINDER {
+        (MAX_CHS_CYLINDER, MAX_CHS_HEAD, MAX_CHS_SECTOR)
+    } else {
+        (cylinder, head, sector)
+    };
+
+    chs_to_mbr_bytes(cylinder, head, sector)
+}
+
+/// Packs a cylinder/head/sector triple into the 3-byte MBR encoding: head, then the
+/// sector (lower 6 bits) combined with the cylinder's high 2 bits, then the
+/// cylinder's low 8 bits. Mirrors `Geometry::to_mbr_bytes`, parameterized over a
+/// specific CHS triple rather than a whole-disk geometry.
+fn chs_to_mbr_bytes(cylinder: usize, head: usize, sector: usize) -> [u8; 3] {
+    let sector_bits = (sector as u8) & 0b0011_1111;
+    let cylinder_byte = (cylinder & 0xFF) as u8;
+    let cylinder_overflow_bits = ((cylinder >> 8) & 0b11) as u8;
+    let sector_byte = sector_bits | (cylinder_overflow_bits << 6);
+
+    [head as u8, sector_byte, cylinder_byte]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::raw::RawImage;
+    use crate::sectorsize::SectorSize;
+    use tempfile::tempdir;
+
+    #[test]
+    fn add_and_iterate_partitions() {
+        let mut mbr = Mbr::new();
+        let slot = mbr.add_partition(None, 0x0C, 2048, 65536, true).unwrap();
+        assert_eq!(slot, 0);
+
+        let used: Vec<_> = mbr.partitions().collect();
+        assert_eq!(used.len(), 1);
+        assert_eq!(used[0].1.start_lba, 2048);
+        assert_eq!(used[0].1.sector_count, 65536);
+        assert!(used[0].1.is_bootable());
+    }
+
+    #[test]
+    fn add_partition_fills_chs_from_geometry() {
+        let geometry = Geometry::new(100, 16, 63).unwrap();
+        let mut mbr = Mbr::new();
+        mbr.add_partition(Some(&geometry), 0x0C, 63, 100, false).unwrap();
+
+        let (_, entry) = mbr.partitions().next().unwrap();
+        assert_ne!(entry.start_chs, [0u8; 3]);
+        assert_ne!(entry.end_chs, [0u8; 3]);
+    }
+
+    #[test]
+    fn add_partition_clamps_chs_beyond_legacy_range() {
+        // Geometry pegged at the legacy CHS maximum (as real tools do for disks
+        // larger than it can address), with a partition that runs past its total
+        // addressable sector count.
+        let geometry = Geometry::new(1024, 255, 63).unwrap();
+        let total_sectors = 1024u32 * 255 * 63;
+        let mut mbr = Mbr::new();
+        mbr.add_partition(Some(&geometry), 0x0C, 0, total_sectors + 1000, false)
+            .unwrap();
+
+        let (_, entry) = mbr.partitions().next().unwrap();
+        assert_eq!(
+            entry.end_chs,
+            chs_to_mbr_bytes(MAX_CHS_CYLINDER, MAX_CHS_HEAD, MAX_CHS_SECTOR)
+        );
+    }
+
+    #[test]
+    fn first_partition_starts_one_track_in() {
+        let geometry = Geometry::new(100, 16, 63).unwrap();
+        let mut mbr = Mbr::new();
+        mbr.add_first_partition(&geometry, 0x06, 1000, true)
+            .unwrap();
+
+        let (_, entry) = mbr.partitions().next().unwrap();
+        assert_eq!(entry.start_lba, geometry.sectors() as u32);
+    }
+
+    #[test]
+    fn fourth_partition_fills_last_slot_and_fifth_fails() {
+        let mut mbr = Mbr::new();
+        for i in 0..4 {
+            mbr.add_partition(None, 0x0C, i * 1000, 500, false).unwrap();
+        }
+        assert_eq!(
+            mbr.add_partition(None, 0x0C, 5000, 500, false),
+            Err(DiskError::GroupTableCorrupt)
+        );
+    }
+
+    #[test]
+    fn delete_partition_frees_the_slot() {
+        let mut mbr = Mbr::new();
+        let slot = mbr.add_partition(None, 0x0C, 2048, 65536, false).unwrap();
+        mbr.delete_partition(slot).unwrap();
+        assert_eq!(mbr.partitions().count(), 0);
+    }
+
+    #[test]
+    fn write_then_read_roundtrips_through_a_disk() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 128).unwrap();
+
+        let mut mbr = Mbr::new();
+        mbr.add_partition(None, 0x0C, 1, 100, true).unwrap();
+        mbr.write(&mut disk).unwrap();
+
+        let reread = Mbr::read(&mut disk).unwrap();
+        assert_eq!(reread, mbr);
+    }
+
+    #[test]
+    fn read_rejects_missing_signature() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+        let mut disk = RawImage::new(&path, SectorSize::S512, 1).unwrap();
+
+        assert!(matches!(
+            Mbr::read(&mut disk),
+            Err(DiskError::InvalidArgument)
+        ));
+    }
+}
@@ -17,7 +17,7 @@ impl RawImage {
         let file = File::options()
             .read(true)
             .write(true)
-            .create_new(true) 
+            .create_new(true)
             .open(path)
             .map_err(|_| DiskError::FileAlreadyExists)?;
 
@@ -31,6 +31,67 @@ impl RawImage {
             sector_count,
         })
     }
+
+    /// Sector sizes tried, in order, when inferring the native sector size of an
+    /// existing flat image or block device from its length alone.
+    const PROBE_SECTOR_SIZES: [SectorSize; 4] = [
+        SectorSize::S512,
+        SectorSize::S1024,
+        SectorSize::S2048,
+        SectorSize::S4096,
+    ];
+
+    /// Opens an existing flat image or block device at `path`, inferring its sector
+    /// size from its length.
+    ///
+    /// The file is not truncated or extended: whatever is already there is treated as
+    /// the disk's contents. Sector size is inferred by testing each of
+    /// `Self::PROBE_SECTOR_SIZES` in turn for an even division into the file length;
+    /// the first one that divides evenly wins.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::FileOpenFailed` if `path` can't be opened, or
+    /// `DiskError::InvalidFileSize` if the length doesn't divide evenly by any probed
+    /// sector size.
+    pub fn open_existing(path: &Path) -> Result<Self, DiskError> {
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|_| DiskError::FileOpenFailed)?;
+
+        let len = file
+            .metadata()
+            .map_err(|_| DiskError::FileMetadataFailed)?
+            .len();
+
+        let sector_size = Self::PROBE_SECTOR_SIZES
+            .into_iter()
+            .find(|size| len % size.as_u64() == 0)
+            .ok_or(DiskError::InvalidFileSize)?;
+
+        Ok(Self {
+            file,
+            sector_size,
+            sector_count: len / sector_size.as_u64(),
+        })
+    }
+}
+
+impl TryFrom<&Path> for Box<dyn Disk> {
+    type Error = DiskError;
+
+    /// Opens an existing flat image or block device at `path` and returns it boxed
+    /// as a [`Disk`]. No cursor is created here; a [`crate::cursor::BlockCursor`]
+    /// wrapped around the result starts at block 0.
+    ///
+    /// This is the read side of the abstraction `RawImage::new` provides for
+    /// creation: it turns a pre-existing DOS image into something that can be
+    /// mounted, inspected, and patched rather than only generated from scratch.
+    fn try_from(path: &Path) -> Result<Self, Self::Error> {
+        Ok(Box::new(RawImage::open_existing(path)?))
+    }
 }
 
 impl Disk for RawImage {
@@ -86,6 +147,55 @@ impl Disk for RawImage {
     fn sector_size(&self) -> SectorSize {
         self.sector_size
     }
+
+    /// Overrides the trait default to do one `seek` plus a single `read_exact` across
+    /// the whole `count`-sector span, instead of looping sector-by-sector.
+    fn read_sectors(&mut self, lba: u64, count: u32, buf: &mut [u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+        let len = count as usize * sector_size;
+
+        if buf.len() < len {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba + count as u64 > self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        let offset = lba * sector_size as u64;
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file
+            .read_exact(&mut buf[..len])
+            .map_err(|_| DiskError::ReadFailed)?;
+
+        Ok(())
+    }
+
+    /// Overrides the trait default to do one `seek` plus a single `write_all` across
+    /// the whole `count`-sector span, instead of looping sector-by-sector.
+    fn write_sectors(&mut self, lba: u64, count: u32, buf: &[u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+        let len = count as usize * sector_size;
+
+        if buf.len() < len {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba + count as u64 > self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        let offset = lba * sector_size as u64;
+        self.file
+            .seek(SeekFrom::Start(offset))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file
+            .write_all(&buf[..len])
+            .map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)?;
+
+        Ok(())
+    }
 }
 
 #[cfg(test)]
@@ -210,4 +320,91 @@ mod tests {
         assert_eq!(raw.sector_size(), sector_size);
         assert_eq!(raw.sector_count(), sector_count);
     }
+
+    #[test]
+    fn open_existing_infers_sector_size_and_count() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        RawImage::new(&path, SectorSize::S512, 4).unwrap();
+
+        let opened = RawImage::open_existing(&path).unwrap();
+        assert_eq!(opened.sector_size(), SectorSize::S512);
+        assert_eq!(opened.sector_count(), 4);
+    }
+
+    #[test]
+    fn open_existing_fails_on_unaligned_length() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        let file = File::create(&path).unwrap();
+        file.set_len(513).unwrap();
+
+        let result = RawImage::open_existing(&path);
+        assert!(matches!(result, Err(DiskError::InvalidFileSize)));
+    }
+
+    #[test]
+    fn try_from_path_yields_boxed_disk() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        RawImage::new(&path, SectorSize::S512, 2).unwrap();
+
+        let mut disk: Box<dyn Disk> = Box::<dyn Disk>::try_from(path.as_path()).unwrap();
+        assert_eq!(disk.sector_count(), 2);
+        assert_eq!(disk.sector_size(), SectorSize::S512);
+    }
+
+    #[test]
+    fn write_and_read_sectors_batched_roundtrip() {
+        let sector_size = SectorSize::S512;
+        let sector_count = 4;
+
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        let mut raw = RawImage::new(&path, sector_size, sector_count).unwrap();
+
+        let write_data = [0xCD; 512 * 3];
+        raw.write_sectors(1, 3, &write_data).unwrap();
+
+        let mut read_buf = [0x00; 512 * 3];
+        raw.read_sectors(1, 3, &mut read_buf).unwrap();
+
+        assert_eq!(write_data, read_buf, "Data mismatch");
+    }
+
+    #[test]
+    fn read_sectors_out_of_bounds_fails() {
+        let sector_size = SectorSize::S512;
+        let sector_count = 2;
+
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        let mut raw = RawImage::new(&path, sector_size, sector_count).unwrap();
+
+        let mut buf = [0x00; 512 * 3];
+        let result = raw.read_sectors(0, 3, &mut buf);
+
+        assert!(matches!(result, Err(DiskError::OutOfBounds)));
+    }
+
+    #[test]
+    fn write_sectors_buffer_too_small_fails() {
+        let sector_size = SectorSize::S512;
+        let sector_count = 4;
+
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.img");
+
+        let mut raw = RawImage::new(&path, sector_size, sector_count).unwrap();
+
+        let buf = [0xAB; 512]; // Only enough for one sector
+        let result = raw.write_sectors(0, 2, &buf);
+
+        assert!(matches!(result, Err(DiskError::BufferTooSmall)));
+    }
 }
@@ -38,6 +38,10 @@ impl SectorSize {
     pub fn get(&self) -> usize {
         self.as_usize()
     }
+
+    pub fn as_u64(&self) -> u64 {
+        self.as_usize() as u64
+    }
 }
 
 impl fmt::Display for SectorSize {
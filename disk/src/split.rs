@@ -0,0 +1,237 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+
+use crate::error::DiskError;
+use crate::sectorsize::SectorSize;
+use crate::Disk;
+
+/// A disk image split across several fixed-size chunk files (e.g. `disk.000`,
+/// `disk.001`, ...), presenting a single contiguous `sector_count` to callers.
+///
+/// Chunk files are named by appending a zero-padded, dot-separated three-digit
+/// index to the path template passed to `new()` (so a template of `disk.img` yields
+/// `disk.img.000`, `disk.img.001`, and so on). Each `read_sector`/`write_sector` call
+/// is routed to the one chunk file that wholly contains it, since the configured
+/// per-chunk byte limit is always rounded down to a whole number of sectors.
+/// Chunk files are opened (and created) lazily, the first time a sector inside them
+/// is actually touched, rather than all up front like `RawImage` does for its single
+/// file.
+#[derive(Debug)]
+pub struct SplitImage {
+    path_template: PathBuf,
+    sector_size: SectorSize,
+    sector_count: u64,
+    chunk_bytes: u64,
+    chunks: Vec<Option<File>>,
+}
+
+impl SplitImage {
+    /// Creates a new split image described by `path_template`, `sector_size`,
+    /// `sector_count`, and a `max_chunk_bytes` ceiling per chunk file.
+    ///
+    /// No chunk file is created yet; each is opened the first time a sector inside
+    /// it is read or written.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::InvalidArgument` if `max_chunk_bytes` is smaller than a
+    /// single sector.
+    pub fn new(
+        path_template: &Path,
+        sector_size: SectorSize,
+        sector_count: u64,
+        max_chunk_bytes: u64,
+    ) -> Result<Self, DiskError> {
+        if max_chunk_bytes < sector_size.as_u64() {
+            return Err(DiskError::InvalidArgument);
+        }
+
+        // Round down to a whole number of sectors so a single-sector transfer is
+        // always wholly inside one chunk, never straddling a boundary.
+        let chunk_sectors = max_chunk_bytes / sector_size.as_u64();
+        let chunk_bytes = chunk_sectors * sector_size.as_u64();
+        let chunk_count = sector_count.div_ceil(chunk_sectors);
+
+        Ok(Self {
+            path_template: path_template.to_path_buf(),
+            sector_size,
+            sector_count,
+            chunk_bytes,
+            chunks: (0..chunk_count).map(|_| None).collect(),
+        })
+    }
+
+    /// Path of the chunk file at `index`: the path template with `.NNN` appended.
+    fn chunk_path(&self, index: u64) -> PathBuf {
+        let mut name = self.path_template.clone().into_os_string();
+        name.push(format!(".{index:03}"));
+        PathBuf::from(name)
+    }
+
+    /// Length in bytes that chunk `index` should be: `chunk_bytes`, except for a
+    /// final chunk that may be shorter.
+    fn chunk_len(&self, index: u64) -> u64 {
+        let total_bytes = self.sector_count * self.sector_size.as_u64();
+        let start = index * self.chunk_bytes;
+        u64::min(self.chunk_bytes, total_bytes - start)
+    }
+
+    /// Splits a global byte offset into its `(chunk_index, offset_within_chunk)`.
+    fn chunk_for_offset(&self, byte_offset: u64) -> (u64, u64) {
+        (byte_offset / self.chunk_bytes, byte_offset % self.chunk_bytes)
+    }
+
+    /// Returns the open file handle for chunk `index`, creating and sizing it first
+    /// if this is the first time it's been touched.
+    fn ensure_chunk(&mut self, index: u64) -> Result<&mut File, DiskError> {
+        if self.chunks[index as usize].is_none() {
+            let path = self.chunk_path(index);
+            let file = File::options()
+                .read(true)
+                .write(true)
+                .create(true)
+                .truncate(false)
+                .open(&path)
+                .map_err(|_| DiskError::FileOpenFailed)?;
+            file.set_len(self.chunk_len(index)).map_err(|_| DiskError::IoError)?;
+            self.chunks[index as usize] = Some(file);
+        }
+
+        Ok(self.chunks[index as usize].as_mut().unwrap())
+    }
+}
+
+impl Disk for SplitImage {
+    fn read_sector(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+
+        if buf.len() < sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba >= self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        let byte_offset = lba * sector_size as u64;
+        let (chunk_index, offset_in_chunk) = self.chunk_for_offset(byte_offset);
+
+        let file = self.ensure_chunk(chunk_index)?;
+        file.seek(SeekFrom::Start(offset_in_chunk)).map_err(|_| DiskError::SeekFailed)?;
+        file.read_exact(&mut buf[..sector_size]).map_err(|_| DiskError::ReadFailed)?;
+
+        Ok(())
+    }
+
+    fn write_sector(&mut self, lba: u64, buf: &[u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+
+        if buf.len() < sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba >= self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        let byte_offset = lba * sector_size as u64;
+        let (chunk_index, offset_in_chunk) = self.chunk_for_offset(byte_offset);
+
+        let file = self.ensure_chunk(chunk_index)?;
+        file.seek(SeekFrom::Start(offset_in_chunk)).map_err(|_| DiskError::SeekFailed)?;
+        file.write_all(&buf[..sector_size]).map_err(|_| DiskError::WriteFailed)?;
+        file.flush().map_err(|_| DiskError::FlushFailed)?;
+
+        Ok(())
+    }
+
+    fn ibmwipe(&mut self) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+        let ibm_empty_sector = vec![0xF6u8; sector_size];
+        for sector in 0..self.sector_count {
+            self.write_sector(sector, &ibm_empty_sector)?;
+        }
+        Ok(())
+    }
+
+    fn sector_count(&self) -> u64 {
+        self.sector_count
+    }
+
+    fn sector_size(&self) -> SectorSize {
+        self.sector_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn write_and_read_sector_within_a_single_chunk() {
+        let tmpdir = tempdir().unwrap();
+        let template = tmpdir.path().join("disk.img");
+        // 2 sectors per chunk (1024 bytes), 4 sectors total => 2 chunks.
+        let mut image = SplitImage::new(&template, SectorSize::S512, 4, 1024).unwrap();
+
+        let data = [0xAB; 512];
+        image.write_sector(0, &data).unwrap();
+
+        let mut readback = [0u8; 512];
+        image.read_sector(0, &mut readback).unwrap();
+        assert_eq!(readback, data);
+    }
+
+    #[test]
+    fn sectors_land_in_the_expected_chunk_file() {
+        let tmpdir = tempdir().unwrap();
+        let template = tmpdir.path().join("disk.img");
+        let mut image = SplitImage::new(&template, SectorSize::S512, 4, 1024).unwrap();
+
+        image.write_sector(0, &[0x11; 512]).unwrap();
+        image.write_sector(2, &[0x22; 512]).unwrap();
+
+        assert!(tmpdir.path().join("disk.img.000").exists());
+        assert!(tmpdir.path().join("disk.img.001").exists());
+    }
+
+    #[test]
+    fn writes_to_different_chunks_do_not_interfere() {
+        let tmpdir = tempdir().unwrap();
+        let template = tmpdir.path().join("disk.img");
+        let mut image = SplitImage::new(&template, SectorSize::S512, 4, 1024).unwrap();
+
+        image.write_sector(1, &[0xAA; 512]).unwrap();
+        image.write_sector(2, &[0xBB; 512]).unwrap();
+
+        let mut first_chunk_tail = [0u8; 512];
+        image.read_sector(1, &mut first_chunk_tail).unwrap();
+        assert_eq!(first_chunk_tail, [0xAA; 512]);
+
+        let mut second_chunk_head = [0u8; 512];
+        image.read_sector(2, &mut second_chunk_head).unwrap();
+        assert_eq!(second_chunk_head, [0xBB; 512]);
+    }
+
+    #[test]
+    fn out_of_bounds_sector_fails() {
+        let tmpdir = tempdir().unwrap();
+        let template = tmpdir.path().join("disk.img");
+        let mut image = SplitImage::new(&template, SectorSize::S512, 4, 1024).unwrap();
+
+        let mut buf = [0u8; 512];
+        assert!(matches!(
+            image.read_sector(10, &mut buf),
+            Err(DiskError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn chunk_smaller_than_a_sector_is_rejected() {
+        let tmpdir = tempdir().unwrap();
+        let template = tmpdir.path().join("disk.img");
+
+        let result = SplitImage::new(&template, SectorSize::S512, 4, 256);
+        assert!(matches!(result, Err(DiskError::InvalidArgument)));
+    }
+}
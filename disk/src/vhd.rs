@@ -0,0 +1,653 @@
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use uuid::Uuid;
+
+use crate::error::DiskError;
+use crate::geometry::Geometry;
+use crate::sectorsize::SectorSize;
+use crate::Disk;
+
+/// Length in bytes of a VHD footer, as fixed by the Microsoft VHD specification.
+const FOOTER_LEN: usize = 512;
+
+/// Length in bytes of a dynamic disk header, as fixed by the Microsoft VHD specification.
+const DYNAMIC_HEADER_LEN: usize = 1024;
+
+/// Block size used for dynamic disks: 2 MiB, the value every mainstream VHD tool uses.
+const BLOCK_SIZE: u64 = 2 * 1024 * 1024;
+
+/// Seconds between the Unix epoch and the VHD epoch (2000-01-01T00:00:00Z), which is
+/// what VHD timestamps are measured from.
+const VHD_EPOCH_OFFSET: u64 = 946_684_800;
+
+/// Sentinel BAT entry meaning "this block has never been written".
+const BAT_UNALLOCATED: u32 = 0xFFFF_FFFF;
+
+/// Whether a [`VhdImage`] is a flat, fully-allocated image or a sparse one that grows
+/// its Block Allocation Table as blocks are first written.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum VhdKind {
+    Fixed,
+    Dynamic,
+}
+
+/// A Microsoft VHD (Connectix) disk image implementing the [`Disk`] trait, readable
+/// and mountable by Virtual PC, Hyper-V, and QEMU.
+///
+/// A fixed image is laid out as a flat, fully-allocated data region followed by a
+/// 512-byte footer. A dynamic image instead starts with a copy of the footer, then a
+/// dynamic-disk header, then a Block Allocation Table (BAT) of 4-byte big-endian
+/// sector offsets (one per [`BLOCK_SIZE`]-sized block, [`BAT_UNALLOCATED`] if the
+/// block has never been written), then the blocks themselves as they get allocated.
+/// Each allocated block is prefixed with a sector-occupancy bitmap so a block that's
+/// been touched at all can still report individual unwritten sectors as zero.
+#[derive(Debug)]
+pub struct VhdImage {
+    file: File,
+    sector_size: SectorSize,
+    sector_count: u64,
+    geometry: Geometry,
+    kind: VhdKind,
+    /// In-memory mirror of the on-disk BAT. Empty for fixed images.
+    bat: Vec<u32>,
+    /// Byte offset of the BAT within the file. Meaningless for fixed images.
+    bat_offset: u64,
+    /// Byte offset of the data region (blocks for dynamic, sectors for fixed).
+    data_offset: u64,
+    /// Number of this image's logical sectors that make up one block.
+    sectors_per_block: u64,
+    /// Number of sector-sized units occupied by a block's occupancy bitmap.
+    bitmap_sectors: u64,
+}
+
+impl VhdImage {
+    /// Creates a new, fully-allocated fixed VHD image backed by `path`.
+    ///
+    /// The data region is zero-filled up front and a matching footer is written at
+    /// the end, exactly mirroring how `RawImage::new` allocates its flat image.
+    pub fn create_fixed(
+        path: &Path,
+        geometry: Geometry,
+        sector_size: SectorSize,
+    ) -> Result<Self, DiskError> {
+        let sector_count = geometry.cylinders() as u64 * geometry.heads() as u64 * geometry.sectors() as u64;
+
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map_err(|_| DiskError::FileAlreadyExists)?;
+
+        let data_len = sector_count * sector_size.as_u64();
+        file.set_len(data_len + FOOTER_LEN as u64)
+            .map_err(|_| DiskError::IoError)?;
+
+        let mut image = Self {
+            file,
+            sector_size,
+            sector_count,
+            geometry,
+            kind: VhdKind::Fixed,
+            bat: Vec::new(),
+            bat_offset: 0,
+            data_offset: 0,
+            sectors_per_block: 0,
+            bitmap_sectors: 0,
+        };
+
+        image.write_footer_at(data_len)?;
+        Ok(image)
+    }
+
+    /// Creates a new, empty dynamic (sparse) VHD image backed by `path`.
+    ///
+    /// No block is allocated until it is first written to; `read_sector` returns
+    /// zeroes for any sector in an unallocated block.
+    pub fn create_dynamic(
+        path: &Path,
+        geometry: Geometry,
+        sector_size: SectorSize,
+    ) -> Result<Self, DiskError> {
+        let sector_count = geometry.cylinders() as u64 * geometry.heads() as u64 * geometry.sectors() as u64;
+
+        let file = File::options()
+            .read(true)
+            .write(true)
+            .create_new(true)
+            .open(path)
+            .map_err(|_| DiskError::FileAlreadyExists)?;
+
+        let sectors_per_block = BLOCK_SIZE / sector_size.as_u64();
+        let max_table_entries = sector_count.div_ceil(sectors_per_block);
+        let bitmap_bytes = (sectors_per_block).div_ceil(8);
+        let bitmap_sectors = bitmap_bytes.div_ceil(sector_size.as_u64());
+
+        let bat_offset = FOOTER_LEN as u64 + DYNAMIC_HEADER_LEN as u64;
+        let bat_bytes = max_table_entries * 4;
+        let data_offset = round_up_to_sector(bat_offset + bat_bytes, sector_size.as_u64());
+
+        let mut image = Self {
+            file,
+            sector_size,
+            sector_count,
+            geometry,
+            kind: VhdKind::Dynamic,
+            bat: vec![BAT_UNALLOCATED; max_table_entries as usize],
+            bat_offset,
+            data_offset,
+            sectors_per_block,
+            bitmap_sectors,
+        };
+
+        image.file.set_len(data_offset).map_err(|_| DiskError::IoError)?;
+        image.write_dynamic_header(max_table_entries)?;
+        image.write_bat()?;
+        // Dynamic images keep a leading copy of the footer at offset 0 as well as a
+        // trailing copy at the end of the file; with no blocks allocated yet, the
+        // end of the file is exactly `data_offset`.
+        image.write_footer_at(0)?;
+        image.write_footer_at(data_offset)?;
+
+        Ok(image)
+    }
+
+    /// Opens an existing VHD image at `path`, parsing its trailing footer (and, for
+    /// dynamic images, its dynamic-disk header and BAT) to reconstruct the in-memory
+    /// state needed to service `read_sector`/`write_sector`.
+    ///
+    /// The real VHD format always uses 512-byte sectors; since `create_fixed` and
+    /// `create_dynamic` accept an arbitrary `SectorSize` as a convenience for this
+    /// crate's other backends, round-tripping a file created with a non-512 sector
+    /// size through `open_existing` is not supported.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::InvalidFileSize` if the file is too short to hold a
+    /// footer, or `DiskError::InvalidArgument` if the footer or dynamic-disk header
+    /// cookie doesn't match what this format expects.
+    pub fn open_existing(path: &Path) -> Result<Self, DiskError> {
+        let mut file = File::options()
+            .read(true)
+            .write(true)
+            .open(path)
+            .map_err(|_| DiskError::FileOpenFailed)?;
+
+        let len = file.metadata().map_err(|_| DiskError::FileMetadataFailed)?.len();
+        if len < FOOTER_LEN as u64 {
+            return Err(DiskError::InvalidFileSize);
+        }
+
+        let mut footer = [0u8; FOOTER_LEN];
+        file.seek(SeekFrom::Start(len - FOOTER_LEN as u64))
+            .map_err(|_| DiskError::SeekFailed)?;
+        file.read_exact(&mut footer).map_err(|_| DiskError::ReadFailed)?;
+
+        if &footer[0..8] != b"conectix" {
+            return Err(DiskError::InvalidArgument);
+        }
+
+        let geometry = Geometry::from_vhd_bytes(footer[56..60].try_into().unwrap());
+        let sector_size = SectorSize::S512;
+        let sector_count =
+            geometry.cylinders() as u64 * geometry.heads() as u64 * geometry.sectors() as u64;
+        let disk_type = u32::from_be_bytes(footer[60..64].try_into().unwrap());
+
+        match disk_type {
+            2 => Ok(Self {
+                file,
+                sector_size,
+                sector_count,
+                geometry,
+                kind: VhdKind::Fixed,
+                bat: Vec::new(),
+                bat_offset: 0,
+                data_offset: 0,
+                sectors_per_block: 0,
+                bitmap_sectors: 0,
+            }),
+            3 => {
+                let mut header = [0u8; DYNAMIC_HEADER_LEN];
+                file.seek(SeekFrom::Start(FOOTER_LEN as u64))
+                    .map_err(|_| DiskError::SeekFailed)?;
+                file.read_exact(&mut header).map_err(|_| DiskError::ReadFailed)?;
+
+                if &header[0..8] != b"cxsparse" {
+                    return Err(DiskError::InvalidArgument);
+                }
+
+                let bat_offset = u64::from_be_bytes(header[16..24].try_into().unwrap());
+                let max_table_entries =
+                    u32::from_be_bytes(header[28..32].try_into().unwrap()) as u64;
+                let block_size = u32::from_be_bytes(header[32..36].try_into().unwrap()) as u64;
+
+                let sectors_per_block = block_size / sector_size.as_u64();
+                let bitmap_sectors = sectors_per_block.div_ceil(8).div_ceil(sector_size.as_u64());
+                let data_offset =
+                    round_up_to_sector(bat_offset + max_table_entries * 4, sector_size.as_u64());
+
+                let mut bat_bytes = vec![0u8; (max_table_entries * 4) as usize];
+                file.seek(SeekFrom::Start(bat_offset)).map_err(|_| DiskError::SeekFailed)?;
+                file.read_exact(&mut bat_bytes).map_err(|_| DiskError::ReadFailed)?;
+                let bat = bat_bytes
+                    .chunks_exact(4)
+                    .map(|chunk| u32::from_be_bytes(chunk.try_into().unwrap()))
+                    .collect();
+
+                Ok(Self {
+                    file,
+                    sector_size,
+                    sector_count,
+                    geometry,
+                    kind: VhdKind::Dynamic,
+                    bat,
+                    bat_offset,
+                    data_offset,
+                    sectors_per_block,
+                    bitmap_sectors,
+                })
+            }
+            _ => Err(DiskError::InvalidArgument),
+        }
+    }
+
+    /// Renders and writes a 512-byte VHD footer at `offset`.
+    fn write_footer_at(&mut self, offset: u64) -> Result<(), DiskError> {
+        let footer = self.render_footer()?;
+        self.file.seek(SeekFrom::Start(offset)).map_err(|_| DiskError::SeekFailed)?;
+        self.file.write_all(&footer).map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)
+    }
+
+    fn render_footer(&self) -> Result<[u8; FOOTER_LEN], DiskError> {
+        let mut footer = [0u8; FOOTER_LEN];
+
+        footer[0..8].copy_from_slice(b"conectix");
+        footer[8..12].copy_from_slice(&0x0000_0002u32.to_be_bytes()); // features: reserved bit set
+        footer[12..16].copy_from_slice(&0x0001_0000u32.to_be_bytes()); // file format version 1.0
+
+        let data_offset: u64 = match self.kind {
+            VhdKind::Fixed => u64::MAX,
+            VhdKind::Dynamic => FOOTER_LEN as u64,
+        };
+        footer[16..24].copy_from_slice(&data_offset.to_be_bytes());
+
+        let timestamp = SystemTime::now()
+            .duration_since(UNIX_EPOCH)
+            .map(|d| d.as_secs().saturating_sub(VHD_EPOCH_OFFSET))
+            .unwrap_or(0) as u32;
+        footer[24..28].copy_from_slice(&timestamp.to_be_bytes());
+
+        footer[28..32].copy_from_slice(b"dosc"); // creator application
+        footer[32..36].copy_from_slice(&0x0001_0000u32.to_be_bytes()); // creator version 1.0
+        footer[36..40].copy_from_slice(b"Wi2k"); // creator host OS
+
+        let disk_size = self.sector_count * self.sector_size.as_u64();
+        footer[40..48].copy_from_slice(&disk_size.to_be_bytes()); // original size
+        footer[48..56].copy_from_slice(&disk_size.to_be_bytes()); // current size
+
+        let geometry_bytes = self.geometry.to_vhd_bytes()?;
+        footer[56..60].copy_from_slice(&geometry_bytes);
+
+        let disk_type: u32 = match self.kind {
+            VhdKind::Fixed => 2,
+            VhdKind::Dynamic => 3,
+        };
+        footer[60..64].copy_from_slice(&disk_type.to_be_bytes());
+
+        // Checksum is computed with its own field zeroed, then written in afterwards.
+        footer[64..68].copy_from_slice(&0u32.to_be_bytes());
+
+        let unique_id = Uuid::new_v4();
+        footer[68..84].copy_from_slice(unique_id.as_bytes());
+
+        footer[84] = 0; // saved state: not saved
+
+        let checksum = vhd_checksum(&footer);
+        footer[64..68].copy_from_slice(&checksum.to_be_bytes());
+
+        Ok(footer)
+    }
+
+    fn write_dynamic_header(&mut self, max_table_entries: u64) -> Result<(), DiskError> {
+        let mut header = [0u8; DYNAMIC_HEADER_LEN];
+
+        header[0..8].copy_from_slice(b"cxsparse");
+        header[8..16].copy_from_slice(&u64::MAX.to_be_bytes()); // no next header
+        header[16..24].copy_from_slice(&self.bat_offset.to_be_bytes());
+        header[24..28].copy_from_slice(&0x0001_0000u32.to_be_bytes()); // header version 1.0
+        header[28..32].copy_from_slice(&(max_table_entries as u32).to_be_bytes());
+        header[32..36].copy_from_slice(&(BLOCK_SIZE as u32).to_be_bytes());
+
+        header[36..40].copy_from_slice(&0u32.to_be_bytes()); // checksum placeholder
+        let checksum = vhd_checksum(&header);
+        header[36..40].copy_from_slice(&checksum.to_be_bytes());
+
+        self.file
+            .seek(SeekFrom::Start(FOOTER_LEN as u64))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file.write_all(&header).map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)
+    }
+
+    fn write_bat(&mut self) -> Result<(), DiskError> {
+        let mut bytes = Vec::with_capacity(self.bat.len() * 4);
+        for entry in &self.bat {
+            bytes.extend_from_slice(&entry.to_be_bytes());
+        }
+
+        self.file
+            .seek(SeekFrom::Start(self.bat_offset))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file.write_all(&bytes).map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)
+    }
+
+    /// Byte length of one allocated block, bitmap included.
+    fn block_byte_len(&self) -> u64 {
+        (self.bitmap_sectors + self.sectors_per_block) * self.sector_size.as_u64()
+    }
+
+    /// Allocates storage for `block` at the end of the file: a zeroed bitmap followed
+    /// by a zeroed data region, updating the in-memory and on-disk BAT.
+    ///
+    /// The trailing footer copy the VHD spec requires at the very end of the file is
+    /// re-written after the new block, since growing the file always leaves a stale
+    /// footer copy behind at the old end.
+    fn allocate_block(&mut self, block: usize) -> Result<u64, DiskError> {
+        // Allocate past whatever footer copy currently sits at the end of the file;
+        // it gets overwritten below, and a fresh one is appended after the block.
+        let current_len = self.file.metadata().map_err(|_| DiskError::FileMetadataFailed)?.len();
+        let offset = current_len.saturating_sub(FOOTER_LEN as u64);
+
+        self.file.seek(SeekFrom::Start(offset)).map_err(|_| DiskError::SeekFailed)?;
+        let zeroes = vec![0u8; self.block_byte_len() as usize];
+        self.file.write_all(&zeroes).map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)?;
+
+        let sector_offset = (offset / self.sector_size.as_u64()) as u32;
+        self.bat[block] = sector_offset;
+
+        self.file
+            .seek(SeekFrom::Start(self.bat_offset + block as u64 * 4))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file
+            .write_all(&sector_offset.to_be_bytes())
+            .map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)?;
+
+        let new_end = offset + self.block_byte_len();
+        self.write_footer_at(new_end)?;
+
+        Ok(offset)
+    }
+
+    fn block_data_offset(&self, block: usize) -> u64 {
+        self.bat[block] as u64 * self.sector_size.as_u64() + self.bitmap_sectors * self.sector_size.as_u64()
+    }
+
+    fn read_bitmap_bit(&mut self, block: usize, sector_in_block: u64) -> Result<bool, DiskError> {
+        let bitmap_offset = self.bat[block] as u64 * self.sector_size.as_u64();
+        let byte_index = sector_in_block / 8;
+        let mut byte = [0u8; 1];
+        self.file
+            .seek(SeekFrom::Start(bitmap_offset + byte_index))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file.read_exact(&mut byte).map_err(|_| DiskError::ReadFailed)?;
+        let bit = 7 - (sector_in_block % 8);
+        Ok(byte[0] & (1 << bit) != 0)
+    }
+
+    fn set_bitmap_bit(&mut self, block: usize, sector_in_block: u64) -> Result<(), DiskError> {
+        let bitmap_offset = self.bat[block] as u64 * self.sector_size.as_u64();
+        let byte_index = sector_in_block / 8;
+        let mut byte = [0u8; 1];
+        self.file
+            .seek(SeekFrom::Start(bitmap_offset + byte_index))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file.read_exact(&mut byte).map_err(|_| DiskError::ReadFailed)?;
+
+        let bit = 7 - (sector_in_block % 8);
+        byte[0] |= 1 << bit;
+
+        self.file
+            .seek(SeekFrom::Start(bitmap_offset + byte_index))
+            .map_err(|_| DiskError::SeekFailed)?;
+        self.file.write_all(&byte).map_err(|_| DiskError::WriteFailed)?;
+        self.file.flush().map_err(|_| DiskError::FlushFailed)
+    }
+}
+
+/// Computes the one's-complement checksum the VHD spec requires for footers and
+/// dynamic-disk headers: the ones' complement of the sum of all bytes, treating the
+/// checksum field itself as zero.
+fn vhd_checksum(bytes: &[u8]) -> u32 {
+    let sum: u32 = bytes.iter().map(|&b| b as u32).sum();
+    !sum
+}
+
+fn round_up_to_sector(value: u64, sector_size: u64) -> u64 {
+    value.div_ceil(sector_size) * sector_size
+}
+
+impl Disk for VhdImage {
+    fn read_sector(&mut self, lba: u64, buf: &mut [u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+
+        if buf.len() < sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba >= self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        match self.kind {
+            VhdKind::Fixed => {
+                let offset = self.data_offset + lba * sector_size as u64;
+                self.file.seek(SeekFrom::Start(offset)).map_err(|_| DiskError::SeekFailed)?;
+                self.file
+                    .read_exact(&mut buf[..sector_size])
+                    .map_err(|_| DiskError::ReadFailed)?;
+            }
+            VhdKind::Dynamic => {
+                let block = (lba / self.sectors_per_block) as usize;
+                let sector_in_block = lba % self.sectors_per_block;
+
+                if self.bat[block] == BAT_UNALLOCATED || !self.read_bitmap_bit(block, sector_in_block)? {
+                    buf[..sector_size].fill(0);
+                    return Ok(());
+                }
+
+                let offset = self.block_data_offset(block) + sector_in_block * sector_size as u64;
+                self.file.seek(SeekFrom::Start(offset)).map_err(|_| DiskError::SeekFailed)?;
+                self.file
+                    .read_exact(&mut buf[..sector_size])
+                    .map_err(|_| DiskError::ReadFailed)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_sector(&mut self, lba: u64, buf: &[u8]) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+
+        if buf.len() < sector_size {
+            return Err(DiskError::BufferTooSmall);
+        }
+        if lba >= self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+
+        match self.kind {
+            VhdKind::Fixed => {
+                let offset = self.data_offset + lba * sector_size as u64;
+                self.file.seek(SeekFrom::Start(offset)).map_err(|_| DiskError::SeekFailed)?;
+                self.file
+                    .write_all(&buf[..sector_size])
+                    .map_err(|_| DiskError::WriteFailed)?;
+                self.file.flush().map_err(|_| DiskError::FlushFailed)?;
+            }
+            VhdKind::Dynamic => {
+                let block = (lba / self.sectors_per_block) as usize;
+                let sector_in_block = lba % self.sectors_per_block;
+
+                if self.bat[block] == BAT_UNALLOCATED {
+                    self.allocate_block(block)?;
+                }
+
+                let offset = self.block_data_offset(block) + sector_in_block * sector_size as u64;
+                self.file.seek(SeekFrom::Start(offset)).map_err(|_| DiskError::SeekFailed)?;
+                self.file
+                    .write_all(&buf[..sector_size])
+                    .map_err(|_| DiskError::WriteFailed)?;
+                self.file.flush().map_err(|_| DiskError::FlushFailed)?;
+
+                self.set_bitmap_bit(block, sector_in_block)?;
+            }
+        }
+
+        Ok(())
+    }
+
+    fn ibmwipe(&mut self) -> Result<(), DiskError> {
+        let sector_size = self.sector_size.as_usize();
+        let ibm_empty_sector = vec![0xF6u8; sector_size];
+        for sector in 0..self.sector_count {
+            self.write_sector(sector, &ibm_empty_sector)?;
+        }
+        Ok(())
+    }
+
+    fn sector_count(&self) -> u64 {
+        self.sector_count
+    }
+
+    fn sector_size(&self) -> SectorSize {
+        self.sector_size
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use tempfile::tempdir;
+
+    #[test]
+    fn fixed_image_write_and_read_sector_roundtrip() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let mut image = VhdImage::create_fixed(&path, geometry, SectorSize::S512).unwrap();
+
+        let data = [0xAB; 512];
+        image.write_sector(3, &data).unwrap();
+
+        let mut readback = [0u8; 512];
+        image.read_sector(3, &mut readback).unwrap();
+        assert_eq!(readback, data);
+    }
+
+    #[test]
+    fn dynamic_image_unwritten_sectors_read_back_as_zero() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let mut image = VhdImage::create_dynamic(&path, geometry, SectorSize::S512).unwrap();
+
+        let mut buf = [0xAAu8; 512];
+        image.read_sector(5, &mut buf).unwrap();
+        assert_eq!(buf, [0u8; 512]);
+    }
+
+    #[test]
+    fn dynamic_image_write_and_read_sector_roundtrip() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let mut image = VhdImage::create_dynamic(&path, geometry, SectorSize::S512).unwrap();
+
+        let data = [0xCD; 512];
+        image.write_sector(10, &data).unwrap();
+
+        let mut readback = [0u8; 512];
+        image.read_sector(10, &mut readback).unwrap();
+        assert_eq!(readback, data);
+
+        // A neighbouring, still-unwritten sector in the same block stays zero.
+        let mut neighbour = [0xFFu8; 512];
+        image.read_sector(11, &mut neighbour).unwrap();
+        assert_eq!(neighbour, [0u8; 512]);
+    }
+
+    #[test]
+    fn out_of_bounds_sector_fails() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let mut image = VhdImage::create_fixed(&path, geometry, SectorSize::S512).unwrap();
+
+        let mut buf = [0u8; 512];
+        assert!(matches!(
+            image.read_sector(1000, &mut buf),
+            Err(DiskError::OutOfBounds)
+        ));
+    }
+
+    #[test]
+    fn open_existing_fixed_image_preserves_written_data() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let mut image = VhdImage::create_fixed(&path, geometry, SectorSize::S512).unwrap();
+        image.write_sector(3, &[0xAB; 512]).unwrap();
+        drop(image);
+
+        let mut reopened = VhdImage::open_existing(&path).unwrap();
+        assert_eq!(reopened.kind, VhdKind::Fixed);
+        assert_eq!(reopened.sector_count(), 4 * 2 * 9);
+
+        let mut buf = [0u8; 512];
+        reopened.read_sector(3, &mut buf).unwrap();
+        assert_eq!(buf, [0xAB; 512]);
+    }
+
+    #[test]
+    fn open_existing_dynamic_image_preserves_written_data() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let mut image = VhdImage::create_dynamic(&path, geometry, SectorSize::S512).unwrap();
+        image.write_sector(10, &[0xCD; 512]).unwrap();
+        drop(image);
+
+        let mut reopened = VhdImage::open_existing(&path).unwrap();
+        assert_eq!(reopened.kind, VhdKind::Dynamic);
+
+        let mut buf = [0u8; 512];
+        reopened.read_sector(10, &mut buf).unwrap();
+        assert_eq!(buf, [0xCD; 512]);
+
+        let mut unwritten = [0xFFu8; 512];
+        reopened.read_sector(11, &mut unwritten).unwrap();
+        assert_eq!(unwritten, [0u8; 512]);
+    }
+
+    #[test]
+    fn footer_checksum_is_internally_consistent() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("disk.vhd");
+        let geometry = Geometry::new(4, 2, 9).unwrap();
+        let image = VhdImage::create_fixed(&path, geometry, SectorSize::S512).unwrap();
+
+        let mut footer = image.render_footer().unwrap();
+        assert_eq!(&footer[0..8], b"conectix");
+
+        let stored_checksum = u32::from_be_bytes(footer[64..68].try_into().unwrap());
+        footer[64..68].copy_from_slice(&0u32.to_be_bytes());
+        assert_eq!(vhd_checksum(&footer), stored_checksum);
+    }
+}
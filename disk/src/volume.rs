@@ -30,6 +30,60 @@ impl<'a, D: Disk> Volume<'a, D> {
         self.disk.write_sector(self.start_sector + sector, buf)
     }
 
+    /// Reads `buf.len() / sector_size` contiguous sectors starting at `sector`
+    /// into `buf` in one call, so a whole FAT or cluster run can be pulled in
+    /// a single bounds-checked range instead of one `read_sector` call per
+    /// sector. Delegates to the underlying `Disk`'s own `read_sectors`, so a
+    /// backend that overrides it for a genuine bulk transfer (like
+    /// `RawImage`) benefits here too.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::BufferTooSmall` if `buf`'s length isn't a whole
+    /// multiple of the disk's sector size, or `DiskError::OutOfBounds` if
+    /// `sector` plus the sector count `buf` implies exceeds this volume's
+    /// `sector_count`.
+    pub fn read_sectors(&mut self, sector: u64, buf: &mut [u8]) -> Result<(), DiskError> {
+        let sector_size = self.disk.sector_size().as_usize();
+        if buf.len() % sector_size != 0 {
+            return Err(DiskError::BufferTooSmall);
+        }
+        let count = (buf.len() / sector_size) as u32;
+        if sector + count as u64 > self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+        self.disk.read_sectors(self.start_sector + sector, count, buf)
+    }
+
+    /// Writes `buf.len() / sector_size` contiguous sectors starting at
+    /// `sector` from `buf` in one call, the batched counterpart to
+    /// `read_sectors`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `DiskError::BufferTooSmall` if `buf`'s length isn't a whole
+    /// multiple of the disk's sector size, or `DiskError::OutOfBounds` if
+    /// `sector` plus the sector count `buf` implies exceeds this volume's
+    /// `sector_count`.
+    pub fn write_sectors(&mut self, sector: u64, buf: &[u8]) -> Result<(), DiskError> {
+        let sector_size = self.disk.sector_size().as_usize();
+        if buf.len() % sector_size != 0 {
+            return Err(DiskError::BufferTooSmall);
+        }
+        let count = (buf.len() / sector_size) as u32;
+        if sector + count as u64 > self.sector_count {
+            return Err(DiskError::OutOfBounds);
+        }
+        self.disk.write_sectors(self.start_sector + sector, count, buf)
+    }
+
+    /// The underlying disk's sector size, for callers (like
+    /// `AllocationTable::write_all`) that need to size a buffer in terms of
+    /// whole sectors.
+    pub fn sector_size(&self) -> crate::sectorsize::SectorSize {
+        self.disk.sector_size()
+    }
+
     pub fn start_sector(&self) -> u64 {
         self.start_sector
     }
@@ -149,4 +203,55 @@ mod tests {
         assert!(volume.read_sector(2, &mut buf).is_err()); // volume sector 2 is out of bounds (max is 1)
         assert!(volume.write_sector(2, &buf).is_err());
     }
+
+    #[test]
+    fn test_volume_read_write_sectors_batched() {
+        let sector_size = 512;
+        let disk_sectors = 10;
+        let mut mock_disk = MockDisk::new(disk_sectors, sector_size);
+
+        let mut volume = Volume::new(&mut mock_disk, 2, 5);
+
+        let write_data = vec![0xCDu8; sector_size * 3];
+        volume.write_sectors(1, &write_data).unwrap();
+
+        let mut read_buf = vec![0u8; sector_size * 3];
+        volume.read_sectors(1, &mut read_buf).unwrap();
+
+        assert_eq!(read_buf, write_data);
+    }
+
+    #[test]
+    fn test_volume_sectors_rejects_a_misaligned_buffer() {
+        let sector_size = 512;
+        let mut mock_disk = MockDisk::new(10, sector_size);
+        let mut volume = Volume::new(&mut mock_disk, 0, 10);
+
+        let mut buf = vec![0u8; sector_size + 1];
+        assert_eq!(
+            volume.read_sectors(0, &mut buf),
+            Err(DiskError::BufferTooSmall)
+        );
+        assert_eq!(
+            volume.write_sectors(0, &buf),
+            Err(DiskError::BufferTooSmall)
+        );
+    }
+
+    #[test]
+    fn test_volume_sectors_rejects_a_range_past_the_end() {
+        let sector_size = 512;
+        let mut mock_disk = MockDisk::new(10, sector_size);
+        let mut volume = Volume::new(&mut mock_disk, 0, 3);
+
+        let mut buf = vec![0u8; sector_size * 4];
+        assert_eq!(
+            volume.read_sectors(0, &mut buf),
+            Err(DiskError::OutOfBounds)
+        );
+        assert_eq!(
+            volume.write_sectors(0, &buf),
+            Err(DiskError::OutOfBounds)
+        );
+    }
 }
@@ -26,7 +26,25 @@ pub enum DownloadError {
     PathIsEmpty,
     /// Creating the tempdir for zipfiles failed
     ZipDirCreateFailed,
-    
+    /// Local source file for a `file://` URL does not exist
+    LocalSourceNotFound,
+    /// Local source file for a `file://` URL could not be read
+    LocalSourceUnreadable,
+    /// Followed more HTTP redirects than the configured limit allows
+    TooManyRedirects,
+    /// Could not reach the configured proxy server
+    ProxyConnectionError,
+    /// The proxy server rejected the supplied credentials
+    ProxyAuthenticationError,
+    /// Server answered a ranged request with `206` but no usable `Content-Range`
+    RangeNotSupported,
+    /// Failed to load a CA bundle or client certificate, or the TLS handshake itself failed
+    TlsError,
+    /// The HTTP request could not be sent or its body could not be read
+    HttpRequestError,
+    /// The HTTP response status was not successful
+    HttpResponseError,
+
 }
 
 impl std::fmt::Display for DownloadError {
@@ -44,6 +62,15 @@ impl std::fmt::Display for DownloadError {
             DownloadError::UnsupportedScheme => { write!(f, "Unsupported URI scheme.")},
             DownloadError::PathIsEmpty => { write!(f, "Path part of URL is empty.")}
             DownloadError::ZipDirCreateFailed => { write!(f, "Failed to create temporary directory for ZIP files.")}
+            DownloadError::LocalSourceNotFound => { write!(f, "Local source file for file:// URL does not exist.")}
+            DownloadError::LocalSourceUnreadable => { write!(f, "Local source file for file:// URL could not be read.")}
+            DownloadError::TooManyRedirects => { write!(f, "Too many HTTP redirects.")}
+            DownloadError::ProxyConnectionError => { write!(f, "Unable to connect to proxy server.")}
+            DownloadError::ProxyAuthenticationError => { write!(f, "Proxy server rejected the supplied credentials.")}
+            DownloadError::RangeNotSupported => { write!(f, "Server did not honor the requested byte range.")}
+            DownloadError::TlsError => { write!(f, "TLS configuration or handshake failed.")}
+            DownloadError::HttpRequestError => { write!(f, "HTTP request failed.")}
+            DownloadError::HttpResponseError => { write!(f, "HTTP response status was not successful.")}
         }
     }
 }
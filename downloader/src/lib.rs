@@ -1,4 +1,4 @@
-use std::{fs::File, io::Write, path::{Path, PathBuf}};
+use std::{collections::HashSet, fs::File, io::Write, path::{Path, PathBuf}};
 
 use error::DownloadError;
 use ftp::{FtpError, FtpStream};
@@ -7,18 +7,150 @@ use url::Url; // Add FTP support with the `ftp` crate.
 
 mod error;
 
+/// Maximum number of `3xx` hops `download_http` will follow before giving up
+/// with `DownloadError::TooManyRedirects`.
+const MAX_REDIRECTS: u32 = 10;
+
+/// A proxy server to route `download_http`/`download_ftp` requests through,
+/// parsed from a `scheme://[user[:password]@]host[:port]` URL such as the
+/// one carried in `http_proxy`/`https_proxy`/`ftp_proxy`.
+#[derive(Debug, Clone)]
+struct ProxyConfig {
+    scheme: String,
+    host: String,
+    port: u16,
+    username: Option<String>,
+    password: Option<String>,
+}
+
+impl ProxyConfig {
+    fn parse(url: &str) -> Result<Self, DownloadError> {
+        let parsed = Url::parse(url).map_err(|_| DownloadError::InvalidUrl)?;
+        let host = parsed
+            .host_str()
+            .ok_or(DownloadError::InvalidUrl)?
+            .to_string();
+        let port = parsed
+            .port_or_known_default()
+            .unwrap_or(if parsed.scheme() == "https" { 443 } else { 80 });
+        Ok(ProxyConfig {
+            scheme: parsed.scheme().to_string(),
+            host,
+            port,
+            username: (!parsed.username().is_empty()).then(|| parsed.username().to_string()),
+            password: parsed.password().map(|p| p.to_string()),
+        })
+    }
+
+    /// Reads the proxy for `scheme` (`http`, `https`, or `ftp`) from the
+    /// matching `<scheme>_proxy` environment variable, unless `host` appears
+    /// in the comma-separated `no_proxy` list.
+    fn from_env(scheme: &str, host: &str) -> Option<Self> {
+        if let Ok(no_proxy) = std::env::var("no_proxy") {
+            if no_proxy.split(',').any(|skip| skip.trim() == host) {
+                return None;
+            }
+        }
+        let var = format!("{scheme}_proxy");
+        std::env::var(&var).ok().and_then(|url| Self::parse(&url).ok())
+    }
+}
+
+/// TLS settings applied to HTTPS requests: a custom CA bundle, a client
+/// certificate/key pair for mutual TLS, and an escape hatch for skipping
+/// certificate validation against self-signed retro-software mirrors.
+#[derive(Debug, Clone, Default)]
+struct TlsConfig {
+    ca_bundle: Option<PathBuf>,
+    client_cert: Option<(PathBuf, PathBuf)>,
+    danger_accept_invalid_certs: bool,
+}
+
+/// Configures a `Downloader` before it performs its one eager download:
+/// proxy settings via `with_proxy`, TLS settings via `with_ca_bundle`,
+/// `with_client_cert`, and `danger_accept_invalid_certs`.
+#[derive(Debug, Default)]
+pub struct DownloaderBuilder {
+    proxy: Option<ProxyConfig>,
+    tls: TlsConfig,
+}
+
+impl DownloaderBuilder {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Routes the download through `proxy_url` instead of whatever
+    /// `http_proxy`/`https_proxy`/`ftp_proxy` say.
+    pub fn with_proxy(mut self, proxy_url: &str) -> Result<Self, DownloadError> {
+        self.proxy = Some(ProxyConfig::parse(proxy_url)?);
+        Ok(self)
+    }
+
+    /// Trusts the CA certificates in the PEM bundle at `path`, for HTTPS
+    /// mirrors signed by a private CA the system trust store doesn't know.
+    pub fn with_ca_bundle(mut self, path: impl Into<PathBuf>) -> Self {
+        self.tls.ca_bundle = Some(path.into());
+        self
+    }
+
+    /// Presents the PEM-encoded `cert`/`key` pair as a client certificate,
+    /// for HTTPS mirrors gated behind mutual TLS.
+    pub fn with_client_cert(mut self, cert: impl Into<PathBuf>, key: impl Into<PathBuf>) -> Self {
+        self.tls.client_cert = Some((cert.into(), key.into()));
+        self
+    }
+
+    /// Skips certificate validation entirely. Only safe against a mirror
+    /// already trusted by other means (e.g. reached directly by IP on a
+    /// private network) -- it defeats the point of TLS otherwise.
+    pub fn danger_accept_invalid_certs(mut self, accept: bool) -> Self {
+        self.tls.danger_accept_invalid_certs = accept;
+        self
+    }
+
+    /// Downloads `url` with whatever proxy/TLS settings were configured.
+    pub fn build(self, url: &str) -> Result<Downloader, DownloadError> {
+        Downloader::new_with_config(url, self.proxy, self.tls)
+    }
+}
+
 #[derive(Debug)]
 pub struct Downloader {
     zipfile: PathBuf,
     zipdir: TempDir,
+    proxy: Option<ProxyConfig>,
+    tls: TlsConfig,
 }
 
 impl Downloader {
     pub fn new(url: &str) -> Result<Self, DownloadError> {
+        DownloaderBuilder::new().build(url)
+    }
+
+    /// Like `new`, but routes the download through `proxy_url` instead of
+    /// whatever `http_proxy`/`https_proxy`/`ftp_proxy` say.
+    pub fn with_proxy(url: &str, proxy_url: &str) -> Result<Self, DownloadError> {
+        DownloaderBuilder::new().with_proxy(proxy_url)?.build(url)
+    }
+
+    /// Starts a `DownloaderBuilder` for configuring proxy/TLS settings
+    /// before triggering the download.
+    pub fn builder() -> DownloaderBuilder {
+        DownloaderBuilder::new()
+    }
+
+    fn new_with_config(
+        url: &str,
+        proxy: Option<ProxyConfig>,
+        tls: TlsConfig,
+    ) -> Result<Self, DownloadError> {
         let zipdir = TempDir::new().map_err(|_| DownloadError::ZipDirCreateFailed)?;
         let mut downloader = Downloader {
             zipfile: PathBuf::new(),
             zipdir,
+            proxy,
+            tls,
         };
         downloader.set_zipfile(downloader.download_zip(url)?);
         Ok(downloader)
@@ -26,7 +158,7 @@ impl Downloader {
 
     /// Downloads a ZIP file from a given URL and saves it to a local temporary file.
     ///
-    /// This function supports downloading from HTTP, HTTPS, and FTP URLs. It determines
+    /// This function supports downloading from HTTP, HTTPS, FTP, and local `file://` URLs. It determines
     /// the protocol based on the scheme of the provided URL and delegates the download
     /// process to protocol-specific methods. If the scheme is unsupported, it returns an error.
     ///
@@ -53,6 +185,7 @@ impl Downloader {
         match parsed_url.scheme() {
             "http" | "https" => self.download_http(url),
             "ftp" => self.download_ftp(url),
+            "file" => self.download_file(url),
             _ => Err(DownloadError::UnsupportedScheme),
         }
     }
@@ -67,6 +200,29 @@ impl Downloader {
         &self.zipfile
     }
 
+    /// Applies this `Downloader`'s TLS settings (CA bundle, client
+    /// certificate, certificate validation) to an outgoing request builder.
+    /// A no-op when none were configured.
+    fn apply_tls(
+        &self,
+        mut builder: attohttpc::RequestBuilder,
+    ) -> Result<attohttpc::RequestBuilder, DownloadError> {
+        if let Some(ca_bundle) = &self.tls.ca_bundle {
+            let pem = std::fs::read(ca_bundle).map_err(|_| DownloadError::TlsError)?;
+            let cert =
+                attohttpc::tls::Certificate::from_pem(&pem).map_err(|_| DownloadError::TlsError)?;
+            builder = builder.add_root_certificate(cert);
+        }
+        if let Some((cert_path, key_path)) = &self.tls.client_cert {
+            let cert_pem = std::fs::read(cert_path).map_err(|_| DownloadError::TlsError)?;
+            let key_pem = std::fs::read(key_path).map_err(|_| DownloadError::TlsError)?;
+            let identity = attohttpc::tls::Identity::from_pem(&cert_pem, &key_pem)
+                .map_err(|_| DownloadError::TlsError)?;
+            builder = builder.identity(identity);
+        }
+        Ok(builder.danger_accept_invalid_certs(self.tls.danger_accept_invalid_certs))
+    }
+
     /// Downloads a file from an FTP server and saves it in a temporary directory.
     ///
     /// # Parameters
@@ -87,19 +243,35 @@ impl Downloader {
     /// - `DownloadError::FtpTransferTypeError`: If switching to binary transfer mode fails.
     /// - `DownloadError::FtpStreamReadError`: If an error occurs while reading the file stream.
     /// - `DownloadError::FtpDisconnectError`: If disconnecting from the FTP server fails.
+    /// - `DownloadError::ProxyConnectionError`: If a configured proxy can't be reached.
+    /// - `DownloadError::ProxyAuthenticationError`: If the proxy rejects the relayed login.
     ///
     /// # Details
     /// 1. **Validation**: The URL is validated to ensure it is well-formed, uses the `ftp` scheme,
     ///    and contains a valid path and file name.
     /// 2. **Temporary Directory**: The downloaded file is stored in a temporary directory managed by `self.zipdir`.
     /// 3. **FTP Connection**: The function connects to the FTP server using the host and port extracted
-    ///    from the URL. If no port is specified, the default port `21` is used.
+    ///    from the URL, unless a proxy was set via `Downloader::with_proxy` or the `ftp_proxy` environment
+    ///    variable, in which case it connects to the proxy and relays the origin host through the login
+    ///    name (`user@host`) instead. If no port is specified, the default port `21` is used.
     /// 4. **Authentication**: The function authenticates using the username and password provided in the
     ///    URL. If no credentials are provided, it defaults to anonymous authentication.
     /// 5. **File Transfer**: The file is transferred in binary mode and saved to the temporary directory.
-    ///    A buffer is used for efficient reading and writing.
+    ///    A buffer is used for efficient reading and writing. If a partial download from an earlier,
+    ///    interrupted attempt already sits in `self.zipdir`, a `REST` is issued first to resume from
+    ///    where it left off instead of starting over.
     /// 6. **Cleanup**: The FTP connection is gracefully closed after the transfer.
     fn download_ftp(&self, url: &str) -> Result<PathBuf, DownloadError> {
+        self.download_ftp_with_progress(url, None)
+    }
+
+    /// Same as `download_ftp`, but reports transfer progress through `progress`
+    /// (bytes-so-far, total-if-known) as each chunk arrives.
+    fn download_ftp_with_progress(
+        &self,
+        url: &str,
+        mut progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<PathBuf, DownloadError> {
         // Validate and parse the input URL.
         let parsed_url = Url::parse(url).map_err(|_| DownloadError::InvalidUrl)?;
 
@@ -131,9 +303,9 @@ impl Downloader {
         let tempdir = &self.zipdir;
         let filepath = tempdir.path().join(file_name);
 
-        // Perform the FTP transaction.
-        let mut ftp =
-            FtpStream::connect((host, port)).map_err(|_| DownloadError::FtpConnectionError)?;
+        // A partial file from an earlier attempt means we can REST from where
+        // it left off instead of restarting the whole transfer.
+        let resume_offset = std::fs::metadata(&filepath).map(|m| m.len()).unwrap_or(0);
 
         // Authenticate with anonymous credentials if no username/password is provided.
         let username = if parsed_url.username().is_empty() {
@@ -142,18 +314,64 @@ impl Downloader {
             parsed_url.username()
         };
         let password = parsed_url.password().unwrap_or("doscontainer@area536.com");
-        ftp.login(username, password)
-            .map_err(|_| DownloadError::FtpAuthenticationError)?;
+
+        // Without a proxy, connect straight to the origin server. With one,
+        // connect to the proxy instead and fold the origin host into the
+        // username (`user@host`), the login-relay convention FTP proxies
+        // that don't speak a separate proxy protocol expect.
+        let proxy = self
+            .proxy
+            .clone()
+            .or_else(|| ProxyConfig::from_env("ftp", host));
+        let (connect_host, connect_port, login_user) = match &proxy {
+            Some(proxy) => (
+                proxy.host.as_str(),
+                proxy.port,
+                format!("{username}@{host}"),
+            ),
+            None => (host, port, username.to_string()),
+        };
+
+        // Perform the FTP transaction.
+        let mut ftp = FtpStream::connect((connect_host, connect_port)).map_err(|_| {
+            if proxy.is_some() {
+                DownloadError::ProxyConnectionError
+            } else {
+                DownloadError::FtpConnectionError
+            }
+        })?;
+
+        ftp.login(&login_user, password).map_err(|_| {
+            if proxy.is_some() {
+                DownloadError::ProxyAuthenticationError
+            } else {
+                DownloadError::FtpAuthenticationError
+            }
+        })?;
 
         // Switch to binary mode for file transfers.
         ftp.transfer_type(ftp::types::FileType::Binary)
             .map_err(|_| DownloadError::FtpTransferTypeError)?;
 
-        // Start retrieving the file.
+        let total_size = ftp.size(path).ok().flatten().map(|size| size as u64);
+
+        if resume_offset > 0 {
+            ftp.resume_transfer(resume_offset as usize)
+                .map_err(|_| DownloadError::FtpTransferTypeError)?;
+        }
+
+        // Start retrieving the file, appending to any partial file left over
+        // from an earlier attempt instead of truncating it.
         ftp.retr(path, |stream| {
-            let mut local_file =
-                File::create(&filepath).map_err(|e| FtpError::ConnectionError(e))?;
+            let mut local_file = std::fs::OpenOptions::new()
+                .create(true)
+                .write(true)
+                .append(resume_offset > 0)
+                .truncate(resume_offset == 0)
+                .open(&filepath)
+                .map_err(|e| FtpError::ConnectionError(e))?;
             let mut buffer = [0u8; 8192];
+            let mut received = resume_offset;
             loop {
                 let bytes_read = stream
                     .read(&mut buffer)
@@ -164,6 +382,10 @@ impl Downloader {
                 local_file
                     .write_all(&buffer[..bytes_read])
                     .map_err(|e| FtpError::ConnectionError(e))?;
+                received += bytes_read as u64;
+                if let Some(progress) = progress.as_deref_mut() {
+                    progress(received, total_size);
+                }
             }
             Ok(())
         })
@@ -176,6 +398,134 @@ impl Downloader {
         Ok(filepath)
     }
 
+    /// Downloads every one of `paths` over a single FTP session rooted at
+    /// `base_url`, connecting, authenticating, and switching to binary mode
+    /// only once instead of paying that overhead per file.
+    ///
+    /// Between fetches, the session only `CWD`s through the directory
+    /// segments that actually change relative to the previous path (an
+    /// `FTP_COMBINE_CWDS`-style optimization), rather than re-resolving
+    /// each absolute path from the server root.
+    ///
+    /// # Parameters
+    /// - `base_url`: An `ftp://` URL giving the host, port, and credentials
+    ///   for the session. Its path is ignored; each entry in `paths` is
+    ///   resolved relative to the server root instead.
+    /// - `paths`: The server-root-relative paths to fetch, in order.
+    ///
+    /// # Returns
+    /// The downloaded files' paths inside `self.zipdir`, in the same order as `paths`.
+    ///
+    /// # Errors
+    /// Returns the same errors as `download_ftp` (invalid URL, unsupported scheme,
+    /// connection/authentication/proxy failures, an empty file name in any path,
+    /// transfer or disconnect failures).
+    pub fn download_ftp_batch(
+        &self,
+        base_url: &str,
+        paths: &[&str],
+    ) -> Result<Vec<PathBuf>, DownloadError> {
+        let parsed_url = Url::parse(base_url).map_err(|_| DownloadError::InvalidUrl)?;
+        if parsed_url.scheme() != "ftp" {
+            return Err(DownloadError::UnsupportedScheme);
+        }
+
+        let host = parsed_url.host_str().ok_or(DownloadError::InvalidUrl)?;
+        let port = parsed_url.port_or_known_default().unwrap_or(21);
+
+        let username = if parsed_url.username().is_empty() {
+            "anonymous"
+        } else {
+            parsed_url.username()
+        };
+        let password = parsed_url.password().unwrap_or("doscontainer@area536.com");
+
+        let proxy = self
+            .proxy
+            .clone()
+            .or_else(|| ProxyConfig::from_env("ftp", host));
+        let (connect_host, connect_port, login_user) = match &proxy {
+            Some(proxy) => (
+                proxy.host.as_str(),
+                proxy.port,
+                format!("{username}@{host}"),
+            ),
+            None => (host, port, username.to_string()),
+        };
+
+        let mut ftp = FtpStream::connect((connect_host, connect_port)).map_err(|_| {
+            if proxy.is_some() {
+                DownloadError::ProxyConnectionError
+            } else {
+                DownloadError::FtpConnectionError
+            }
+        })?;
+
+        ftp.login(&login_user, password).map_err(|_| {
+            if proxy.is_some() {
+                DownloadError::ProxyAuthenticationError
+            } else {
+                DownloadError::FtpAuthenticationError
+            }
+        })?;
+
+        ftp.transfer_type(ftp::types::FileType::Binary)
+            .map_err(|_| DownloadError::FtpTransferTypeError)?;
+
+        let mut current_dir: Vec<&str> = Vec::new();
+        let mut results = Vec::with_capacity(paths.len());
+
+        for path in paths {
+            let mut segments: Vec<&str> = path.split('/').filter(|s| !s.is_empty()).collect();
+            let file_name = segments.pop().ok_or(DownloadError::FileNameIsEmpty)?;
+            if file_name.is_empty() {
+                return Err(DownloadError::FileNameIsEmpty);
+            }
+
+            // Only CWD through the directory segments that differ from
+            // wherever the previous fetch left the session.
+            let common = current_dir
+                .iter()
+                .zip(segments.iter())
+                .take_while(|(a, b)| a == b)
+                .count();
+            for _ in common..current_dir.len() {
+                ftp.cdup().map_err(|_| DownloadError::FtpConnectionError)?;
+            }
+            for segment in &segments[common..] {
+                ftp.cwd(segment)
+                    .map_err(|_| DownloadError::FtpConnectionError)?;
+            }
+            current_dir = segments;
+
+            let filepath = self.zipdir.path().join(file_name);
+            ftp.retr(file_name, |stream| {
+                let mut local_file =
+                    File::create(&filepath).map_err(|e| FtpError::ConnectionError(e))?;
+                let mut buffer = [0u8; 8192];
+                loop {
+                    let bytes_read = stream
+                        .read(&mut buffer)
+                        .map_err(|e| FtpError::ConnectionError(e))?;
+                    if bytes_read == 0 {
+                        break; // EOF
+                    }
+                    local_file
+                        .write_all(&buffer[..bytes_read])
+                        .map_err(|e| FtpError::ConnectionError(e))?;
+                }
+                Ok(())
+            })
+            .map_err(|_| DownloadError::FtpStreamReadError)?;
+
+            results.push(filepath);
+        }
+
+        ftp.quit().map_err(|_| DownloadError::FtpDisconnectError)?;
+
+        Ok(results)
+    }
+
     /// Downloads a file over HTTP or HTTPS and saves it in a temporary directory.
     ///
     /// # Parameters
@@ -192,55 +542,225 @@ impl Downloader {
     /// - `DownloadError::HttpResponseError`: If the HTTP response status is not successful (non-2xx).
     /// - `DownloadError::LocalFileCreationError`: If the file cannot be created in the temporary directory.
     /// - `DownloadError::LocalFileWriteError`: If writing to the local file fails.
+    /// - `DownloadError::TooManyRedirects`: If more than `MAX_REDIRECTS` hops are followed,
+    ///   or a redirect points back at a URL already visited this request.
+    /// - `DownloadError::ProxyConnectionError`: If a configured proxy can't be reached, or
+    ///   the URL is `https` and a proxy is configured (CONNECT tunneling isn't supported).
+    /// - `DownloadError::ProxyAuthenticationError`: If the proxy rejects the supplied credentials.
     ///
     /// # Details
-    /// 1. **Validation**: The URL is parsed and validated to ensure it uses the `http` or `https` scheme.  
+    /// 1. **Validation**: The URL is parsed and validated to ensure it uses the `http` or `https` scheme.
     ///    The path must contain a valid file name.
     /// 2. **Temporary Directory**: The file is saved in the directory specified by `self.zipdir`.
-    /// 3. **HTTP Request**: The function sends an HTTP request using `attohttpc` and ensures the response is successful.
-    /// 4. **File Handling**: The response body is written to a file in the temporary directory.
+    /// 3. **HTTP Request**: The function sends an HTTP request using `attohttpc`, following any
+    ///    3xx redirects itself (see `MAX_REDIRECTS`) rather than leaving it to the client. When a
+    ///    proxy was set via `Downloader::with_proxy` or `http_proxy`/`https_proxy`, plain HTTP
+    ///    requests are sent to the proxy's host with the origin preserved in the `Host` header and
+    ///    any proxy credentials in `Proxy-Authorization`.
+    /// 4. **File Handling**: The response body is written to a file in the temporary directory,
+    ///    named after the *final* URL's path. If a partial file from an earlier, interrupted
+    ///    attempt is already there, a `Range` header asks the server to resume from where it
+    ///    left off; a `206` response appends, while a `200` means the server ignored the range
+    ///    and the download restarts from scratch.
     pub fn download_http(&self, url: &str) -> Result<PathBuf, DownloadError> {
+        self.download_http_with_progress(url, None)
+    }
+
+    /// Same as `download_http`, but reports transfer progress through `progress`
+    /// (bytes-so-far, total-if-known) as each chunk is written out.
+    pub fn download_http_with_progress(
+        &self,
+        url: &str,
+        mut progress: Option<&mut dyn FnMut(u64, Option<u64>)>,
+    ) -> Result<PathBuf, DownloadError> {
         // Validate and parse the input URL.
-        let parsed_url = url::Url::parse(url).map_err(|_| DownloadError::InvalidUrl)?;
+        let mut current_url = Url::parse(url).map_err(|_| DownloadError::InvalidUrl)?;
 
         // Ensure the URL uses HTTP or HTTPS.
-        let scheme = parsed_url.scheme();
+        let scheme = current_url.scheme();
         if scheme != "http" && scheme != "https" {
             return Err(DownloadError::UnsupportedScheme);
         }
 
-        // Extract the file name from the URL's path.
-        let path = parsed_url.path();
-        let file_name = path.split('/').last().ok_or(DownloadError::InvalidUrl)?;
-        if file_name.is_empty() {
-            return Err(DownloadError::InvalidUrl);
-        }
+        // Follow redirects ourselves, up to MAX_REDIRECTS hops, bailing out on
+        // a loop back to a URL we've already visited.
+        let mut visited = HashSet::new();
+        let (response, filepath, resume_offset) = loop {
+            if visited.len() as u32 > MAX_REDIRECTS || !visited.insert(current_url.clone()) {
+                return Err(DownloadError::TooManyRedirects);
+            }
 
-        // Create the full path for the file in the temporary directory.
-        let filepath = self.zipdir.path().join(file_name);
+            let origin_host = current_url.host_str().ok_or(DownloadError::InvalidUrl)?;
+            let proxy = self
+                .proxy
+                .clone()
+                .or_else(|| ProxyConfig::from_env(current_url.scheme(), origin_host));
 
-        // Send the HTTP request and retrieve the response.
-        let response = attohttpc::get(url)
-            .send()
-            .map_err(|_| DownloadError::HttpRequestError)?;
+            // A partial file left over from an earlier attempt at this URL
+            // means we can ask the server to resume instead of starting over.
+            let path = current_url.path();
+            let file_name = path.split('/').last().ok_or(DownloadError::InvalidUrl)?;
+            if file_name.is_empty() {
+                return Err(DownloadError::InvalidUrl);
+            }
+            let filepath = self.zipdir.path().join(file_name);
+            let resume_offset = std::fs::metadata(&filepath).map(|m| m.len()).unwrap_or(0);
+
+            let builder = match &proxy {
+                // HTTPS through a proxy needs a CONNECT tunnel, which this
+                // minimal client doesn't implement.
+                Some(_) if current_url.scheme() == "https" => {
+                    return Err(DownloadError::ProxyConnectionError)
+                }
+                Some(proxy) => {
+                    let mut request_url = current_url.clone();
+                    request_url
+                        .set_host(Some(&proxy.host))
+                        .map_err(|_| DownloadError::ProxyConnectionError)?;
+                    request_url
+                        .set_port(Some(proxy.port))
+                        .map_err(|_| DownloadError::ProxyConnectionError)?;
+                    let mut builder = attohttpc::get(request_url.as_str())
+                        .header("Host", origin_host)
+                        .follow_redirects(false);
+                    if let Some(username) = &proxy.username {
+                        builder = builder
+                            .basic_auth(username, proxy.password.clone());
+                    }
+                    builder
+                }
+                None => attohttpc::get(current_url.as_str()).follow_redirects(false),
+            };
+            let builder = if resume_offset > 0 {
+                builder.header("Range", format!("bytes={resume_offset}-"))
+            } else {
+                builder
+            };
+            let builder = self.apply_tls(builder)?;
+
+            let response = builder
+                .send()
+                .map_err(|_| DownloadError::HttpRequestError)?;
+
+            if proxy.is_some() && response.status() == attohttpc::StatusCode::PROXY_AUTHENTICATION_REQUIRED {
+                return Err(DownloadError::ProxyAuthenticationError);
+            }
+
+            if response.status().is_redirection() {
+                let location = response
+                    .headers()
+                    .get("location")
+                    .and_then(|value| value.to_str().ok())
+                    .ok_or(DownloadError::HttpResponseError)?;
+                current_url = current_url
+                    .join(location)
+                    .map_err(|_| DownloadError::InvalidUrl)?;
+                continue;
+            }
+
+            break (response, filepath, resume_offset);
+        };
 
         // Ensure the response status is successful (2xx).
         if !response.is_success() {
             return Err(DownloadError::HttpResponseError);
         }
 
-        // Create the file in the temporary directory.
-        let mut file =
-            File::create(&filepath).map_err(|_| DownloadError::LocalFileCreationError)?;
+        // If we asked for a range and the server answered 200 instead of 206,
+        // it ignored the Range header; fall back to a clean restart.
+        let resuming = resume_offset > 0 && response.status() == attohttpc::StatusCode::PARTIAL_CONTENT;
+
+        let total_size = if resuming {
+            let content_range = response
+                .headers()
+                .get("content-range")
+                .and_then(|value| value.to_str().ok())
+                .ok_or(DownloadError::RangeNotSupported)?;
+            if !content_range.starts_with(&format!("bytes {resume_offset}-")) {
+                return Err(DownloadError::RangeNotSupported);
+            }
+            content_range
+                .rsplit('/')
+                .next()
+                .and_then(|total| total.parse::<u64>().ok())
+        } else {
+            response
+                .headers()
+                .get("content-length")
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+        };
+
+        // Open the destination file: appending if we're resuming a partial
+        // download, truncating to start fresh otherwise.
+        let mut file = if resuming {
+            std::fs::OpenOptions::new()
+                .append(true)
+                .open(&filepath)
+                .map_err(|_| DownloadError::LocalFileCreationError)?
+        } else {
+            File::create(&filepath).map_err(|_| DownloadError::LocalFileCreationError)?
+        };
 
-        // Write the response body to the file.
-        let mut content = response
+        // Write the response body to the file, reporting progress as we go.
+        let content = response
             .bytes()
             .map_err(|_| DownloadError::HttpRequestError)?;
-        file.write_all(&mut content)
-            .map_err(|_| DownloadError::LocalFileWriteError)?;
+        let mut written = if resuming { resume_offset } else { 0 };
+        for chunk in content.chunks(8192) {
+            file.write_all(chunk)
+                .map_err(|_| DownloadError::LocalFileWriteError)?;
+            written += chunk.len() as u64;
+            if let Some(progress) = progress.as_deref_mut() {
+                progress(written, total_size);
+            }
+        }
 
         // Return the path to the downloaded file.
         Ok(filepath)
     }
+
+    /// "Downloads" a file from a `file://` URL by copying it into the
+    /// temporary directory, for pointing the build pipeline at a local
+    /// mirror or a mounted share without a running server.
+    ///
+    /// # Parameters
+    /// - `url`: A string slice representing the `file://` URL to copy from.
+    ///
+    /// # Returns
+    /// - `Ok(PathBuf)`: The path to the copied file in the temporary directory.
+    /// - `Err(DownloadError)`: An error if the copy fails at any stage.
+    ///
+    /// # Errors
+    /// - `DownloadError::InvalidUrl`: If the URL is invalid, isn't a `file://` URL, or
+    ///   can't be converted to a local path.
+    /// - `DownloadError::LocalSourceNotFound`: If the source path doesn't exist.
+    /// - `DownloadError::LocalSourceUnreadable`: If the source path exists but can't be read.
+    fn download_file(&self, url: &str) -> Result<PathBuf, DownloadError> {
+        // Validate and parse the input URL.
+        let parsed_url = Url::parse(url).map_err(|_| DownloadError::InvalidUrl)?;
+
+        // Ensure the URL uses the `file` scheme.
+        if parsed_url.scheme() != "file" {
+            return Err(DownloadError::UnsupportedScheme);
+        }
+
+        let source = parsed_url
+            .to_file_path()
+            .map_err(|_| DownloadError::InvalidUrl)?;
+
+        if !source.exists() {
+            return Err(DownloadError::LocalSourceNotFound);
+        }
+
+        let file_name = source
+            .file_name()
+            .ok_or(DownloadError::FileNameIsEmpty)?;
+        let filepath = self.zipdir.path().join(file_name);
+
+        std::fs::copy(&source, &filepath).map_err(|_| DownloadError::LocalSourceUnreadable)?;
+
+        // Return the path to the copied file.
+        Ok(filepath)
+    }
 }
\ No newline at end of file
@@ -1,6 +1,17 @@
-use std::collections::BTreeMap;
+use std::collections::{BTreeMap, HashMap, HashSet};
 
-use crate::{error::FileSystemError, ClusterIndex};
+use disk::{volume::Volume, Disk};
+
+use crate::{
+    error::FileSystemError,
+    serializer::{ibmdos100::IbmDos100, FatTableSerializer},
+    ClusterIndex,
+};
+
+/// Number of mirrored copies a FAT volume carries unless told otherwise —
+/// the overwhelming majority of real DOS media, from the first 360K floppy
+/// onward, ship exactly two.
+const DEFAULT_FAT_COPIES: usize = 2;
 
 #[derive(Debug)]
 pub enum ClusterValue {
@@ -11,9 +22,97 @@ pub enum ClusterValue {
     Bad,
 }
 
-#[derive(Debug)]
+/// The three on-disk FAT widths, auto-selected from a volume's cluster count
+/// via [`FatType::for_cluster_count`]/[`FatType::for_volume`] rather than
+/// tied to any particular `disk::disktype::DiskType`, so a hard disk or
+/// large image isn't stuck with the floppy-era FAT12 packing.
+#[derive(Debug, PartialEq)]
 pub enum FatType {
     Fat12,
+    Fat16,
+    Fat32,
+}
+
+impl FatType {
+    /// Number of clusters below which a FAT12 table stays addressable, per the
+    /// classic FAT cluster-count thresholds.
+    const FAT12_CLUSTER_LIMIT: usize = 4085;
+
+    /// Number of clusters below which a FAT16 table stays addressable.
+    const FAT16_CLUSTER_LIMIT: usize = 65525;
+
+    /// Picks the narrowest FAT width that can address `cluster_count` clusters,
+    /// using the classic thresholds: fewer than 4085 clusters fits FAT12, fewer
+    /// than 65525 fits FAT16, anything larger needs FAT32.
+    pub fn for_cluster_count(cluster_count: usize) -> FatType {
+        if cluster_count < Self::FAT12_CLUSTER_LIMIT {
+            FatType::Fat12
+        } else if cluster_count < Self::FAT16_CLUSTER_LIMIT {
+            FatType::Fat16
+        } else {
+            FatType::Fat32
+        }
+    }
+
+    /// Picks the FAT width a volume of this geometry needs, the way
+    /// `mkfs_msdos`/`newfs_msdos` do: works out the number of data clusters as
+    /// `(total_sectors - reserved_sectors - fat_count * sectors_per_fat -
+    /// root_dir_sectors) / sectors_per_cluster`, then dispatches through
+    /// `for_cluster_count`.
+    pub fn for_volume(
+        total_sectors: usize,
+        reserved_sectors: usize,
+        fat_count: usize,
+        sectors_per_fat: usize,
+        root_dir_sectors: usize,
+        sectors_per_cluster: usize,
+    ) -> FatType {
+        let non_data_sectors = reserved_sectors + fat_count * sectors_per_fat + root_dir_sectors;
+        let data_sectors = total_sectors.saturating_sub(non_data_sectors);
+        let data_clusters = data_sectors / sectors_per_cluster.max(1);
+        Self::for_cluster_count(data_clusters)
+    }
+
+    /// Largest cluster count a table of this width can address.
+    fn max_cluster_count(&self) -> usize {
+        match self {
+            FatType::Fat12 => 4096,
+            FatType::Fat16 => 65524,
+            FatType::Fat32 => 268_435_444,
+        }
+    }
+}
+
+/// A structural problem within an [`AllocationTable`]'s own chains, found by
+/// [`AllocationTable::check`]. Unlike [`crate::fsck::FsckFinding`], which
+/// cross-references a [`crate::pool::Pool`]'s directory entries against the
+/// table, this only ever looks at the table itself: an image builder can
+/// hand-write a `Next` link into the FAT that no directory entry agrees
+/// with, and these are the ways that can go wrong.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FatInconsistency {
+    /// Walking a chain from `from` followed a `Next` link to `to`, but `to`
+    /// is out of range, `Free`, `Reserved`, or `Bad` rather than a real
+    /// continuation.
+    DanglingLink {
+        from: ClusterIndex,
+        to: ClusterIndex,
+    },
+    /// `cluster` is reachable from both `first_head` and `second_head`'s
+    /// chains. `via` is the cluster in `second_head`'s chain whose `Next`
+    /// link reaches it, or `None` if `cluster` is `second_head` itself.
+    CrossLinked {
+        cluster: ClusterIndex,
+        first_head: ClusterIndex,
+        second_head: ClusterIndex,
+        via: Option<ClusterIndex>,
+    },
+    /// The chain starting at `head` loops back on a cluster it already
+    /// visited instead of reaching `EndOfChain`.
+    Cycle { head: ClusterIndex },
+    /// `cluster` is allocated (`Next` or `EndOfChain`) but reachable from
+    /// none of the chain heads `check` was given.
+    LostChain { cluster: ClusterIndex },
 }
 
 #[derive(Debug)]
@@ -22,6 +121,16 @@ pub struct AllocationTable {
     cluster_size: usize,
     cluster_count: usize,
     fat_type: FatType,
+    /// Hint for where the next free-cluster search should start, so
+    /// `allocate_chain` doesn't rescan clusters already known to be used.
+    /// Stale hints (past `cluster_count`, or pointing at a cluster that's
+    /// since been used) are harmless: the search wraps around once and
+    /// still finds every free cluster.
+    next_free: ClusterIndex,
+    /// Number of identical on-disk copies this table's FAT is mirrored
+    /// across, used by `write_all`/`read_verify` to know how many copies to
+    /// touch.
+    fat_copies: usize,
 }
 
 impl Default for AllocationTable {
@@ -31,24 +140,81 @@ impl Default for AllocationTable {
             cluster_size: 512,
             cluster_count: 340,
             fat_type: FatType::Fat12,
+            next_free: 0,
+            fat_copies: DEFAULT_FAT_COPIES,
         }
     }
 }
 
 impl AllocationTable {
+    /// Builds an allocation table of the given width, sized for `cluster_count`
+    /// clusters of `cluster_size` bytes each.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::FatSizeTooLarge` if `cluster_count` doesn't fit
+    /// within `fat_type`'s addressable range.
+    pub fn new(
+        fat_type: FatType,
+        cluster_size: usize,
+        cluster_count: usize,
+    ) -> Result<Self, FileSystemError> {
+        if cluster_count > fat_type.max_cluster_count() {
+            return Err(FileSystemError::FatSizeTooLarge);
+        }
+
+        Ok(AllocationTable {
+            clusters: BTreeMap::new(),
+            cluster_size,
+            cluster_count,
+            fat_type,
+            next_free: 0,
+            fat_copies: DEFAULT_FAT_COPIES,
+        })
+    }
+
     pub fn clusters(&self) -> &BTreeMap<ClusterIndex, ClusterValue> {
         &self.clusters
     }
 
+    pub fn fat_type(&self) -> &FatType {
+        &self.fat_type
+    }
+
+    pub fn fat_copies(&self) -> usize {
+        self.fat_copies
+    }
+
+    /// Sets how many identical copies this table's FAT is mirrored across on
+    /// disk, for volumes that deviate from the usual two (DOS itself will
+    /// format with a single copy if asked).
+    pub fn set_fat_copies(&mut self, fat_copies: usize) {
+        self.fat_copies = fat_copies;
+    }
+
+    pub fn cluster_size(&self) -> usize {
+        self.cluster_size
+    }
+
+    /// Grows the table to `cluster_count` clusters.
+    ///
+    /// The new count is capped by `fat_type`'s own addressable range (4096 for
+    /// FAT12, 65524 for FAT16, 268,435,444/`0x0FFFFFF4` for FAT32), each
+    /// width's `Fat*Serializer`/`Fat*Deserializer` pair using that width's own
+    /// end-of-chain/bad-cluster markers (`0xFF8`/`0xFF7` packed 12-bit,
+    /// `0xFFF8`/`0xFFF7` 16-bit, `0x0FFFFFF8`/`0x0FFFFFF7` masked 32-bit).
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::WontShrinkAllocationTable` if `cluster_count`
+    /// is smaller than the table's current size, or `FileSystemError::FatSizeTooLarge`
+    /// if it exceeds what `fat_type` can address.
     pub fn set_cluster_count(&mut self, cluster_count: usize) -> Result<(), FileSystemError> {
         if cluster_count < self.cluster_count {
             return Err(FileSystemError::WontShrinkAllocationTable);
         }
-        let max_cluster_count = match self.fat_type {
-            FatType::Fat12 => 4096,
-        };
 
-        if cluster_count > max_cluster_count {
+        if cluster_count > self.fat_type.max_cluster_count() {
             return Err(FileSystemError::FatSizeTooLarge);
         }
 
@@ -59,9 +225,22 @@ impl AllocationTable {
     pub fn allocate_entry(&mut self, size: usize) -> Result<Vec<ClusterIndex>, FileSystemError> {
         // Always allocate at least one cluster
         let clusters_needed = std::cmp::max(1, size.div_ceil(self.cluster_size));
+        self.allocate_chain(clusters_needed)
+    }
+
+    /// Finds `count` free clusters and links them into a single chain,
+    /// terminated with `EndOfChain`, the way `allocate_entry` does from a
+    /// byte size rather than a cluster count directly.
+    ///
+    /// Scans starting from the `next_free` hint rather than cluster 0, and
+    /// wraps around to the beginning once to still cover the whole table
+    /// when the hint is stale.
+    pub fn allocate_chain(&mut self, count: usize) -> Result<Vec<ClusterIndex>, FileSystemError> {
+        let clusters_needed = std::cmp::max(1, count);
+        let start = self.next_free.min(self.cluster_count);
 
         let mut free_clusters = Vec::with_capacity(clusters_needed);
-        for index in 0..self.cluster_count {
+        for index in (start..self.cluster_count).chain(0..start) {
             if self.is_free(index)? {
                 free_clusters.push(index);
                 if free_clusters.len() == clusters_needed {
@@ -74,6 +253,10 @@ impl AllocationTable {
             return Err(FileSystemError::NotEnoughFreeClusters);
         }
 
+        if let Some(&last) = free_clusters.last() {
+            self.next_free = (last + 1) % self.cluster_count.max(1);
+        }
+
         for i in 0..clusters_needed {
             let current = free_clusters[i];
             let next = if i + 1 < clusters_needed {
@@ -151,4 +334,304 @@ impl AllocationTable {
             _ => Ok(false),
         }
     }
+
+    /// Number of clusters, out of `cluster_count`, not currently allocated
+    /// to any chain.
+    pub fn free_clusters(&self) -> usize {
+        (0..self.cluster_count)
+            .filter(|index| matches!(self.is_free(*index), Ok(true)))
+            .count()
+    }
+
+    /// Number of clusters, out of `cluster_count`, currently allocated to
+    /// some chain.
+    pub fn used_clusters(&self) -> usize {
+        self.cluster_count - self.free_clusters()
+    }
+
+    /// Same count as `free_clusters`, under the name a caller reporting free
+    /// space (e.g. a future FAT32 FSInfo sector) will want.
+    pub fn count_free_clusters(&self) -> usize {
+        self.free_clusters()
+    }
+
+    /// Directly installs a cluster's value, bypassing the allocation-safety
+    /// checks `allocate`/`reserve`/`mark_end_of_chain` apply. Meant for
+    /// reconstructing a table read back from an on-disk FAT, where every
+    /// entry's value is already known and doesn't need re-validating against
+    /// the free/used bookkeeping those methods maintain.
+    pub fn set_cluster(
+        &mut self,
+        index: ClusterIndex,
+        value: ClusterValue,
+    ) -> Result<(), FileSystemError> {
+        if index >= self.cluster_count {
+            return Err(FileSystemError::InvalidClusterIndex);
+        }
+        self.clusters.insert(index, value);
+        Ok(())
+    }
+
+    /// Walks the cluster chain starting at `start`, following `Next` links
+    /// until it hits `EndOfChain`.
+    pub fn chain(&self, start: ClusterIndex) -> ClusterChain<'_> {
+        ClusterChain {
+            clusters: &self.clusters,
+            current: Some(start),
+            max_steps: self.cluster_count,
+            steps: 0,
+        }
+    }
+
+    /// Walks the cluster chain starting at `start`, the same way `chain`
+    /// does, and resets every cluster in it back to `Free`. Returns the
+    /// number of clusters freed. Used when a file is deleted or truncated
+    /// and its clusters need to go back into the free pool.
+    ///
+    /// # Errors
+    ///
+    /// Whatever `chain`'s iterator returns for a corrupt or out-of-range
+    /// chain: `FileSystemError::Recursion` for a cycle, or
+    /// `FileSystemError::ClusterNotUsable` for a link into a free, reserved,
+    /// bad, or out-of-range cluster.
+    pub fn free_chain(&mut self, start: ClusterIndex) -> Result<usize, FileSystemError> {
+        let indices: Vec<ClusterIndex> = self.chain(start).collect::<Result<_, _>>()?;
+        for index in &indices {
+            self.clusters.insert(*index, ClusterValue::Free);
+        }
+        Ok(indices.len())
+    }
+
+    /// Validates this table's chains on their own terms, without
+    /// cross-referencing a [`crate::pool::Pool`] the way
+    /// [`crate::fsck::check`] does: walks the chain starting at each index
+    /// in `chain_heads`, reporting any `Next` link into an out-of-range,
+    /// `Free`, `Reserved`, or `Bad` cluster ([`FatInconsistency::DanglingLink`]),
+    /// any cluster two different heads' chains both reach
+    /// ([`FatInconsistency::CrossLinked`]), and any chain that loops back on
+    /// itself ([`FatInconsistency::Cycle`]).
+    ///
+    /// If `chain_heads` is non-empty, also reports every allocated cluster
+    /// (`Next` or `EndOfChain`) that no supplied head's chain ever reached
+    /// ([`FatInconsistency::LostChain`]) — pass an empty slice to skip that
+    /// check when the full set of chain heads isn't known.
+    pub fn check(&self, chain_heads: &[ClusterIndex]) -> Vec<FatInconsistency> {
+        let mut findings = Vec::new();
+        let mut owners: HashMap<ClusterIndex, ClusterIndex> = HashMap::new();
+
+        for &head in chain_heads {
+            let mut visited_this_chain = HashSet::new();
+            let mut prev: Option<ClusterIndex> = None;
+            let mut current = Some(head);
+
+            while let Some(index) = current {
+                if !visited_this_chain.insert(index) {
+                    findings.push(FatInconsistency::Cycle { head });
+                    break;
+                }
+
+                match owners.get(&index) {
+                    Some(&other_head) if other_head != head => {
+                        findings.push(FatInconsistency::CrossLinked {
+                            cluster: index,
+                            first_head: other_head,
+                            second_head: head,
+                            via: prev,
+                        });
+                    }
+                    _ => {
+                        owners.insert(index, head);
+                    }
+                }
+
+                match self.clusters.get(&index) {
+                    Some(ClusterValue::Next(next)) => {
+                        let dangling = *next >= self.cluster_count
+                            || matches!(
+                                self.clusters.get(next),
+                                Some(ClusterValue::Free)
+                                    | Some(ClusterValue::Reserved)
+                                    | Some(ClusterValue::Bad)
+                                    | None
+                            );
+                        if dangling {
+                            findings.push(FatInconsistency::DanglingLink {
+                                from: index,
+                                to: *next,
+                            });
+                            current = None;
+                        } else {
+                            prev = Some(index);
+                            current = Some(*next);
+                        }
+                    }
+                    _ => current = None,
+                }
+            }
+        }
+
+        if !chain_heads.is_empty() {
+            let visited: HashSet<ClusterIndex> = owners.keys().copied().collect();
+            for (&index, value) in &self.clusters {
+                let is_chain_link = matches!(value, ClusterValue::Next(_) | ClusterValue::EndOfChain);
+                if is_chain_link && !visited.contains(&index) {
+                    findings.push(FatInconsistency::LostChain { cluster: index });
+                }
+            }
+        }
+
+        findings
+    }
+
+    /// Applies the fixes [`AllocationTable::check`] can't make on its own:
+    /// truncates a [`FatInconsistency::DanglingLink`] at `from` and a
+    /// [`FatInconsistency::CrossLinked`] at its `via` predecessor (both
+    /// become `EndOfChain`, severing the bad continuation), and frees every
+    /// cluster behind a [`FatInconsistency::LostChain`]. A `CrossLinked`
+    /// finding whose `via` is `None` (the cross-linked cluster is a chain
+    /// head itself) can't be truncated this way — fixing that means
+    /// repointing a directory entry, which is the [`crate::pool::Pool`]'s
+    /// job, not this table's, so it's left untouched.
+    ///
+    /// Returns the subset of `findings` actually acted on.
+    pub fn repair(&mut self, findings: &[FatInconsistency]) -> Vec<FatInconsistency> {
+        let mut repaired = Vec::new();
+
+        for finding in findings {
+            match finding {
+                FatInconsistency::DanglingLink { from, .. } => {
+                    self.clusters.insert(*from, ClusterValue::EndOfChain);
+                    repaired.push(finding.clone());
+                }
+                FatInconsistency::CrossLinked {
+                    via: Some(via), ..
+                } => {
+                    self.clusters.insert(*via, ClusterValue::EndOfChain);
+                    repaired.push(finding.clone());
+                }
+                FatInconsistency::CrossLinked { via: None, .. } => {}
+                FatInconsistency::Cycle { .. } => {}
+                FatInconsistency::LostChain { cluster } => {
+                    self.clusters.insert(*cluster, ClusterValue::Free);
+                    repaired.push(finding.clone());
+                }
+            }
+        }
+
+        repaired
+    }
+
+    /// Encodes this table once via `IbmDos100::serialize_fat_table` and
+    /// writes the result to each of `fat_copies` mirrored copies on
+    /// `volume`, starting at `first_fat_lba` with each copy
+    /// `fat_size_sectors` sectors further along than the last — exactly
+    /// where DOS itself expects to find, and cross-check, every FAT copy on
+    /// a volume.
+    ///
+    /// # Errors
+    ///
+    /// Returns whatever `IbmDos100::serialize_fat_table` returns for a table
+    /// that can't be packed into its FAT width, or
+    /// `FileSystemError::DiskError` if a write fails.
+    pub fn write_all<D: Disk>(
+        &self,
+        volume: &mut Volume<D>,
+        first_fat_lba: u64,
+        fat_size_sectors: u64,
+    ) -> Result<(), FileSystemError> {
+        let sector_size = volume.sector_size().as_usize();
+        let mut bytes = IbmDos100::serialize_fat_table(self)?;
+        bytes.resize(fat_size_sectors as usize * sector_size, 0);
+
+        for copy in 0..self.fat_copies {
+            let copy_lba = first_fat_lba + copy as u64 * fat_size_sectors;
+            volume
+                .write_sectors(copy_lba, &bytes)
+                .map_err(|_| FileSystemError::DiskError)?;
+        }
+
+        Ok(())
+    }
+
+    /// Reads back every mirrored FAT copy `write_all` would have written and
+    /// compares them against each other, the way a DOS utility cross-checks
+    /// mirrored FATs before trusting either one.
+    ///
+    /// Returns `Ok(None)` if every copy is identical, or `Ok(Some(copy))`
+    /// with the index (0-based) of the first copy found to differ from
+    /// copy 0.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::DiskError` if a read fails.
+    pub fn read_verify<D: Disk>(
+        &self,
+        volume: &mut Volume<D>,
+        first_fat_lba: u64,
+        fat_size_sectors: u64,
+    ) -> Result<Option<usize>, FileSystemError> {
+        let sector_size = volume.sector_size().as_usize();
+        let copy_bytes = fat_size_sectors as usize * sector_size;
+
+        let mut reference: Option<Vec<u8>> = None;
+        for copy in 0..self.fat_copies {
+            let copy_lba = first_fat_lba + copy as u64 * fat_size_sectors;
+            let mut buf = vec![0u8; copy_bytes];
+            volume
+                .read_sectors(copy_lba, &mut buf)
+                .map_err(|_| FileSystemError::DiskError)?;
+
+            match &reference {
+                None => reference = Some(buf),
+                Some(reference_bytes) if *reference_bytes != buf => return Ok(Some(copy)),
+                Some(_) => {}
+            }
+        }
+
+        Ok(None)
+    }
+}
+
+/// Iterates a cluster chain from a starting index to its `EndOfChain` link.
+///
+/// Bounds iteration at the table's `cluster_count` so a chain corrupted into
+/// a cycle can't loop forever, and errors out if a link ever points at a
+/// free, reserved, or bad cluster instead of a real continuation.
+pub struct ClusterChain<'a> {
+    clusters: &'a BTreeMap<ClusterIndex, ClusterValue>,
+    current: Option<ClusterIndex>,
+    max_steps: usize,
+    steps: usize,
+}
+
+impl<'a> Iterator for ClusterChain<'a> {
+    type Item = Result<ClusterIndex, FileSystemError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let current = self.current?;
+
+        if self.steps >= self.max_steps {
+            self.current = None;
+            return Some(Err(FileSystemError::Recursion));
+        }
+        self.steps += 1;
+
+        match self.clusters.get(&current) {
+            Some(ClusterValue::Next(next)) => {
+                self.current = Some(*next);
+                Some(Ok(current))
+            }
+            Some(ClusterValue::EndOfChain) => {
+                self.current = None;
+                Some(Ok(current))
+            }
+            Some(ClusterValue::Free)
+            | Some(ClusterValue::Reserved)
+            | Some(ClusterValue::Bad)
+            | None => {
+                self.current = None;
+                Some(Err(FileSystemError::ClusterNotUsable))
+            }
+        }
+    }
 }
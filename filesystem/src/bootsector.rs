@@ -0,0 +1,123 @@
+use crate::bpb::BiosParameterBlock;
+use crate::error::FileSystemError;
+
+/// Offset within the sector where boot code begins: right after the jump
+/// instruction, OEM name, BPB, and DOS 3.4 EBPB tail that
+/// [`BiosParameterBlock::to_bytes`] lays out.
+const BOOT_CODE_OFFSET: usize = 62;
+
+/// Offset of the boot signature bytes (`0x55 0xAA`), matching
+/// `BiosParameterBlock`'s own constant of the same name.
+const BOOT_SIGNATURE_OFFSET: usize = 510;
+
+/// Which boot code a [`BootSector`] embeds after its BPB.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BootCode {
+    /// Prints a "Non-system disk" message and waits for a keypress before
+    /// rebooting, the way a formatted-but-not-bootable FAT volume does.
+    NonSystem,
+    /// A minimal IPL stub that transfers control to `0x0700:0x0000`, the
+    /// conventional location this crate's own bootstrap-loading code places
+    /// an OS's first-stage loader. Real DOS boot sectors locate and load
+    /// that file themselves by walking the root directory and FAT; this
+    /// crate already writes the loader bytes directly when it formats a
+    /// system disk, so the stub's only remaining job is the jump.
+    Ipl,
+}
+
+/// A complete, bootable 512-byte FAT boot sector: a [`BiosParameterBlock`]
+/// plus a choice of boot code, the part `BiosParameterBlock::to_bytes` leaves
+/// zeroed.
+#[derive(Debug)]
+pub struct BootSector {
+    bpb: BiosParameterBlock,
+    boot_code: BootCode,
+}
+
+impl BootSector {
+    pub fn new(bpb: BiosParameterBlock, boot_code: BootCode) -> Self {
+        BootSector { bpb, boot_code }
+    }
+
+    pub fn bpb(&self) -> &BiosParameterBlock {
+        &self.bpb
+    }
+
+    pub fn boot_code(&self) -> BootCode {
+        self.boot_code
+    }
+
+    /// Parses a complete boot sector back into a `BootSector`, the inverse of
+    /// [`BootSector::to_bytes`]: delegates the BPB fields to
+    /// [`BiosParameterBlock::from_bytes`] (which validates the signature and
+    /// the core geometry fields), then identifies which stub is present from
+    /// its leading opcode byte — `0xEA` (a far jump) for [`BootCode::Ipl`],
+    /// anything else is treated as [`BootCode::NonSystem`].
+    ///
+    /// # Errors
+    ///
+    /// Whatever `BiosParameterBlock::from_bytes` returns.
+    pub fn parse(sector: &[u8; 512]) -> Result<Self, FileSystemError> {
+        let bpb = BiosParameterBlock::from_bytes(sector)?;
+
+        let boot_code = match sector[BOOT_CODE_OFFSET] {
+            0xEA => BootCode::Ipl,
+            _ => BootCode::NonSystem,
+        };
+
+        Ok(BootSector { bpb, boot_code })
+    }
+
+    /// Renders the full 512-byte boot sector: the BPB's jump instruction, OEM
+    /// name, parameter block, and EBPB tail, followed by the selected boot
+    /// code stub and the `0x55 0xAA` signature.
+    pub fn to_bytes(&self) -> [u8; 512] {
+        let mut sector = self.bpb.to_bytes();
+
+        let stub = match self.boot_code {
+            BootCode::NonSystem => Self::non_system_stub(),
+            BootCode::Ipl => Self::ipl_stub(),
+        };
+        sector[BOOT_CODE_OFFSET..BOOT_CODE_OFFSET + stub.len()].copy_from_slice(&stub);
+
+        sector[BOOT_SIGNATURE_OFFSET..BOOT_SIGNATURE_OFFSET + 2].copy_from_slice(&[0x55, 0xAA]);
+
+        sector
+    }
+
+    /// Hand-assembled 8086 real-mode code, relocatable in the sense that it
+    /// only ever branches relative to its own position, never to a fixed
+    /// segment: BIOS always loads a boot sector to `0000:7C00`, so the
+    /// message string is addressed directly rather than computed at runtime.
+    ///
+    /// Prints the message byte by byte via `int 10h` teletype output, then
+    /// waits for a keypress with `int 16h` and reboots via `int 19h`.
+    fn non_system_stub() -> Vec<u8> {
+        const MESSAGE: &[u8] = b"Non-system disk - press any key to reboot\r\n\0";
+
+        let mut stub = vec![
+            0xBE, 0x00, 0x00, // mov si, <msg> (patched below)
+            0xAC, // .print: lodsb
+            0x84, 0xC0, // test al, al
+            0x74, 0x08, // jz .done
+            0xB4, 0x0E, // mov ah, 0x0E
+            0xB3, 0x07, // mov bl, 0x07
+            0xCD, 0x10, // int 0x10
+            0xEB, 0xF3, // jmp .print
+            0x31, 0xC0, // .done: xor ax, ax
+            0xCD, 0x16, // int 0x16
+            0xCD, 0x19, // int 0x19
+        ];
+
+        let msg_offset = 0x7C00 + BOOT_CODE_OFFSET + stub.len();
+        stub[1..3].copy_from_slice(&(msg_offset as u16).to_le_bytes());
+        stub.extend_from_slice(MESSAGE);
+        stub
+    }
+
+    /// A single far jump to `0x0700:0x0000`, where the OS loader this crate
+    /// writes separately is expected to already be staged.
+    fn ipl_stub() -> Vec<u8> {
+        vec![0xEA, 0x00, 0x00, 0x00, 0x07] // jmp far 0700:0000
+    }
+}
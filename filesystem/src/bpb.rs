@@ -1,9 +1,30 @@
 use chrono::{Datelike, Local, Timelike};
-use common::storage::FloppyType;
-use disk::sectorsize::SectorSize;
+use common::storage::Floppy;
+use disk::{geometry::Geometry, sectorsize::SectorSize};
 
+use crate::allocationtable::FatType;
 use crate::error::FileSystemError;
 
+/// Offset of the boot signature bytes (`0x55 0xAA`) within a boot sector.
+const BOOT_SIGNATURE_OFFSET: usize = 510;
+
+/// The boot signature bytes every valid boot sector ends with.
+const BOOT_SIGNATURE: [u8; 2] = [0x55, 0xAA];
+
+/// Size in bytes of a single FAT directory entry.
+const DIRENTRY_SIZE: usize = 32;
+
+/// Extended boot signature value (`0x29`) stamped at offset 38, marking the
+/// DOS 3.4 EBPB fields (volume serial number, label, filesystem type) as present.
+const EXTENDED_BOOT_SIGNATURE: u8 = 0x29;
+
+/// Root directory size for a [`BiosParameterBlock::for_volume`] hard disk
+/// partition, matching the conventional DOS `FORMAT` default.
+const HARD_DISK_ROOT_DIR_ENTRIES: usize = 512;
+
+/// Media descriptor byte for a fixed (non-removable) disk.
+const FIXED_DISK_MEDIA_DESCRIPTOR: u8 = 0xF8;
+
 #[derive(Debug)]
 pub struct BiosParameterBlock {
     bytes_per_sector: usize,
@@ -14,112 +35,49 @@ pub struct BiosParameterBlock {
     logical_sector_count: usize,
     media_descriptor: u8,
     sectors_per_fat: usize,
+    oem_name: [u8; 8],
     // Added with DOS 3.0
-    /* sectors_per_track: usize,
-    number_of_heads: usize,
-    hidden_sectors: usize,
-    // Added with DOS 3.2
-    physical_sector_count: usize,
+    sectors_per_track: u16,
+    number_of_heads: u16,
+    hidden_sectors: u32,
     // Added with DOS 3.4
-    physical_drive_number: usize,
-    flags: usize,
-    extended_boot_signature: usize,
+    physical_drive_number: u8,
+    flags: u8,
     volume_serial_number: u32,
-    volume_label: String,
-    filesystem_type: usize, */
+    volume_label: [u8; 11],
+    filesystem_type: [u8; 8],
 }
 
 impl Default for BiosParameterBlock {
     fn default() -> Self {
         // This is a 160KB floppy disk
-        BiosParameterBlock::new(SectorSize::S512, 1, 1, 64, 320, 0xFE, 2)
+        BiosParameterBlock::new(SectorSize::S512, 1, 1, 2, 64, 320, 0xFE, 2)
     }
 }
 
 impl BiosParameterBlock {
-    /// These values may not be correct. Only F525_160 is currently verified from actual systems.
-    pub fn from_floppytype(floppy_type: &FloppyType) -> Self {
-        match floppy_type {
-            FloppyType::F525_160 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 1,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 64,
-                logical_sector_count: 320,
-                media_descriptor: 0xFE,
-                sectors_per_fat: 1,
-            },
-            FloppyType::F525_180 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 1,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 64,
-                logical_sector_count: 360,
-                media_descriptor: 0xFC,
-                sectors_per_fat: 1,
-            },
-            FloppyType::F525_320 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 2,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 112,
-                logical_sector_count: 640,
-                media_descriptor: 0xFF,
-                sectors_per_fat: 2,
-            },
-            FloppyType::F525_360 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 2,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 112,
-                logical_sector_count: 720,
-                media_descriptor: 0xFD,
-                sectors_per_fat: 2,
-            },
-            FloppyType::F525_1200 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 1,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 224,
-                logical_sector_count: 2400,
-                media_descriptor: 0xF9,
-                sectors_per_fat: 7,
-            },
-            FloppyType::F35_720 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 2,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 112,
-                logical_sector_count: 1440,
-                media_descriptor: 0xF9,
-                sectors_per_fat: 3,
-            },
-            FloppyType::F35_1440 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 1,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 224,
-                logical_sector_count: 2880,
-                media_descriptor: 0xF0,
-                sectors_per_fat: 9,
-            },
-            FloppyType::F35_2880 => BiosParameterBlock {
-                bytes_per_sector: 512,
-                sectors_per_cluster: 2,
-                reserved_sectors: 1,
-                fat_count: 2,
-                root_dir_entries: 240,
-                logical_sector_count: 5760,
-                media_descriptor: 0xF0,
-                sectors_per_fat: 9,
-            },
+    /// Builds the BPB for a standard, never-low-level-reformatted floppy of the
+    /// given type, using the classic DOS-documented geometry for that format.
+    pub fn from_floppytype(floppy_type: &Floppy) -> Self {
+        let geometry = floppy_type.geometry();
+        BiosParameterBlock {
+            bytes_per_sector: floppy_type.sector_size() as usize,
+            sectors_per_cluster: geometry.sectors_per_cluster,
+            reserved_sectors: 1,
+            fat_count: 2,
+            root_dir_entries: geometry.root_dir_entries,
+            logical_sector_count: floppy_type.sector_count() as usize,
+            media_descriptor: geometry.media_descriptor,
+            sectors_per_fat: geometry.sectors_per_fat,
+            oem_name: *b"DOSCNTNR",
+            sectors_per_track: 0,
+            number_of_heads: 0,
+            hidden_sectors: 0,
+            physical_drive_number: 0,
+            flags: 0,
+            volume_serial_number: Self::generate_volume_serial_number(),
+            volume_label: *b"NO NAME    ",
+            filesystem_type: *b"FAT12   ",
         }
     }
 
@@ -127,6 +85,7 @@ impl BiosParameterBlock {
         sector_size: SectorSize,
         sectors_per_cluster: usize,
         reserved_sectors: usize,
+        fat_count: usize,
         root_dir_entries: usize,
         sector_count: usize,
         media_descriptor: u8,
@@ -136,24 +95,293 @@ impl BiosParameterBlock {
             bytes_per_sector: sector_size.as_usize(),
             sectors_per_cluster,
             reserved_sectors,
-            fat_count: 2,
+            fat_count,
             root_dir_entries,
             logical_sector_count: sector_count,
             media_descriptor,
             sectors_per_fat,
-            /* sectors_per_track: todo!(),
-            number_of_heads: todo!(),
-            hidden_sectors: todo!(),
-            physical_sector_count: todo!(),
-            physical_drive_number: todo!(),
-            flags: todo!(),
-            extended_boot_signature: todo!(),
-            volume_serial_number: todo!(),
-            volume_label: todo!(),
-            filesystem_type: todo!(), */
+            oem_name: *b"DOSCNTNR",
+            sectors_per_track: 0,
+            number_of_heads: 0,
+            hidden_sectors: 0,
+            physical_drive_number: 0,
+            flags: 0,
+            volume_serial_number: Self::generate_volume_serial_number(),
+            volume_label: *b"NO NAME    ",
+            filesystem_type: *b"FAT12   ",
+        }
+    }
+
+    /// Builds a BPB for an arbitrary FAT12/FAT16 volume, such as a hard disk
+    /// partition, the way `fatfs`'s format path sizes one: starting from
+    /// `desired_cluster_size` sectors per cluster, the cluster size is doubled
+    /// until the data area's cluster count no longer classifies as FAT32 (which
+    /// this crate doesn't support), and `sectors_per_fat` is solved by the
+    /// standard fixed-point iteration (guess a FAT size, see how many clusters
+    /// that leaves room for, recompute the FAT size those clusters actually
+    /// need, repeat until the guess stops changing).
+    ///
+    /// `media_descriptor` is fixed at `0xF8`, the standard value for a fixed
+    /// disk, and the root directory is sized for 512 entries, the conventional
+    /// hard disk default. `sectors_per_track`, `number_of_heads`, and
+    /// `hidden_sectors` default to zero; set them with
+    /// [`BiosParameterBlock::set_geometry`] and
+    /// [`BiosParameterBlock::set_hidden_sectors`].
+    pub fn for_volume(
+        total_sectors: usize,
+        bytes_per_sector: usize,
+        desired_cluster_size: usize,
+    ) -> Self {
+        const FAT_COUNT: usize = 2;
+        const RESERVED_SECTORS: usize = 1;
+
+        let root_dir_entries = HARD_DISK_ROOT_DIR_ENTRIES;
+        let root_dir_sectors = (root_dir_entries * DIRENTRY_SIZE).div_ceil(bytes_per_sector);
+
+        let mut sectors_per_cluster = desired_cluster_size.max(1);
+        let sectors_per_fat = loop {
+            let sectors_per_fat = Self::solve_sectors_per_fat(
+                total_sectors,
+                bytes_per_sector,
+                RESERVED_SECTORS,
+                FAT_COUNT,
+                root_dir_sectors,
+                sectors_per_cluster,
+            );
+
+            let cluster_count = Self::data_cluster_count(
+                total_sectors,
+                RESERVED_SECTORS,
+                FAT_COUNT,
+                sectors_per_fat,
+                root_dir_sectors,
+                sectors_per_cluster,
+            );
+
+            if FatType::for_cluster_count(cluster_count) != FatType::Fat32
+                || sectors_per_cluster >= 128
+            {
+                break sectors_per_fat;
+            }
+            sectors_per_cluster *= 2;
+        };
+
+        let cluster_count = Self::data_cluster_count(
+            total_sectors,
+            RESERVED_SECTORS,
+            FAT_COUNT,
+            sectors_per_fat,
+            root_dir_sectors,
+            sectors_per_cluster,
+        );
+        let filesystem_type: [u8; 8] = match FatType::for_cluster_count(cluster_count) {
+            FatType::Fat12 => *b"FAT12   ",
+            FatType::Fat16 | FatType::Fat32 => *b"FAT16   ",
+        };
+
+        Self {
+            bytes_per_sector,
+            sectors_per_cluster,
+            reserved_sectors: RESERVED_SECTORS,
+            fat_count: FAT_COUNT,
+            root_dir_entries,
+            logical_sector_count: total_sectors,
+            media_descriptor: FIXED_DISK_MEDIA_DESCRIPTOR,
+            sectors_per_fat,
+            oem_name: *b"DOSCNTNR",
+            sectors_per_track: 0,
+            number_of_heads: 0,
+            hidden_sectors: 0,
+            physical_drive_number: 0,
+            flags: 0,
+            volume_serial_number: Self::generate_volume_serial_number(),
+            volume_label: *b"NO NAME    ",
+            filesystem_type,
         }
     }
 
+    /// Number of data-region clusters a volume with this layout has room for.
+    fn data_cluster_count(
+        total_sectors: usize,
+        reserved_sectors: usize,
+        fat_count: usize,
+        sectors_per_fat: usize,
+        root_dir_sectors: usize,
+        sectors_per_cluster: usize,
+    ) -> usize {
+        let non_data_sectors = reserved_sectors + fat_count * sectors_per_fat + root_dir_sectors;
+        let data_sectors = total_sectors.saturating_sub(non_data_sectors);
+        data_sectors / sectors_per_cluster
+    }
+
+    /// Solves for `sectors_per_fat` by fixed-point iteration: starting from a
+    /// guess of 1 sector, computes the cluster count that guess leaves room
+    /// for, works out the FAT size those clusters actually require, and
+    /// repeats until the guess stops changing.
+    fn solve_sectors_per_fat(
+        total_sectors: usize,
+        bytes_per_sector: usize,
+        reserved_sectors: usize,
+        fat_count: usize,
+        root_dir_sectors: usize,
+        sectors_per_cluster: usize,
+    ) -> usize {
+        let mut sectors_per_fat = 1;
+        loop {
+            let cluster_count = Self::data_cluster_count(
+                total_sectors,
+                reserved_sectors,
+                fat_count,
+                sectors_per_fat,
+                root_dir_sectors,
+                sectors_per_cluster,
+            );
+
+            let bits_per_entry: usize = match FatType::for_cluster_count(cluster_count) {
+                FatType::Fat12 => 12,
+                FatType::Fat16 | FatType::Fat32 => 16,
+            };
+            let fat_bytes = ((cluster_count + 2) * bits_per_entry).div_ceil(8);
+            let next_sectors_per_fat = fat_bytes.div_ceil(bytes_per_sector).max(1);
+
+            if next_sectors_per_fat == sectors_per_fat {
+                return sectors_per_fat;
+            }
+            sectors_per_fat = next_sectors_per_fat;
+        }
+    }
+
+    /// Parses a standard FAT boot sector back into a `BiosParameterBlock`,
+    /// the inverse of [`BiosParameterBlock::to_bytes`]. Doesn't attempt to
+    /// recover the PC-DOS 1.x bare-BPB case (no BPB fields written at all);
+    /// see [`crate::deserializer::GeometryInference`] for that.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::InvalidBootSignature` if bytes 510-511 aren't
+    /// `0x55 0xAA`, `FileSystemError::InvalidBytesPerSector` if the
+    /// `bytes_per_sector` field is zero or not a power of two, or
+    /// `FileSystemError::InvalidSectorsPerCluster` if `sectors_per_cluster` is
+    /// zero. A third-party or hand-crafted image failing any of these isn't a
+    /// valid, mountable FAT volume.
+    pub fn from_bytes(sector: &[u8; 512]) -> Result<Self, FileSystemError> {
+        if sector[BOOT_SIGNATURE_OFFSET..BOOT_SIGNATURE_OFFSET + 2] != BOOT_SIGNATURE {
+            return Err(FileSystemError::InvalidBootSignature);
+        }
+
+        let mut oem_name = [0u8; 8];
+        oem_name.copy_from_slice(&sector[3..11]);
+
+        let bytes_per_sector = u16::from_le_bytes([sector[11], sector[12]]) as usize;
+        if bytes_per_sector == 0 || !bytes_per_sector.is_power_of_two() {
+            return Err(FileSystemError::InvalidBytesPerSector);
+        }
+
+        let sectors_per_cluster = sector[13] as usize;
+        if sectors_per_cluster == 0 {
+            return Err(FileSystemError::InvalidSectorsPerCluster);
+        }
+        let reserved_sectors = u16::from_le_bytes([sector[14], sector[15]]) as usize;
+        let fat_count = sector[16] as usize;
+        let root_dir_entries = u16::from_le_bytes([sector[17], sector[18]]) as usize;
+
+        let small_sector_count = u16::from_le_bytes([sector[19], sector[20]]) as usize;
+        let media_descriptor = sector[21];
+        let sectors_per_fat = u16::from_le_bytes([sector[22], sector[23]]) as usize;
+        let sectors_per_track = u16::from_le_bytes([sector[24], sector[25]]);
+        let number_of_heads = u16::from_le_bytes([sector[26], sector[27]]);
+        let hidden_sectors = u32::from_le_bytes([sector[28], sector[29], sector[30], sector[31]]);
+        let large_sector_count =
+            u32::from_le_bytes([sector[32], sector[33], sector[34], sector[35]]) as usize;
+        let logical_sector_count = if small_sector_count != 0 {
+            small_sector_count
+        } else {
+            large_sector_count
+        };
+
+        let physical_drive_number = sector[36];
+        let flags = sector[37];
+        let volume_serial_number =
+            u32::from_le_bytes([sector[39], sector[40], sector[41], sector[42]]);
+
+        let mut volume_label = [0u8; 11];
+        volume_label.copy_from_slice(&sector[43..54]);
+        let mut filesystem_type = [0u8; 8];
+        filesystem_type.copy_from_slice(&sector[54..62]);
+
+        Ok(Self {
+            bytes_per_sector,
+            sectors_per_cluster,
+            reserved_sectors,
+            fat_count,
+            root_dir_entries,
+            logical_sector_count,
+            media_descriptor,
+            sectors_per_fat,
+            oem_name,
+            sectors_per_track,
+            number_of_heads,
+            hidden_sectors,
+            physical_drive_number,
+            flags,
+            volume_serial_number,
+            volume_label,
+            filesystem_type,
+        })
+    }
+
+    pub fn bytes_per_sector(&self) -> usize {
+        self.bytes_per_sector
+    }
+
+    /// Total number of sectors in the volume, recovered from whichever of
+    /// the 16-bit or 32-bit sector-count fields [`BiosParameterBlock::to_bytes`]
+    /// actually populated.
+    pub fn logical_sector_count(&self) -> usize {
+        self.logical_sector_count
+    }
+
+    pub fn media_descriptor(&self) -> u8 {
+        self.media_descriptor
+    }
+
+    pub fn sectors_per_cluster(&self) -> usize {
+        self.sectors_per_cluster
+    }
+
+    pub fn reserved_sectors(&self) -> usize {
+        self.reserved_sectors
+    }
+
+    pub fn fat_count(&self) -> usize {
+        self.fat_count
+    }
+
+    pub fn sectors_per_fat(&self) -> usize {
+        self.sectors_per_fat
+    }
+
+    pub fn root_dir_entries(&self) -> usize {
+        self.root_dir_entries
+    }
+
+    /// Number of sectors the root directory region occupies.
+    pub fn root_dir_sectors(&self) -> usize {
+        (self.root_dir_entries * DIRENTRY_SIZE).div_ceil(self.bytes_per_sector)
+    }
+
+    /// Sector, relative to the start of the volume, where the root directory
+    /// region begins: right after the reserved area and all FAT copies.
+    pub fn root_dir_start_sector(&self) -> usize {
+        self.reserved_sectors + self.fat_count * self.sectors_per_fat
+    }
+
+    /// Sector, relative to the start of the volume, where the data region
+    /// (cluster 2 onward) begins: right after the root directory region.
+    pub fn data_region_start(&self) -> usize {
+        self.root_dir_start_sector() + self.root_dir_sectors()
+    }
+
     pub fn set_sectors_per_cluster(&mut self, sector_count: usize) -> Result<(), FileSystemError> {
         match sector_count {
             1 => self.sectors_per_cluster = 1,
@@ -169,6 +397,98 @@ impl BiosParameterBlock {
         Ok(())
     }
 
+    /// Overrides the OEM name field this BPB's boot sector carries. Defaults
+    /// to `"DOSCNTNR"`; real DOS tools stamp their own name here, so callers
+    /// reproducing a specific historical disk may want to match it.
+    pub fn set_oem_name(&mut self, oem_name: [u8; 8]) {
+        self.oem_name = oem_name;
+    }
+
+    /// Sets the sectors-per-track and number-of-heads fields from a disk's CHS
+    /// `geometry`. These aren't properties of the volume itself, the same way an
+    /// MBR partition entry's CHS fields are derived from the disk rather than the
+    /// partition, so they're supplied separately instead of at construction time.
+    pub fn set_geometry(&mut self, geometry: &Geometry) {
+        self.sectors_per_track = geometry.sectors() as u16;
+        self.number_of_heads = geometry.heads() as u16;
+    }
+
+    /// Sets the number of sectors preceding this volume on the disk (i.e. the
+    /// partition's starting LBA), stamped into the boot sector's hidden-sectors
+    /// field. Zero for a volume that starts at the beginning of the disk, such as
+    /// a floppy.
+    pub fn set_hidden_sectors(&mut self, hidden_sectors: u32) {
+        self.hidden_sectors = hidden_sectors;
+    }
+
+    /// Overrides the volume label stamped into the DOS 3.4 EBPB. Defaults to the
+    /// classic `"NO NAME"`, space-padded to 11 bytes; callers with a real label
+    /// should pre-pad it themselves, matching [`BiosParameterBlock::set_oem_name`]'s
+    /// convention of taking the on-disk bytes directly.
+    pub fn set_volume_label(&mut self, volume_label: [u8; 11]) {
+        self.volume_label = volume_label;
+    }
+
+    /// Overrides the filesystem-type string stamped into the DOS 3.4 EBPB.
+    /// Defaults to `"FAT12"`, space-padded to 8 bytes; callers formatting a
+    /// FAT16 volume should pass `*b"FAT16   "`.
+    pub fn set_filesystem_type(&mut self, filesystem_type: [u8; 8]) {
+        self.filesystem_type = filesystem_type;
+    }
+
+    /// Renders this BPB into a complete 512-byte FAT boot sector: the DOS 2.0
+    /// core BPB, the DOS 3.0/3.2 extensions (sectors-per-track, heads, hidden
+    /// sectors, and a 32-bit sector count for volumes too large for the 16-bit
+    /// field), the DOS 3.4 EBPB (physical drive number, extended boot signature,
+    /// volume serial number, label, and filesystem type), and the `0x55 0xAA`
+    /// boot signature. The jump instruction is a fixed value, since this crate
+    /// never emits real bootstrap code here.
+    ///
+    /// `sectors_per_track`/`number_of_heads` and `hidden_sectors` default to zero
+    /// until set via [`BiosParameterBlock::set_geometry`] and
+    /// [`BiosParameterBlock::set_hidden_sectors`].
+    pub fn to_bytes(&self) -> [u8; 512] {
+        let mut sector = [0u8; 512];
+
+        // A short jump over the (absent) bootstrap code, then a NOP, as real boot
+        // sectors do.
+        sector[0..3].copy_from_slice(&[0xEB, 0x3C, 0x90]);
+        sector[3..11].copy_from_slice(&self.oem_name);
+
+        sector[11..13].copy_from_slice(&(self.bytes_per_sector as u16).to_le_bytes());
+        sector[13] = self.sectors_per_cluster as u8;
+        sector[14..16].copy_from_slice(&(self.reserved_sectors as u16).to_le_bytes());
+        sector[16] = self.fat_count as u8;
+        sector[17..19].copy_from_slice(&(self.root_dir_entries as u16).to_le_bytes());
+
+        // A volume too large for the 16-bit total-sectors field reports zero
+        // there and carries its real count in the 32-bit field at 32..36 instead.
+        let small_sector_count = u16::try_from(self.logical_sector_count).unwrap_or(0);
+        let large_sector_count = if small_sector_count == 0 {
+            self.logical_sector_count as u32
+        } else {
+            0
+        };
+        sector[19..21].copy_from_slice(&small_sector_count.to_le_bytes());
+        sector[21] = self.media_descriptor;
+        sector[22..24].copy_from_slice(&(self.sectors_per_fat as u16).to_le_bytes());
+        sector[24..26].copy_from_slice(&self.sectors_per_track.to_le_bytes());
+        sector[26..28].copy_from_slice(&self.number_of_heads.to_le_bytes());
+        sector[28..32].copy_from_slice(&self.hidden_sectors.to_le_bytes());
+        sector[32..36].copy_from_slice(&large_sector_count.to_le_bytes());
+
+        sector[36] = self.physical_drive_number;
+        sector[37] = self.flags;
+        sector[38] = EXTENDED_BOOT_SIGNATURE;
+        sector[39..43].copy_from_slice(&self.volume_serial_number.to_le_bytes());
+        sector[43..54].copy_from_slice(&self.volume_label);
+        sector[54..62].copy_from_slice(&self.filesystem_type);
+
+        sector[BOOT_SIGNATURE_OFFSET..BOOT_SIGNATURE_OFFSET + 2].copy_from_slice(&BOOT_SIGNATURE);
+
+        sector
+    }
+
     pub fn generate_volume_serial_number() -> u32 {
         let now = Local::now();
 
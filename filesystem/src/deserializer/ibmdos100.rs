@@ -0,0 +1,456 @@
+use chrono::{NaiveDate, NaiveDateTime, NaiveTime};
+use common::storage::Floppy;
+
+use crate::{
+    allocationtable::{AllocationTable, ClusterValue, FatType},
+    direntry::DirEntry,
+    error::FileSystemError,
+    serializer::ibmdos100::IbmDos100,
+};
+
+use super::{
+    DirEntryDeserializer, DirectoryDeserializer, Fat12Deserializer, Fat16Deserializer,
+    Fat32Deserializer, FatTableDeserializer, GeometryInference,
+};
+
+/// Range, within a boot sector, that a real BPB occupies from the
+/// bytes-per-sector field through sectors-per-FAT. PC-DOS 1.x never wrote
+/// one; these bytes are left at zero on its floppies.
+const BPB_REGION: std::ops::Range<usize> = 11..24;
+
+/// The only floppy formats PC-DOS 1.x ever shipped a BPB-less boot sector
+/// for (see `planner::ossupport::SUPPORTED_OS`'s `IBMDOS100`/`IBMDOS110`/
+/// `IBMDOS200` entries).
+const BARE_BPB_FLOPPIES: [Floppy; 3] = [Floppy::F525_160, Floppy::F525_180, Floppy::F525_360];
+
+impl IbmDos100 {
+    /// Recovers a short name's `"NAME.EXT"` string form from its raw 11-byte
+    /// on-disk representation, the inverse of `serialize_entryname`.
+    fn decode_entryname(raw: &[u8]) -> Result<String, FileSystemError> {
+        let filename = String::from_utf8_lossy(&raw[0..8]).trim_end().to_string();
+        let extension = String::from_utf8_lossy(&raw[8..11]).trim_end().to_string();
+
+        if filename.is_empty() {
+            return Err(FileSystemError::EmptyFileName);
+        }
+
+        if extension.is_empty() {
+            Ok(filename)
+        } else {
+            Ok(format!("{filename}.{extension}"))
+        }
+    }
+
+    /// Recovers a creation timestamp from its packed date/time fields, the
+    /// inverse of `encode_date`/`encode_time`.
+    fn decode_datetime(date: u16, time: u16) -> Result<NaiveDateTime, FileSystemError> {
+        let year = 1980 + (date >> 9) as i32;
+        let month = ((date >> 5) & 0x0F) as u32;
+        let day = (date & 0x1F) as u32;
+
+        let hour = (time >> 11) as u32;
+        let minute = ((time >> 5) & 0x3F) as u32;
+        let second = ((time & 0x1F) as u32) * 2;
+
+        let date = NaiveDate::from_ymd_opt(year, month, day)
+            .ok_or(FileSystemError::InvalidDirectoryTimestamp)?;
+        let time = NaiveTime::from_hms_opt(hour, minute, second)
+            .ok_or(FileSystemError::InvalidDirectoryTimestamp)?;
+
+        Ok(NaiveDateTime::new(date, time))
+    }
+}
+
+impl Fat12Deserializer for IbmDos100 {
+    fn deserialize_fat12(
+        bytes: &[u8],
+        cluster_count: usize,
+    ) -> Result<AllocationTable, FileSystemError> {
+        const FAT12_MASK: u16 = 0x0FFF;
+
+        // Walk three packed bytes at a time, the exact reverse of
+        // `serialize_fat12`'s packing. An odd trailing byte (or two) can't
+        // form a full pair of entries and is dropped, same as that
+        // function's own resize-to-sector-boundary padding.
+        let mut raw_entries: Vec<u16> = Vec::with_capacity(cluster_count + 2);
+        for triplet in bytes.chunks_exact(3) {
+            let (b0, b1, b2) = (triplet[0] as u16, triplet[1] as u16, triplet[2] as u16);
+            raw_entries.push((b0 | ((b1 & 0x0F) << 8)) & FAT12_MASK);
+            raw_entries.push(((b1 >> 4) | (b2 << 4)) & FAT12_MASK);
+        }
+
+        let mut table = AllocationTable::new(FatType::Fat12, 512, cluster_count)?;
+
+        // Entries 0 and 1 are the reserved media-descriptor/end-of-chain
+        // markers `serialize_fat12` writes, not real cluster links.
+        for (index, &raw) in raw_entries.iter().enumerate().skip(2) {
+            let value = match raw {
+                0x000 => continue,
+                0xFF7 => ClusterValue::Bad,
+                0xFF0..=0xFF6 => ClusterValue::Reserved,
+                n if n >= 0xFF8 => ClusterValue::EndOfChain,
+                n => ClusterValue::Next(n as usize),
+            };
+            table.set_cluster(index, value)?;
+        }
+
+        Ok(table)
+    }
+}
+
+impl Fat16Deserializer for IbmDos100 {
+    fn deserialize_fat16(
+        bytes: &[u8],
+        cluster_count: usize,
+    ) -> Result<AllocationTable, FileSystemError> {
+        const FAT16_EOC: u16 = 0xFFF8;
+
+        let mut table = AllocationTable::new(FatType::Fat16, 512, cluster_count)?;
+
+        // Entries 0 and 1 are the reserved media-descriptor/end-of-chain
+        // markers `serialize_fat16` writes, not real cluster links.
+        for (index, raw) in bytes
+            .chunks_exact(2)
+            .map(|pair| u16::from_le_bytes([pair[0], pair[1]]))
+            .enumerate()
+            .skip(2)
+        {
+            let value = match raw {
+                0x0000 => continue,
+                0xFFF7 => ClusterValue::Bad,
+                0xFFF0..=0xFFF6 => ClusterValue::Reserved,
+                n if n >= FAT16_EOC => ClusterValue::EndOfChain,
+                n => ClusterValue::Next(n as usize),
+            };
+            table.set_cluster(index, value)?;
+        }
+
+        Ok(table)
+    }
+}
+
+impl Fat32Deserializer for IbmDos100 {
+    fn deserialize_fat32(
+        bytes: &[u8],
+        cluster_count: usize,
+    ) -> Result<AllocationTable, FileSystemError> {
+        const FAT32_MASK: u32 = 0x0FFF_FFFF;
+        const FAT32_EOC: u32 = 0x0FFF_FFF8;
+
+        let mut table = AllocationTable::new(FatType::Fat32, 512, cluster_count)?;
+
+        // Entries 0 and 1 are the reserved media-descriptor/end-of-chain
+        // markers `serialize_fat32` writes, not real cluster links.
+        for (index, raw) in bytes
+            .chunks_exact(4)
+            .map(|quad| u32::from_le_bytes([quad[0], quad[1], quad[2], quad[3]]) & FAT32_MASK)
+            .enumerate()
+            .skip(2)
+        {
+            let value = match raw {
+                0x0000_0000 => continue,
+                0x0FFF_FFF7 => ClusterValue::Bad,
+                0x0FFF_FFF0..=0x0FFF_FFF6 => ClusterValue::Reserved,
+                n if n >= FAT32_EOC => ClusterValue::EndOfChain,
+                n => ClusterValue::Next(n as usize),
+            };
+            table.set_cluster(index, value)?;
+        }
+
+        Ok(table)
+    }
+}
+
+impl FatTableDeserializer for IbmDos100 {
+    fn deserialize_fat_table(
+        bytes: &[u8],
+        cluster_count: usize,
+        fat_type: &FatType,
+    ) -> Result<AllocationTable, FileSystemError> {
+        match fat_type {
+            FatType::Fat12 => <IbmDos100 as Fat12Deserializer>::deserialize_fat12(bytes, cluster_count),
+            FatType::Fat16 => <IbmDos100 as Fat16Deserializer>::deserialize_fat16(bytes, cluster_count),
+            FatType::Fat32 => <IbmDos100 as Fat32Deserializer>::deserialize_fat32(bytes, cluster_count),
+        }
+    }
+}
+
+impl DirEntryDeserializer for IbmDos100 {
+    fn deserialize_direntry(
+        bytes: &[u8; 32],
+        parent: &DirEntry,
+    ) -> Result<DirEntry, FileSystemError> {
+        let attribute_byte = bytes[11];
+        let is_volume_label = attribute_byte & 0x08 != 0;
+        let is_subdir = attribute_byte & 0x10 != 0;
+        let is_system = attribute_byte & 0x04 != 0;
+
+        let name = Self::decode_entryname(&bytes[0..11])?;
+
+        // A real subdirectory's first two on-disk entries are "." and
+        // "..", which `EntryName::from_str` (and so `DirEntry::new_directory`)
+        // rejects outright since callers naming their own entries can't use
+        // them. `DirEntry::new_dot`/`new_dotdot` build these the same way
+        // `Pool::create_dir` does, so they're reconstructed here instead.
+        let mut entry = if name == "." {
+            DirEntry::new_dot(*parent.uuid())
+        } else if name == ".." {
+            DirEntry::new_dotdot(*parent.uuid())
+        } else if is_volume_label {
+            DirEntry::new_volume_label(&name)?
+        } else if is_subdir {
+            DirEntry::new_directory(&name)?
+        } else if is_system {
+            DirEntry::new_sysfile(&name)?
+        } else {
+            DirEntry::new_file(&name)?
+        };
+
+        entry.set_parent(parent);
+
+        let time = u16::from_le_bytes([bytes[22], bytes[23]]);
+        let date = u16::from_le_bytes([bytes[24], bytes[25]]);
+        // A zero date field carries no timestamp; the entry keeps its
+        // default creation time then.
+        if date != 0 {
+            entry.set_creation_time(Self::decode_datetime(date, time)?);
+        }
+
+        let start_cluster = u16::from_le_bytes([bytes[26], bytes[27]]);
+        if start_cluster != 0 {
+            entry.set_start_cluster(start_cluster as usize);
+        }
+
+        let file_size = u32::from_le_bytes([bytes[28], bytes[29], bytes[30], bytes[31]]);
+        entry.set_filesize(file_size as usize);
+
+        Ok(entry)
+    }
+}
+
+impl DirectoryDeserializer for IbmDos100 {
+    fn deserialize_directory(
+        bytes: &[u8],
+        parent: &DirEntry,
+        fat: &AllocationTable,
+    ) -> Result<Vec<DirEntry>, FileSystemError> {
+        let mut entries = Vec::new();
+
+        for slot in bytes.chunks_exact(32) {
+            match slot[0] {
+                0x00 => break,
+                0xE5 => continue,
+                _ => {}
+            }
+
+            let slot: &[u8; 32] = slot
+                .try_into()
+                .expect("chunks_exact(32) always yields 32 bytes");
+            let mut entry =
+                <IbmDos100 as DirEntryDeserializer>::deserialize_direntry(slot, parent)?;
+
+            if entry.is_file() {
+                if let Some(start) = entry.start_cluster() {
+                    let cluster_map = fat
+                        .chain(start)
+                        .collect::<Result<Vec<_>, FileSystemError>>()?;
+                    entry.set_cluster_map(&cluster_map);
+                }
+            }
+
+            entries.push(entry);
+        }
+
+        Ok(entries)
+    }
+}
+
+impl GeometryInference for IbmDos100 {
+    fn infer_floppy_geometry(
+        boot_sector: &[u8; 512],
+        floppy_sector_count: u64,
+    ) -> Result<Floppy, FileSystemError> {
+        let has_jump_header = matches!(boot_sector[0], 0xEB | 0xE9);
+        let bpb_is_empty = boot_sector[BPB_REGION].iter().all(|&b| b == 0);
+
+        if !has_jump_header || !bpb_is_empty {
+            return Err(FileSystemError::GeometryNotInferable);
+        }
+
+        BARE_BPB_FLOPPIES
+            .into_iter()
+            .find(|floppy| floppy.sector_count() == floppy_sector_count)
+            .ok_or(FileSystemError::UnrecognizedMediaSize)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::serializer::{Fat12Serializer, Fat16Serializer, Fat32Serializer, NameSerializer};
+
+    #[test]
+    fn fat16_roundtrips_a_simple_chain() {
+        let mut fat = AllocationTable::new(FatType::Fat16, 512, 10).unwrap();
+        fat.allocate(2, Some(3)).unwrap();
+        fat.allocate(3, None).unwrap();
+
+        let bytes = <IbmDos100 as Fat16Serializer>::serialize_fat16(&fat).unwrap();
+        let decoded = <IbmDos100 as Fat16Deserializer>::deserialize_fat16(&bytes, 10).unwrap();
+
+        assert!(matches!(
+            decoded.clusters().get(&2),
+            Some(ClusterValue::Next(3))
+        ));
+        assert!(matches!(
+            decoded.clusters().get(&3),
+            Some(ClusterValue::EndOfChain)
+        ));
+    }
+
+    #[test]
+    fn fat32_roundtrips_a_simple_chain() {
+        let mut fat = AllocationTable::new(FatType::Fat32, 512, 10).unwrap();
+        fat.allocate(2, Some(3)).unwrap();
+        fat.allocate(3, None).unwrap();
+
+        let bytes = <IbmDos100 as Fat32Serializer>::serialize_fat32(&fat).unwrap();
+        let decoded = <IbmDos100 as Fat32Deserializer>::deserialize_fat32(&bytes, 10).unwrap();
+
+        assert!(matches!(
+            decoded.clusters().get(&2),
+            Some(ClusterValue::Next(3))
+        ));
+        assert!(matches!(
+            decoded.clusters().get(&3),
+            Some(ClusterValue::EndOfChain)
+        ));
+    }
+
+    #[test]
+    fn fat12_roundtrips_a_simple_chain() {
+        let mut fat = AllocationTable::new(FatType::Fat12, 512, 10).unwrap();
+        fat.allocate(2, Some(3)).unwrap();
+        fat.allocate(3, None).unwrap();
+
+        let bytes = <IbmDos100 as Fat12Serializer>::serialize_fat12(&fat).unwrap();
+        let decoded = <IbmDos100 as Fat12Deserializer>::deserialize_fat12(&bytes, 10).unwrap();
+
+        assert!(matches!(
+            decoded.clusters().get(&2),
+            Some(ClusterValue::Next(3))
+        ));
+        assert!(matches!(
+            decoded.clusters().get(&3),
+            Some(ClusterValue::EndOfChain)
+        ));
+    }
+
+    fn raw_direntry(name: &str, attribute: u8, start_cluster: u16, file_size: u32) -> [u8; 32] {
+        let mut buf = [0u8; 32];
+        let name_bytes = IbmDos100::serialize_entryname(&name.parse().unwrap()).unwrap();
+        buf[0..11].copy_from_slice(&name_bytes);
+        buf[11] = attribute;
+        buf[26..28].copy_from_slice(&start_cluster.to_le_bytes());
+        buf[28..32].copy_from_slice(&file_size.to_le_bytes());
+        buf
+    }
+
+    #[test]
+    fn deserializes_a_regular_file_entry() {
+        let parent = DirEntry::new_rootdir();
+        let raw = raw_direntry("FOO.TXT", 0x20, 2, 1920);
+
+        let entry =
+            <IbmDos100 as DirEntryDeserializer>::deserialize_direntry(&raw, &parent).unwrap();
+
+        assert!(entry.is_file());
+        assert_eq!(entry.start_cluster(), Some(2));
+        assert_eq!(entry.file_size(), 1920);
+        assert_eq!(entry.parent(), Some(parent.uuid()));
+    }
+
+    #[test]
+    fn directory_walk_stops_at_end_marker_skips_deleted_and_follows_chain() {
+        let parent = DirEntry::new_rootdir();
+
+        let mut deleted = raw_direntry("GONE.TXT", 0x20, 0, 0);
+        deleted[0] = 0xE5;
+
+        let mut bytes = Vec::new();
+        bytes.extend_from_slice(&deleted);
+        bytes.extend_from_slice(&raw_direntry("FOO.TXT", 0x20, 2, 1024));
+        bytes.extend_from_slice(&[0u8; 32]); // end-of-directory marker
+
+        let mut fat = AllocationTable::new(FatType::Fat12, 512, 10).unwrap();
+        fat.allocate(2, Some(3)).unwrap();
+        fat.allocate(3, None).unwrap();
+
+        let entries =
+            <IbmDos100 as DirectoryDeserializer>::deserialize_directory(&bytes, &parent, &fat)
+                .unwrap();
+
+        assert_eq!(entries.len(), 1);
+        assert_eq!(entries[0].name().unwrap().to_string(), "FOO.TXT");
+        assert_eq!(entries[0].cluster_map(), &[2, 3]);
+    }
+
+    #[test]
+    fn deserializes_dot_and_dotdot_entries_instead_of_rejecting_them() {
+        let parent = DirEntry::new_rootdir();
+
+        // "." and ".." can't go through `raw_direntry`: it parses the name
+        // via `EntryName::from_str`, which rejects both outright. Their
+        // on-disk form is the short name left-justified and space-padded,
+        // same as any other entry.
+        let mut raw = [0u8; 32];
+        raw[0..11].fill(0x20);
+        raw[0] = b'.';
+        raw[11] = 0x10; // subdirectory attribute
+        raw[26..28].copy_from_slice(&5u16.to_le_bytes());
+
+        let entry =
+            <IbmDos100 as DirEntryDeserializer>::deserialize_direntry(&raw, &parent).unwrap();
+
+        assert!(entry.is_directory());
+        assert_eq!(entry.name().unwrap().to_string(), ".");
+        assert_eq!(entry.parent(), Some(parent.uuid()));
+    }
+
+    #[test]
+    fn infers_160k_geometry_from_bare_bpb() {
+        let mut sector = [0u8; 512];
+        sector[0] = 0xEB;
+        let geometry = <IbmDos100 as GeometryInference>::infer_floppy_geometry(
+            &sector,
+            Floppy::F525_160.sector_count(),
+        )
+        .unwrap();
+        assert_eq!(geometry, Floppy::F525_160);
+    }
+
+    #[test]
+    fn rejects_geometry_inference_when_bpb_is_populated() {
+        let mut sector = [0u8; 512];
+        sector[0] = 0xEB;
+        sector[11] = 0x02; // bytes-per-sector low byte, a real BPB would set this
+        let err = <IbmDos100 as GeometryInference>::infer_floppy_geometry(
+            &sector,
+            Floppy::F525_160.sector_count(),
+        )
+        .unwrap_err();
+        assert_eq!(err, FileSystemError::GeometryNotInferable);
+    }
+
+    #[test]
+    fn rejects_geometry_inference_for_unknown_media_size() {
+        let mut sector = [0u8; 512];
+        sector[0] = 0xEB;
+        let err = <IbmDos100 as GeometryInference>::infer_floppy_geometry(
+            &sector,
+            Floppy::F35_1440.sector_count(),
+        )
+        .unwrap_err();
+        assert_eq!(err, FileSystemError::UnrecognizedMediaSize);
+    }
+}
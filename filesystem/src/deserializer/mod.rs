@@ -0,0 +1,77 @@
+use common::storage::Floppy;
+
+use crate::{
+    allocationtable::{AllocationTable, FatType},
+    direntry::DirEntry,
+    error::FileSystemError,
+};
+
+pub mod ibmdos100;
+
+/// Reconstructs an `AllocationTable` from a FAT12 table's on-disk bytes: the
+/// exact inverse of `Fat12Serializer::serialize_fat12`.
+pub trait Fat12Deserializer {
+    fn deserialize_fat12(
+        bytes: &[u8],
+        cluster_count: usize,
+    ) -> Result<AllocationTable, FileSystemError>;
+}
+
+/// Reconstructs an `AllocationTable` from a FAT16 table's on-disk bytes: the
+/// exact inverse of `Fat16Serializer::serialize_fat16`.
+pub trait Fat16Deserializer {
+    fn deserialize_fat16(
+        bytes: &[u8],
+        cluster_count: usize,
+    ) -> Result<AllocationTable, FileSystemError>;
+}
+
+/// Reconstructs an `AllocationTable` from a FAT32 table's on-disk bytes: the
+/// exact inverse of `Fat32Serializer::serialize_fat32`.
+pub trait Fat32Deserializer {
+    fn deserialize_fat32(
+        bytes: &[u8],
+        cluster_count: usize,
+    ) -> Result<AllocationTable, FileSystemError>;
+}
+
+/// Reconstructs an `AllocationTable` from its on-disk bytes regardless of
+/// FAT width, dispatching to the unpacking rule `fat_type` calls for. The
+/// read-back counterpart of `FatTableSerializer::serialize_fat_table`.
+pub trait FatTableDeserializer {
+    fn deserialize_fat_table(
+        bytes: &[u8],
+        cluster_count: usize,
+        fat_type: &FatType,
+    ) -> Result<AllocationTable, FileSystemError>;
+}
+
+/// Parses one 32-byte directory slot back into a `DirEntry`. Callers are
+/// expected to have already skipped the `0x00` end-of-directory marker and
+/// `0xE5` deleted-entry markers themselves.
+pub trait DirEntryDeserializer {
+    fn deserialize_direntry(
+        bytes: &[u8; 32],
+        parent: &DirEntry,
+    ) -> Result<DirEntry, FileSystemError>;
+}
+
+/// Walks a directory region's raw bytes into its live `DirEntry`s, stopping
+/// at the first `0x00` end marker, skipping `0xE5` deleted slots, and
+/// following `fat` to recover each file's cluster run.
+pub trait DirectoryDeserializer {
+    fn deserialize_directory(
+        bytes: &[u8],
+        parent: &DirEntry,
+        fat: &AllocationTable,
+    ) -> Result<Vec<DirEntry>, FileSystemError>;
+}
+
+/// Infers the floppy geometry a boot sector implies when it carries no BPB
+/// at all, as PC-DOS 1.x's boot sectors didn't.
+pub trait GeometryInference {
+    fn infer_floppy_geometry(
+        boot_sector: &[u8; 512],
+        floppy_sector_count: u64,
+    ) -> Result<Floppy, FileSystemError>;
+}
@@ -1,6 +1,6 @@
 use std::str::FromStr;
 
-use chrono::{Local, NaiveDateTime};
+use chrono::{NaiveDate, NaiveDateTime};
 use uuid::Uuid;
 
 use crate::{
@@ -10,6 +10,19 @@ use crate::{
     ClusterIndex,
 };
 
+/// The earliest date the DOS packed date/time format can represent
+/// (`IbmDos100::encode_date`/`encode_time` clamp to this). Entries default to
+/// it rather than the wall clock so images come out byte-identical across
+/// runs unless a caller sets an explicit timestamp via
+/// [`DirEntry::set_creation_time`] (or `Fat12::mkfile`/`mkdir`'s
+/// `creation_time` argument).
+fn dos_epoch() -> NaiveDateTime {
+    NaiveDate::from_ymd_opt(1980, 1, 1)
+        .unwrap()
+        .and_hms_opt(0, 0, 0)
+        .unwrap()
+}
+
 #[derive(Debug, PartialEq)]
 pub struct DirEntry {
     uid: Uuid,
@@ -17,6 +30,7 @@ pub struct DirEntry {
     parent: Option<Uuid>,
     attributes: Attributes,
     name: Option<EntryName>,
+    long_name: Option<String>,
     creation_time: NaiveDateTime,
     start_cluster: Option<ClusterIndex>,
     cluster_map: Vec<ClusterIndex>,
@@ -45,7 +59,8 @@ impl DirEntry {
             entry_type: DirEntryType::Directory,
             attributes: Attributes::from_preset(AttributesPreset::Directory),
             name: None,
-            creation_time: Local::now().naive_local(),
+            long_name: None,
+            creation_time: dos_epoch(),
             start_cluster: None,
             cluster_map: Vec::new(),
             file_size: 0,
@@ -67,6 +82,45 @@ impl DirEntry {
         Self::new_from_preset(name, AttributesPreset::VolumeLabel)
     }
 
+    /// Creates the `.` self-reference entry a real FAT subdirectory stores as
+    /// its first entry, a child of `directory` pointing back at `directory`
+    /// itself.
+    ///
+    /// Bypasses [`EntryName::from_str`], which rejects `.`/`..` outright
+    /// (see [`FileSystemError::CannotCreateDotfiles`]): those names are only
+    /// disallowed for entries callers name themselves, not for this special
+    /// pair that [`crate::pool::Pool::create_dir`] inserts automatically.
+    pub(crate) fn new_dot(directory: Uuid) -> Self {
+        Self::new_dot_entry(".", directory)
+    }
+
+    /// Creates the `..` parent-reference entry a real FAT subdirectory stores
+    /// as its second entry, also a child of `directory`. Real FAT layouts
+    /// additionally have `..` carry the parent directory's cluster number,
+    /// which here (as with any other entry) is assigned later via
+    /// [`DirEntry::set_start_cluster`].
+    pub(crate) fn new_dotdot(directory: Uuid) -> Self {
+        Self::new_dot_entry("..", directory)
+    }
+
+    fn new_dot_entry(name: &str, directory: Uuid) -> Self {
+        Self {
+            uid: Uuid::new_v4(),
+            entry_type: DirEntryType::Directory,
+            parent: Some(directory),
+            attributes: Attributes::from_preset(AttributesPreset::Directory),
+            name: Some(EntryName {
+                filename: name.to_string(),
+                extension: String::new(),
+            }),
+            long_name: None,
+            creation_time: dos_epoch(),
+            start_cluster: None,
+            cluster_map: Vec::new(),
+            file_size: 0,
+        }
+    }
+
     pub fn uuid(&self) -> &Uuid {
         &self.uid
     }
@@ -79,6 +133,14 @@ impl DirEntry {
         self.parent = Some(*parent.uuid());
     }
 
+    /// Reassigns this entry's parent by UUID, for callers like
+    /// [`crate::pool::Pool::rename`] that already know the new parent's UUID
+    /// without holding a `&DirEntry` borrowed from the same pool they're
+    /// mutating.
+    pub fn set_parent_uuid(&mut self, parent: Uuid) {
+        self.parent = Some(parent);
+    }
+
     /// Check whether the current entry is the root node
     pub fn is_root(&self) -> bool {
         self.parent.is_none()
@@ -98,6 +160,25 @@ impl DirEntry {
         self.name.as_ref()
     }
 
+    /// Renames this entry to `name`, clearing any previously recorded long
+    /// name since it no longer describes the entry's new short name.
+    pub fn set_name(&mut self, name: EntryName) {
+        self.name = Some(name);
+        self.long_name = None;
+    }
+
+    /// The full requested name, if it didn't losslessly fit into the short
+    /// name this entry was given. Set via `set_long_name` when a short name
+    /// had to be generated lossily; `None` means the short name already is
+    /// the whole story, and no VFAT LFN entries are needed for this entry.
+    pub fn long_name(&self) -> Option<&str> {
+        self.long_name.as_deref()
+    }
+
+    pub fn set_long_name(&mut self, long_name: String) {
+        self.long_name = Some(long_name);
+    }
+
     pub fn creation_time(&self) -> NaiveDateTime {
         self.creation_time
     }
@@ -130,7 +211,6 @@ impl DirEntry {
     /// Is the entry a directory?
     pub fn is_directory(&self) -> bool {
         matches!(self.entry_type, DirEntryType::Directory)
-
     }
 
     fn new_from_preset(name: &str, preset: AttributesPreset) -> Result<Self, FileSystemError> {
@@ -144,8 +224,9 @@ impl DirEntry {
             entry_type,
             parent: None,
             name: Some(EntryName::from_str(name)?),
+            long_name: None,
             attributes: Attributes::from_preset(preset),
-            creation_time: Local::now().naive_local(),
+            creation_time: dos_epoch(),
             start_cluster: None,
             cluster_map: Vec::new(),
             file_size: 0,
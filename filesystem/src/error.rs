@@ -1,19 +1,36 @@
 #[derive(Debug, PartialEq)]
 pub enum FileSystemError {
+    AllocationFailed,
     CannotAddParentlessEntry,
     CannotCreateDotfiles,
     ClusterAlreadyAllocated,
     ClusterNotUsable,
+    DirectoryNotEmpty,
+    DiskError,
     DuplicateEntry,
     EntryCannotHaveChildren,
+    EntryNotFound,
     EmptyFileName,
     ExtensionTooLong,
+    FatSizeTooLarge,
     FileNameTooLong,
+    GeometryNotInferable,
+    InvalidBootSignature,
+    InvalidBytesPerSector,
     InvalidCharInExt,
     InvalidCharInName,
     InvalidClusterIndex,
+    InvalidDirectoryTimestamp,
     InvalidPath,
+    InvalidSectorsPerCluster,
+    NotADirectory,
+    NotAbsolute,
     ParentNotFound,
+    PartitionTooLarge,
+    Recursion,
     TooManyFileNameParts,
+    TooManyRootEntries,
+    UnrecognizedMediaSize,
+    UnsupportedFatWidth,
     WontShrinkAllocationTable,
 }
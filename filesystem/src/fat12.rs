@@ -3,11 +3,44 @@ use std::path::Path;
 use chrono::NaiveDateTime;
 use disk::{sectorsize::SectorSize, volume::Volume, Disk};
 use operatingsystem::OperatingSystem;
+use uuid::Uuid;
 
 use crate::{
-    allocationtable::AllocationTable, direntry::DirEntry, error::FileSystemError, pool::Pool, serializer::{ibmdos100::IbmDos100, DirectorySerializer, Fat12Serializer}, ClusterIO, ClusterIndex, FileSystem
+    allocationtable::{AllocationTable, ClusterValue, FatType},
+    bpb::BiosParameterBlock,
+    deserializer::{DirectoryDeserializer, Fat12Deserializer},
+    direntry::DirEntry,
+    error::FileSystemError,
+    fsck::{self, FsckFinding},
+    name::DirEntryName,
+    pool::Pool,
+    serializer::{
+        ibmdos100::IbmDos100, DirectorySerializer, FatTableSerializer, VfatDirectorySerializer,
+    },
+    ClusterIO, ClusterIndex, FileSystem,
 };
 
+/// Default media descriptor and root directory size used when a `Fat12`
+/// isn't built from a known `Floppy` type. The 112-entry root directory is
+/// the size the 360K and 720K formats use, a reasonable middle ground for an
+/// otherwise-unspecified volume.
+const DEFAULT_MEDIA_DESCRIPTOR: u8 = 0xF0;
+const DEFAULT_ROOT_DIR_ENTRIES: usize = 112;
+const DEFAULT_FAT_COUNT: usize = 2;
+
+/// Sizes a FAT table of the given width for `cluster_count` clusters,
+/// following the same bits-per-entry math `FatLayout` uses for FAT12/16
+/// formatting: 12 bits per entry for FAT12, 16 for FAT16.
+fn sectors_per_fat_for(fat_type: &FatType, sector_size: SectorSize, cluster_count: usize) -> usize {
+    let bits_per_entry = match fat_type {
+        FatType::Fat12 => 12,
+        FatType::Fat16 | FatType::Fat32 => 16,
+    };
+    let fat_bits = (cluster_count + 2) * bits_per_entry;
+    let fat_bytes = fat_bits.div_ceil(8);
+    fat_bytes.div_ceil(sector_size.as_usize()).max(1)
+}
+
 #[derive(Debug)]
 pub struct Fat12<'a, D: Disk> {
     allocation_table: AllocationTable,
@@ -15,6 +48,11 @@ pub struct Fat12<'a, D: Disk> {
     cluster_size: usize, // Cluster size in sectors
     cluster_count: usize,
     sector_size: SectorSize,
+    bpb: BiosParameterBlock,
+    /// Whether to emit VFAT LFN entries for names that don't losslessly fit
+    /// into 8.3. Off by default: targets like PC-DOS 1.00 predate VFAT and
+    /// would choke on directory entries they can't recognize.
+    lfn_enabled: bool,
     volume: &'a mut Volume<'a, D>,
 }
 
@@ -56,6 +94,31 @@ impl<'a, D: Disk> ClusterIO for Fat12<'a, D> {
         Ok(())
     }
 
+    /// Reads the contents of a cluster at the given cluster index.
+    ///
+    /// # Parameters
+    /// - `index`: The cluster index to read from. Must be ≥ 2.
+    ///
+    /// # Returns
+    /// The cluster's raw bytes (`sector_size * cluster_size` long), or an appropriate `FileSystemError`.
+    fn read_cluster(&mut self, index: ClusterIndex) -> Result<Vec<u8>, FileSystemError> {
+        let first_sector = self.cluster_to_sector(index);
+        let sector_size_bytes = self.sector_size.as_usize();
+        let sectors_per_cluster = self.cluster_size;
+
+        let mut buffer = vec![0u8; sector_size_bytes * sectors_per_cluster];
+
+        for i in 0..sectors_per_cluster {
+            let offset = i * sector_size_bytes;
+            let sector_data = &mut buffer[offset..offset + sector_size_bytes];
+            self.volume
+                .read_sector(first_sector as u64 + i as u64, sector_data)
+                .map_err(|_| FileSystemError::DiskError)?;
+        }
+
+        Ok(buffer)
+    }
+
     /// Converts a cluster index to the corresponding starting sector number.
     ///
     /// Cluster indices must start from 2, as per FAT12 conventions. This calculation
@@ -72,13 +135,11 @@ impl<'a, D: Disk> ClusterIO for Fat12<'a, D> {
 
     /// Returns the starting sector number of the data region.
     ///
-    /// This implementation assumes a PC-DOS 1.00 layout and is hardcoded accordingly.
-    /// Future versions should derive this from the actual BPB or filesystem metadata.
-    ///
     /// # Returns
-    /// The sector number where the first data cluster begins.
+    /// The sector number where the first data cluster begins, derived from
+    /// this volume's `BiosParameterBlock`.
     fn data_region_start(&self) -> usize {
-        7
+        self.bpb.data_region_start()
     }
 }
 
@@ -89,31 +150,201 @@ impl<'a, D: Disk> Fat12<'a, D> {
         cluster_count: usize,
         volume: &'a mut Volume<'a, D>,
     ) -> Result<Self, FileSystemError> {
+        let fat_type = FatType::for_cluster_count(cluster_count);
+        let sectors_per_fat = sectors_per_fat_for(&fat_type, sector_size, cluster_count);
+        let bpb = BiosParameterBlock::new(
+            sector_size,
+            cluster_size,
+            1,
+            DEFAULT_FAT_COUNT,
+            DEFAULT_ROOT_DIR_ENTRIES,
+            volume.sector_count() as usize,
+            DEFAULT_MEDIA_DESCRIPTOR,
+            sectors_per_fat,
+        );
         let filesystem = Fat12 {
-            allocation_table: AllocationTable::default(),
+            allocation_table: AllocationTable::new(
+                fat_type,
+                sector_size.as_usize(),
+                cluster_count,
+            )?,
             pool: Pool::default(),
             cluster_size,
             cluster_count,
             sector_size,
+            bpb,
+            lfn_enabled: false,
+            volume,
+        };
+        Ok(filesystem)
+    }
+
+    /// Reconstructs a `Fat12` from an existing on-disk image: reads the boot
+    /// sector's BPB for the volume's geometry, parses the first FAT copy into
+    /// an `AllocationTable`, then walks the root directory region and every
+    /// subdirectory's cluster chain to rebuild the `Pool`, the exact inverse
+    /// of what [`Fat12::write_crud`] writes out (minus the LFN entries that
+    /// writer doesn't emit by default either).
+    ///
+    /// Only the plain `IbmDos100` on-disk format is understood so far: a
+    /// single FAT12 table and a fixed-size root directory region.
+    pub fn from_volume(volume: &'a mut Volume<'a, D>) -> Result<Self, FileSystemError> {
+        let mut boot_sector = [0u8; 512];
+        volume
+            .read_sector(0, &mut boot_sector)
+            .map_err(|_| FileSystemError::DiskError)?;
+
+        let bpb = BiosParameterBlock::from_bytes(&boot_sector)?;
+        let sector_size = SectorSize::try_from(bpb.bytes_per_sector())
+            .map_err(|_| FileSystemError::UnrecognizedMediaSize)?;
+
+        let data_sectors = bpb
+            .logical_sector_count()
+            .saturating_sub(bpb.data_region_start());
+        let cluster_count = data_sectors / bpb.sectors_per_cluster().max(1);
+
+        let fat_bytes = Self::read_sectors(
+            volume,
+            bpb.reserved_sectors(),
+            bpb.sectors_per_fat(),
+            sector_size,
+        )?;
+        let allocation_table =
+            <IbmDos100 as Fat12Deserializer>::deserialize_fat12(&fat_bytes, cluster_count)?;
+
+        let root_dir_bytes = Self::read_sectors(
+            volume,
+            bpb.root_dir_start_sector(),
+            bpb.root_dir_sectors(),
+            sector_size,
+        )?;
+
+        let mut filesystem = Fat12 {
+            allocation_table,
+            pool: Pool::default(),
+            cluster_size: bpb.sectors_per_cluster(),
+            cluster_count,
+            sector_size,
+            bpb,
+            lfn_enabled: false,
             volume,
         };
+
+        let root_uuid = *filesystem
+            .pool
+            .root_entry()
+            .ok_or(FileSystemError::ParentNotFound)?
+            .uuid();
+        filesystem.load_directory(root_uuid, &root_dir_bytes)?;
+
         Ok(filesystem)
     }
 
+    /// Reads `count` consecutive sectors starting at `start` into one buffer.
+    fn read_sectors(
+        volume: &mut Volume<'a, D>,
+        start: usize,
+        count: usize,
+        sector_size: SectorSize,
+    ) -> Result<Vec<u8>, FileSystemError> {
+        let mut buffer = vec![0u8; count * sector_size.as_usize()];
+        for (i, chunk) in buffer.chunks_mut(sector_size.as_usize()).enumerate() {
+            volume
+                .read_sector(start as u64 + i as u64, chunk)
+                .map_err(|_| FileSystemError::DiskError)?;
+        }
+        Ok(buffer)
+    }
+
+    /// Parses `bytes` as the raw contents of the directory belonging to
+    /// `parent_uuid`, adds each entry it finds to the pool, and recurses
+    /// into any subdirectory among them (skipping the `.`/`..` self- and
+    /// parent-references every subdirectory carries, which don't need
+    /// their own contents loaded).
+    fn load_directory(&mut self, parent_uuid: Uuid, bytes: &[u8]) -> Result<(), FileSystemError> {
+        let parent = self
+            .pool
+            .entry(&parent_uuid)
+            .ok_or(FileSystemError::ParentNotFound)?;
+        let entries = <IbmDos100 as DirectoryDeserializer>::deserialize_directory(
+            bytes,
+            parent,
+            &self.allocation_table,
+        )?;
+
+        let mut subdirs = Vec::new();
+        for entry in entries {
+            let is_dot_entry = matches!(
+                entry.name().map(|name| name.filename.as_str()),
+                Some(".") | Some("..")
+            );
+            if entry.is_directory() && !is_dot_entry {
+                if let Some(start) = entry.start_cluster() {
+                    subdirs.push((*entry.uuid(), start));
+                }
+            }
+            self.pool.add_entry(entry)?;
+        }
+
+        for (uuid, start) in subdirs {
+            let clusters: Vec<ClusterIndex> = self
+                .allocation_table
+                .chain(start)
+                .collect::<Result<Vec<_>, _>>()?;
+            let mut dir_bytes = Vec::new();
+            for cluster in clusters {
+                dir_bytes.extend(self.read_cluster(cluster)?);
+            }
+            self.load_directory(uuid, &dir_bytes)?;
+        }
+
+        Ok(())
+    }
+
+    /// Enables or disables VFAT LFN entries for names that don't losslessly
+    /// fit into 8.3. Leave disabled for targets that predate VFAT.
+    pub fn set_lfn_enabled(&mut self, enabled: bool) {
+        self.lfn_enabled = enabled;
+    }
+
+    /// Serializes the allocation table and writes it into every FAT copy
+    /// this volume's BPB describes, starting at the reserved-sector offset
+    /// (`reserved_sectors`, then one `sectors_per_fat`-sized copy per
+    /// `fat_count`).
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::DiskError` if a sector write fails, or
+    /// whatever `IbmDos100::serialize_fat_table` returns for a table that
+    /// can't be packed into its FAT width.
+    pub fn write_fat(&mut self) -> Result<(), FileSystemError> {
+        let fatbytes = IbmDos100::serialize_fat_table(self.allocation_table())?;
+        for i in 0..self.bpb.fat_count() {
+            let fat_start =
+                self.bpb.reserved_sectors() as u64 + (i * self.bpb.sectors_per_fat()) as u64;
+            self.volume
+                .write_sector(fat_start, &fatbytes)
+                .map_err(|_| FileSystemError::DiskError)?;
+        }
+        Ok(())
+    }
+
     /// THIS HAS TO GO!!
     pub fn write_crud(&mut self) {
         let os = operatingsystem::OperatingSystem::from_vendor_version("ibm", "1.00").unwrap();
-        let fatbytes = IbmDos100::serialize_fat12(&self.allocation_table()).unwrap();
-        let databytes = IbmDos100::serialize_directory(
-            self.pool(),
-            self.pool().root_entry().unwrap(),
-        )
-        .unwrap();
+        let root = self.pool().root_entry().unwrap();
+        let databytes = if self.lfn_enabled {
+            IbmDos100::serialize_directory_vfat(self.pool(), root).unwrap()
+        } else {
+            IbmDos100::serialize_directory(self.pool(), root).unwrap()
+        };
         self.volume.write_sector(0, os.bootsector()).unwrap();
-        self.volume.write_sector(1, &fatbytes).unwrap();
-        self.volume.write_sector(2, &fatbytes).unwrap();
+        self.write_fat().unwrap();
+        let dir_start = self.bpb.root_dir_start_sector() as u64;
         for (i, chunk) in databytes.chunks(512).enumerate() {
-            self.volume.write_sector(3 + i as u64, chunk).unwrap();
+            self.volume
+                .write_sector(dir_start + i as u64, chunk)
+                .unwrap();
         }
     }
 
@@ -125,6 +356,115 @@ impl<'a, D: Disk> Fat12<'a, D> {
         &self.pool
     }
 
+    pub fn bpb(&self) -> &BiosParameterBlock {
+        &self.bpb
+    }
+
+    /// Cross-references every entry in the pool against the allocation
+    /// table, reporting broken, cross-linked, and lost cluster chains and
+    /// file sizes that don't match the chain they describe. See
+    /// [`FsckFinding`] for what's checked; an empty result means the two
+    /// structures agree with each other.
+    pub fn check(&self) -> Vec<FsckFinding> {
+        fsck::check(&self.pool, &self.allocation_table, self.allocation_table.cluster_size())
+    }
+
+    /// Runs [`Fat12::check`] and fixes what it finds: broken and
+    /// cross-linked chains are truncated at the last cluster they can
+    /// safely reach, clusters that truncation (or a prior corruption)
+    /// leaves unreferenced are freed, and directory entries whose file size
+    /// no longer matches their chain are rewritten to match it.
+    ///
+    /// Returns the findings from the fix-up pass: an empty result means the
+    /// pool and table were left fully consistent.
+    pub fn repair(&mut self) -> Vec<FsckFinding> {
+        for finding in self.check() {
+            match finding {
+                FsckFinding::BrokenChain { entry, .. } => {
+                    if let Some(start) = self.pool.entry(&entry).and_then(DirEntry::start_cluster) {
+                        self.truncate_chain_at(start, None);
+                    }
+                }
+                FsckFinding::CrossLinkedCluster {
+                    cluster, second, ..
+                } => {
+                    if let Some(start) = self.pool.entry(&second).and_then(DirEntry::start_cluster) {
+                        self.truncate_chain_at(start, Some(cluster));
+                    }
+                }
+                FsckFinding::LostCluster { .. } | FsckFinding::SizeMismatch { .. } => {
+                    // Handled below, once truncation above has settled the
+                    // chains these findings' sizes and cluster ownership
+                    // depend on.
+                }
+            }
+        }
+
+        let remaining = self.check();
+        for finding in &remaining {
+            match finding {
+                FsckFinding::LostCluster { cluster } => {
+                    let _ = self.allocation_table.set_cluster(*cluster, ClusterValue::Free);
+                }
+                FsckFinding::SizeMismatch {
+                    entry, chain_size, ..
+                } => {
+                    if let Some(e) = self.pool.entry_mut(entry) {
+                        e.set_filesize(*chain_size);
+                    }
+                }
+                FsckFinding::BrokenChain { .. } | FsckFinding::CrossLinkedCluster { .. } => {}
+            }
+        }
+
+        remaining
+    }
+
+    /// Walks the raw chain from `start`, stopping at `stop_before` (if
+    /// given) or at the first link this table can't safely continue
+    /// through (out of range, or already visited this walk), and marks the
+    /// last cluster it reached before that point as the chain's new end.
+    fn truncate_chain_at(&mut self, start: ClusterIndex, stop_before: Option<ClusterIndex>) {
+        let mut visited = std::collections::HashSet::new();
+        let mut last_good = None;
+        let mut current = Some(start);
+
+        while let Some(cluster) = current {
+            if Some(cluster) == stop_before || !visited.insert(cluster) {
+                break;
+            }
+            last_good = Some(cluster);
+            current = match self.allocation_table.clusters().get(&cluster) {
+                Some(ClusterValue::Next(next)) if *next < self.cluster_count => Some(*next),
+                _ => None,
+            };
+        }
+
+        if let Some(last_good) = last_good {
+            let _ = self
+                .allocation_table
+                .set_cluster(last_good, ClusterValue::EndOfChain);
+        }
+    }
+
+    /// Generates an 8.3 short name for `desired`, deduplicated against the
+    /// names already present under `parent`.
+    fn short_name(
+        &self,
+        desired: &str,
+        parent: &DirEntry,
+    ) -> Result<DirEntryName, FileSystemError> {
+        self.pool.generate_short_name(desired, parent)
+    }
+
+    /// Sets `entry`'s long name to `desired` if `short_name` had to
+    /// normalize it lossily, so a VFAT-aware serializer can recover it later.
+    fn apply_long_name(entry: &mut DirEntry, desired: &str, short_name: &DirEntryName) {
+        if short_name.lossy() {
+            entry.set_long_name(desired.to_string());
+        }
+    }
+
     /// Helper method: takes a path, returns the filename from it if it exists.
     fn get_filename(path: &Path) -> Option<String> {
         let filename = path
@@ -165,8 +505,10 @@ impl<'a, D: disk::Disk> FileSystem for Fat12<'a, D> {
             .entry_by_path(parent_path)
             .ok_or(FileSystemError::ParentNotFound)?;
 
-        let mut entry = DirEntry::new_file(filename.as_str())?;
-        // If we're given a real creation time, use it. Otherwise it'll be the current host system clock.
+        let short_name = self.short_name(filename.as_str(), parent)?;
+        let mut entry = DirEntry::new_file(&short_name.to_string())?;
+        Self::apply_long_name(&mut entry, filename.as_str(), &short_name);
+        // If we're given a real creation time, use it. Otherwise it keeps the DOS epoch default set by DirEntry's constructor.
         if let Some(time) = creation_time {
             entry.set_creation_time(time);
         }
@@ -207,8 +549,10 @@ impl<'a, D: disk::Disk> FileSystem for Fat12<'a, D> {
             .entry_by_path(parent_path)
             .ok_or(FileSystemError::ParentNotFound)?;
 
-        let mut entry = DirEntry::new_sysfile(filename.as_str())?;
-        // If we're given a real creation time, use it. Otherwise it'll be the current host system clock.
+        let short_name = self.short_name(filename.as_str(), parent)?;
+        let mut entry = DirEntry::new_sysfile(&short_name.to_string())?;
+        Self::apply_long_name(&mut entry, filename.as_str(), &short_name);
+        // If we're given a real creation time, use it. Otherwise it keeps the DOS epoch default set by DirEntry's constructor.
         if let Some(time) = creation_time {
             entry.set_creation_time(time);
         }
@@ -251,17 +595,19 @@ impl<'a, D: disk::Disk> FileSystem for Fat12<'a, D> {
 
         let dirname = Self::get_filename(path).ok_or(FileSystemError::EmptyFileName)?;
 
-        let mut entry = DirEntry::new_directory(dirname.as_str())?;
-        // If we're given a real creation time, use it. Otherwise it'll be the current host system clock.
-        if let Some(time) = creation_time {
-            entry.set_creation_time(time);
-        }
-
         // Get the parent directory path (if any)
         let parent_path = path.parent().ok_or(FileSystemError::ParentNotFound)?;
 
         // Find the parent entry in the pool
         if let Some(parent) = self.pool.entry_by_path(parent_path) {
+            let short_name = self.short_name(dirname.as_str(), parent)?;
+            let mut entry = DirEntry::new_directory(&short_name.to_string())?;
+            Self::apply_long_name(&mut entry, dirname.as_str(), &short_name);
+            // If we're given a real creation time, use it. Otherwise it keeps the DOS epoch default set by DirEntry's constructor.
+            if let Some(time) = creation_time {
+                entry.set_creation_time(time);
+            }
+
             entry.set_parent(parent);
 
             // Allocate one cluster for the directory
@@ -277,4 +623,42 @@ impl<'a, D: disk::Disk> FileSystem for Fat12<'a, D> {
             Err(FileSystemError::ParentNotFound)
         }
     }
+
+    /// Reads a file's full contents back from disk.
+    ///
+    /// Resolves `path` in the pool, walks its cluster chain, and truncates
+    /// the assembled bytes to the entry's recorded file size (clusters are
+    /// always whole, so the last one is usually padded).
+    fn read_file(&mut self, path_str: &str) -> Result<Vec<u8>, FileSystemError> {
+        let path = Path::new(path_str);
+        let entry = self
+            .pool
+            .entry_by_path(path)
+            .ok_or(FileSystemError::ParentNotFound)?;
+        let start_cluster = entry
+            .start_cluster()
+            .ok_or(FileSystemError::InvalidClusterIndex)?;
+        let file_size = entry.file_size();
+
+        let clusters: Vec<ClusterIndex> = self
+            .allocation_table
+            .chain(start_cluster)
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut data = Vec::new();
+        for cluster in clusters {
+            data.extend(self.read_cluster(cluster)?);
+        }
+
+        data.truncate(file_size);
+        Ok(data)
+    }
+
+    fn free_clusters(&self) -> usize {
+        self.allocation_table.free_clusters()
+    }
+
+    fn used_clusters(&self) -> usize {
+        self.allocation_table.used_clusters()
+    }
 }
@@ -0,0 +1,440 @@
+use chrono::NaiveDateTime;
+use disk::{geometry::Geometry, sectorsize::SectorSize, Disk};
+
+use crate::{
+    bpb::BiosParameterBlock,
+    direntry::DirEntry,
+    error::FileSystemError,
+    formatoptions::FormatOptions,
+    serializer::{ibmdos100::IbmDos100, DirEntrySerializer},
+    ClusterIndex,
+};
+
+/// Size in bytes of a single FAT directory entry.
+const DIRENTRY_SIZE: usize = 32;
+
+/// The FAT table width a volume is formatted with.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FatWidth {
+    Fat12,
+    Fat16,
+}
+
+impl FatWidth {
+    /// Number of bits a single FAT entry occupies under this width.
+    fn bits_per_entry(self) -> usize {
+        match self {
+            FatWidth::Fat12 => 12,
+            FatWidth::Fat16 => 16,
+        }
+    }
+
+    /// The reserved bytes that open a fresh FAT: cluster 0 carries the media
+    /// descriptor in its low byte with the rest of its bits set, and cluster 1 is
+    /// marked end-of-chain. Every other entry starts out free (zeroed).
+    fn reserved_prefix(self, media_descriptor: u8) -> Vec<u8> {
+        match self {
+            FatWidth::Fat12 => vec![media_descriptor, 0xFF, 0xFF],
+            FatWidth::Fat16 => vec![media_descriptor, 0xFF, 0xFF, 0xFF],
+        }
+    }
+
+    /// Picks a conventional sectors-per-cluster value for a volume of
+    /// `sector_count` sectors, following the classic DOS cluster-size tables.
+    fn default_sectors_per_cluster(self, sector_count: usize) -> usize {
+        match self {
+            FatWidth::Fat12 => {
+                if sector_count <= 2_880 {
+                    1
+                } else {
+                    2
+                }
+            }
+            FatWidth::Fat16 => match sector_count {
+                0..=32_680 => 2,
+                32_681..=262_144 => 4,
+                262_145..=524_288 => 8,
+                524_289..=1_048_576 => 16,
+                _ => 32,
+            },
+        }
+    }
+}
+
+/// The sector-level layout of a freshly formatted FAT12/FAT16 volume: where the
+/// reserved area, FAT copies, and root directory sit, and how big each is.
+///
+/// All sector numbers this type deals with are relative to the start of the
+/// partition being formatted, not the whole disk.
+#[derive(Debug, Clone, Copy)]
+pub struct FatLayout {
+    width: FatWidth,
+    sector_size: SectorSize,
+    sector_count: usize,
+    sectors_per_cluster: usize,
+    reserved_sectors: usize,
+    fat_count: usize,
+    sectors_per_fat: usize,
+    root_dir_entries: usize,
+}
+
+impl FatLayout {
+    /// Works out a `FatLayout` for a `sector_count`-sector volume, picking
+    /// sectors-per-cluster from the classic DOS tables and sizing the FAT off of
+    /// the resulting approximate cluster count.
+    ///
+    /// `root_dir_entries` is taken as given, the same way `BiosParameterBlock::new`
+    /// does, since its sizing is a DOS-version/media convention rather than
+    /// something derivable from volume size alone. `fat_count` likewise is taken
+    /// as given rather than assumed, since most DOS tools write two copies but
+    /// nothing about a volume's size dictates that number.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::PartitionTooLarge` if `sector_count` doesn't fit
+    /// in the 16-bit total-sectors field a FAT12/16 boot sector stores.
+    pub fn new(
+        width: FatWidth,
+        sector_size: SectorSize,
+        sector_count: usize,
+        root_dir_entries: usize,
+        fat_count: usize,
+    ) -> Result<Self, FileSystemError> {
+        let _: u16 = sector_count
+            .try_into()
+            .map_err(|_| FileSystemError::PartitionTooLarge)?;
+
+        let reserved_sectors = 1;
+        let sectors_per_cluster = width.default_sectors_per_cluster(sector_count);
+
+        let bytes_per_sector = sector_size.as_usize();
+        let root_dir_sectors = (root_dir_entries * DIRENTRY_SIZE).div_ceil(bytes_per_sector);
+
+        // The data region's size depends on the FAT's size, which depends on the
+        // cluster count, which depends on the data region's size. Approximate by
+        // sizing the FAT off of the volume as if it had no FAT overhead at all;
+        // since the FAT is tiny relative to the volume this slightly overestimates
+        // the cluster count, which only makes the FAT marginally larger than
+        // strictly necessary, never too small.
+        let approx_data_sectors = sector_count.saturating_sub(reserved_sectors + root_dir_sectors);
+        let approx_cluster_count = approx_data_sectors / sectors_per_cluster;
+
+        let fat_bits = (approx_cluster_count + 2) * width.bits_per_entry();
+        let fat_bytes = fat_bits.div_ceil(8);
+        let sectors_per_fat = fat_bytes.div_ceil(bytes_per_sector).max(1);
+
+        Ok(Self {
+            width,
+            sector_size,
+            sector_count,
+            sectors_per_cluster,
+            reserved_sectors,
+            fat_count,
+            sectors_per_fat,
+            root_dir_entries,
+        })
+    }
+
+    /// Same as `new`, but takes its layout knobs from a `FormatOptions`
+    /// instead of assuming the classic DOS defaults: `sectors_per_cluster`
+    /// falls back to the DOS cluster-size tables only if `options` doesn't
+    /// override it, and `reserved_sectors`/`root_dir_entries` are taken from
+    /// `options` outright.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::PartitionTooLarge` if `sector_count` doesn't fit
+    /// in the 16-bit total-sectors field a FAT12/16 boot sector stores.
+    pub fn from_options(
+        width: FatWidth,
+        sector_size: SectorSize,
+        sector_count: usize,
+        fat_count: usize,
+        options: &FormatOptions,
+    ) -> Result<Self, FileSystemError> {
+        let _: u16 = sector_count
+            .try_into()
+            .map_err(|_| FileSystemError::PartitionTooLarge)?;
+
+        let reserved_sectors = options.reserved_sectors();
+        let root_dir_entries = options.root_dir_entries();
+        let sectors_per_cluster = options
+            .sectors_per_cluster()
+            .unwrap_or_else(|| width.default_sectors_per_cluster(sector_count));
+
+        let bytes_per_sector = sector_size.as_usize();
+        let root_dir_sectors = (root_dir_entries * DIRENTRY_SIZE).div_ceil(bytes_per_sector);
+
+        let approx_data_sectors = sector_count.saturating_sub(reserved_sectors + root_dir_sectors);
+        let approx_cluster_count = approx_data_sectors / sectors_per_cluster;
+
+        let fat_bits = (approx_cluster_count + 2) * width.bits_per_entry();
+        let fat_bytes = fat_bits.div_ceil(8);
+        let sectors_per_fat = fat_bytes.div_ceil(bytes_per_sector).max(1);
+
+        Ok(Self {
+            width,
+            sector_size,
+            sector_count,
+            sectors_per_cluster,
+            reserved_sectors,
+            fat_count,
+            sectors_per_fat,
+            root_dir_entries,
+        })
+    }
+
+    /// Number of sectors the root directory region occupies.
+    pub fn root_dir_sectors(&self) -> usize {
+        (self.root_dir_entries * DIRENTRY_SIZE).div_ceil(self.sector_size.as_usize())
+    }
+
+    /// Sector, relative to the partition start, where the root directory region
+    /// begins: right after the reserved area and all `fat_count` FAT copies.
+    pub fn root_dir_start_sector(&self) -> usize {
+        self.reserved_sectors + self.fat_count * self.sectors_per_fat
+    }
+
+    /// Sector, relative to the partition start, where the data region (cluster 2
+    /// onward) begins: `reserved + num_fats * fat_secs + root_dir_secs`.
+    pub fn data_region_start(&self) -> usize {
+        self.root_dir_start_sector() + self.root_dir_sectors()
+    }
+
+    pub fn reserved_sectors(&self) -> usize {
+        self.reserved_sectors
+    }
+
+    pub fn fat_count(&self) -> usize {
+        self.fat_count
+    }
+
+    pub fn sectors_per_fat(&self) -> usize {
+        self.sectors_per_fat
+    }
+
+    pub fn root_dir_entries(&self) -> usize {
+        self.root_dir_entries
+    }
+
+    pub fn sector_size(&self) -> SectorSize {
+        self.sector_size
+    }
+
+    /// Builds the `BiosParameterBlock` describing this layout.
+    pub fn to_bpb(&self, media_descriptor: u8) -> BiosParameterBlock {
+        BiosParameterBlock::new(
+            self.sector_size,
+            self.sectors_per_cluster,
+            self.reserved_sectors,
+            self.fat_count,
+            self.root_dir_entries,
+            self.sector_count,
+            media_descriptor,
+            self.sectors_per_fat,
+        )
+    }
+
+    /// Same as `to_bpb`, but takes the media descriptor and OEM name from
+    /// `options` instead of a bare byte.
+    pub fn to_bpb_with_options(&self, options: &FormatOptions) -> BiosParameterBlock {
+        let mut bpb = self.to_bpb(options.media_descriptor());
+        bpb.set_oem_name(options.oem_name());
+        bpb
+    }
+
+    /// Builds one full FAT copy's worth of bytes: the reserved cluster-0/1 entries
+    /// followed by all-free (zeroed) entries for the rest of the table.
+    fn reserved_fat_bytes(&self, media_descriptor: u8) -> Vec<u8> {
+        let mut bytes = vec![0u8; self.sectors_per_fat * self.sector_size.as_usize()];
+        let prefix = self.width.reserved_prefix(media_descriptor);
+        bytes[..prefix.len()].copy_from_slice(&prefix);
+        bytes
+    }
+}
+
+/// Formats `sector_count` sectors of `disk`, starting at `partition_start_lba`, as
+/// a fresh FAT12 or FAT16 volume per `layout`: writes the boot sector, every FAT
+/// copy (with only the reserved cluster-0/1 entries set), and zeroes the root
+/// directory region.
+///
+/// `geometry` supplies the CHS fields the boot sector's BPB carries; it need not
+/// match the partition's own size, the same way an MBR partition entry's CHS
+/// fields are derived from the disk's geometry rather than the partition's.
+///
+/// # Errors
+///
+/// Returns `FileSystemError::PartitionTooLarge` if `partition_start_lba` doesn't
+/// fit in the boot sector's 32-bit hidden-sectors field, or `FileSystemError::DiskError`
+/// if any underlying sector read or write fails.
+pub fn format(
+    disk: &mut dyn Disk,
+    partition_start_lba: u64,
+    geometry: &Geometry,
+    layout: &FatLayout,
+    media_descriptor: u8,
+) -> Result<BiosParameterBlock, FileSystemError> {
+    let hidden_sectors: u32 = partition_start_lba
+        .try_into()
+        .map_err(|_| FileSystemError::PartitionTooLarge)?;
+
+    let mut bpb = layout.to_bpb(media_descriptor);
+    bpb.set_geometry(geometry);
+    bpb.set_hidden_sectors(hidden_sectors);
+    let boot_sector = bpb.to_bytes();
+    disk.write_sector(partition_start_lba, &boot_sector)
+        .map_err(|_| FileSystemError::DiskError)?;
+
+    let fat_bytes = layout.reserved_fat_bytes(media_descriptor);
+    let bytes_per_sector = layout.sector_size().as_usize();
+    for fat_index in 0..layout.fat_count() {
+        let fat_start_lba = partition_start_lba
+            + layout.reserved_sectors() as u64
+            + (fat_index * layout.sectors_per_fat()) as u64;
+
+        for (i, sector) in fat_bytes.chunks(bytes_per_sector).enumerate() {
+            disk.write_sector(fat_start_lba + i as u64, sector)
+                .map_err(|_| FileSystemError::DiskError)?;
+        }
+    }
+
+    let root_dir_start_lba = partition_start_lba + layout.root_dir_start_sector() as u64;
+    let zero_sector = vec![0u8; bytes_per_sector];
+    for i in 0..layout.root_dir_sectors() {
+        disk.write_sector(root_dir_start_lba + i as u64, &zero_sector)
+            .map_err(|_| FileSystemError::DiskError)?;
+    }
+
+    Ok(bpb)
+}
+
+/// Same as `format`, but takes its media descriptor and OEM name from
+/// `options` instead of a bare byte, and writes a volume-label entry into the
+/// root directory when `options` carries one.
+///
+/// # Errors
+///
+/// Same as `format`, plus whatever `DirEntry::new_volume_label` returns if
+/// `options.volume_label()` isn't a valid 8.3-style label.
+pub fn format_with_options(
+    disk: &mut dyn Disk,
+    partition_start_lba: u64,
+    geometry: &Geometry,
+    layout: &FatLayout,
+    options: &FormatOptions,
+    volume_label_creation_time: NaiveDateTime,
+) -> Result<BiosParameterBlock, FileSystemError> {
+    let hidden_sectors: u32 = partition_start_lba
+        .try_into()
+        .map_err(|_| FileSystemError::PartitionTooLarge)?;
+
+    let mut bpb = layout.to_bpb_with_options(options);
+    bpb.set_geometry(geometry);
+    bpb.set_hidden_sectors(hidden_sectors);
+    let boot_sector = bpb.to_bytes();
+    disk.write_sector(partition_start_lba, &boot_sector)
+        .map_err(|_| FileSystemError::DiskError)?;
+
+    let media_descriptor = options.media_descriptor();
+    let fat_bytes = layout.reserved_fat_bytes(media_descriptor);
+    let bytes_per_sector = layout.sector_size().as_usize();
+    for fat_index in 0..layout.fat_count() {
+        let fat_start_lba = partition_start_lba
+            + layout.reserved_sectors() as u64
+            + (fat_index * layout.sectors_per_fat()) as u64;
+
+        for (i, sector) in fat_bytes.chunks(bytes_per_sector).enumerate() {
+            disk.write_sector(fat_start_lba + i as u64, sector)
+                .map_err(|_| FileSystemError::DiskError)?;
+        }
+    }
+
+    let root_dir_start_lba = partition_start_lba + layout.root_dir_start_sector() as u64;
+    let zero_sector = vec![0u8; bytes_per_sector];
+    for i in 0..layout.root_dir_sectors() {
+        disk.write_sector(root_dir_start_lba + i as u64, &zero_sector)
+            .map_err(|_| FileSystemError::DiskError)?;
+    }
+
+    if let Some(label) = options.volume_label() {
+        let entry = volume_label_entry_bytes(label, volume_label_creation_time)?;
+        write_root_entries(disk, partition_start_lba, layout, &[entry])?;
+    }
+
+    Ok(bpb)
+}
+
+/// Builds the 32-byte on-disk form of a root-directory 8.3 entry for `name`.
+///
+/// This is a thin convenience over `DirEntry::new_file` and the `IbmDos100`
+/// serializer, for callers that want to seed a freshly formatted volume with a
+/// handful of entries without pulling in the full `Pool`-based filesystem.
+pub fn root_directory_entry_bytes(
+    name: &str,
+    creation_time: NaiveDateTime,
+    start_cluster: Option<ClusterIndex>,
+    file_size: usize,
+) -> Result<[u8; DIRENTRY_SIZE], FileSystemError> {
+    let mut entry = DirEntry::new_file(name)?;
+    entry.set_creation_time(creation_time);
+    if let Some(cluster) = start_cluster {
+        entry.set_start_cluster(cluster);
+    }
+    entry.set_filesize(file_size);
+
+    let serialized = IbmDos100::serialize_direntry(&entry)?;
+    let mut bytes = [0u8; DIRENTRY_SIZE];
+    bytes.copy_from_slice(&serialized);
+    Ok(bytes)
+}
+
+/// Builds the 32-byte on-disk form of a volume-label entry for `label`.
+///
+/// Thin convenience over `DirEntry::new_volume_label` and the `IbmDos100`
+/// serializer, mirroring `root_directory_entry_bytes` for callers seeding a
+/// freshly formatted volume's root directory with a volume label.
+pub fn volume_label_entry_bytes(
+    label: &str,
+    creation_time: NaiveDateTime,
+) -> Result<[u8; DIRENTRY_SIZE], FileSystemError> {
+    let mut entry = DirEntry::new_volume_label(label)?;
+    entry.set_creation_time(creation_time);
+
+    let serialized = IbmDos100::serialize_direntry(&entry)?;
+    let mut bytes = [0u8; DIRENTRY_SIZE];
+    bytes.copy_from_slice(&serialized);
+    Ok(bytes)
+}
+
+/// Writes `entries` into the root directory region of a volume `format` already
+/// zeroed, leaving any remaining slots as free (all-zero) entries.
+///
+/// # Errors
+///
+/// Returns `FileSystemError::TooManyRootEntries` if `entries` doesn't fit within
+/// `layout.root_dir_entries()`.
+pub fn write_root_entries(
+    disk: &mut dyn Disk,
+    partition_start_lba: u64,
+    layout: &FatLayout,
+    entries: &[[u8; DIRENTRY_SIZE]],
+) -> Result<(), FileSystemError> {
+    if entries.len() > layout.root_dir_entries() {
+        return Err(FileSystemError::TooManyRootEntries);
+    }
+
+    let bytes_per_sector = layout.sector_size().as_usize();
+    let mut buffer = vec![0u8; layout.root_dir_sectors() * bytes_per_sector];
+    for (i, entry) in entries.iter().enumerate() {
+        let offset = i * DIRENTRY_SIZE;
+        buffer[offset..offset + DIRENTRY_SIZE].copy_from_slice(entry);
+    }
+
+    let root_dir_start_lba = partition_start_lba + layout.root_dir_start_sector() as u64;
+    for (i, sector) in buffer.chunks(bytes_per_sector).enumerate() {
+        disk.write_sector(root_dir_start_lba + i as u64, sector)
+            .map_err(|_| FileSystemError::DiskError)?;
+    }
+
+    Ok(())
+}
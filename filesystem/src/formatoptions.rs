@@ -0,0 +1,86 @@
+/// Layout knobs a caller can override when formatting a volume, mirroring the
+/// switches `newfs_msdos` exposes (`-c`, `-r`, `-e`, `-O`, `-L`) for
+/// reproducing nonstandard historical disks. Every field defaults to the
+/// value `format()` used before this type existed, so leaving it untouched
+/// reproduces the out-of-the-box layout.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormatOptions {
+    sectors_per_cluster: Option<usize>,
+    reserved_sectors: usize,
+    root_dir_entries: usize,
+    oem_name: [u8; 8],
+    media_descriptor: u8,
+    volume_label: Option<String>,
+}
+
+impl Default for FormatOptions {
+    fn default() -> Self {
+        Self {
+            sectors_per_cluster: None,
+            reserved_sectors: 1,
+            root_dir_entries: 64,
+            oem_name: *b"DOSCNTNR",
+            media_descriptor: 0xFE,
+            volume_label: None,
+        }
+    }
+}
+
+impl FormatOptions {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Overrides the sectors-per-cluster `FatLayout::from_options` would
+    /// otherwise pick from the classic DOS cluster-size tables.
+    pub fn set_sectors_per_cluster(&mut self, sectors_per_cluster: usize) {
+        self.sectors_per_cluster = Some(sectors_per_cluster);
+    }
+
+    pub fn set_reserved_sectors(&mut self, reserved_sectors: usize) {
+        self.reserved_sectors = reserved_sectors;
+    }
+
+    pub fn set_root_dir_entries(&mut self, root_dir_entries: usize) {
+        self.root_dir_entries = root_dir_entries;
+    }
+
+    pub fn set_oem_name(&mut self, oem_name: [u8; 8]) {
+        self.oem_name = oem_name;
+    }
+
+    pub fn set_media_descriptor(&mut self, media_descriptor: u8) {
+        self.media_descriptor = media_descriptor;
+    }
+
+    /// Sets the label written as a volume-label entry in the root directory.
+    /// Pass `None` (the default) to omit it, the same way `newfs_msdos`
+    /// leaves a volume unlabeled unless `-L` is given.
+    pub fn set_volume_label(&mut self, volume_label: Option<String>) {
+        self.volume_label = volume_label;
+    }
+
+    pub fn sectors_per_cluster(&self) -> Option<usize> {
+        self.sectors_per_cluster
+    }
+
+    pub fn reserved_sectors(&self) -> usize {
+        self.reserved_sectors
+    }
+
+    pub fn root_dir_entries(&self) -> usize {
+        self.root_dir_entries
+    }
+
+    pub fn oem_name(&self) -> [u8; 8] {
+        self.oem_name
+    }
+
+    pub fn media_descriptor(&self) -> u8 {
+        self.media_descriptor
+    }
+
+    pub fn volume_label(&self) -> Option<&str> {
+        self.volume_label.as_deref()
+    }
+}
@@ -0,0 +1,110 @@
+use std::collections::{HashMap, HashSet};
+
+use uuid::Uuid;
+
+use crate::{
+    allocationtable::{AllocationTable, ClusterValue},
+    error::FileSystemError,
+    pool::Pool,
+    ClusterIndex,
+};
+
+/// One consistency problem found by [`crate::fat12::Fat12::check`] while
+/// cross-referencing the [`Pool`]'s directory entries against the
+/// [`AllocationTable`]'s cluster chains.
+#[derive(Debug, PartialEq)]
+pub enum FsckFinding {
+    /// `entry`'s cluster chain couldn't be walked to completion: `cause` is
+    /// [`FileSystemError::Recursion`] for a chain that loops back on
+    /// itself, or [`FileSystemError::ClusterNotUsable`] for a link into a
+    /// free, reserved, bad, or out-of-range cluster.
+    BrokenChain { entry: Uuid, cause: FileSystemError },
+    /// `cluster` is claimed by the chains of both `first` and `second`.
+    CrossLinkedCluster {
+        cluster: ClusterIndex,
+        first: Uuid,
+        second: Uuid,
+    },
+    /// `entry`'s recorded file size doesn't match the bytes its cluster
+    /// chain actually spans.
+    SizeMismatch {
+        entry: Uuid,
+        recorded_size: usize,
+        chain_size: usize,
+    },
+    /// `cluster` is allocated in the table but referenced by no entry in
+    /// the pool.
+    LostCluster { cluster: ClusterIndex },
+}
+
+/// Walks every entry in `pool` that has a starting cluster, cross-checking
+/// its chain in `table` against every other entry's chain and against its
+/// own recorded file size, then scans `table` for allocated clusters no
+/// chain ever reached. `cluster_size` is the number of bytes a cluster
+/// holds, used to judge whether a chain's length matches its entry's file
+/// size.
+///
+/// Returns one [`FsckFinding`] per problem found; an empty result means
+/// the pool and the table agree with each other.
+pub(crate) fn check(pool: &Pool, table: &AllocationTable, cluster_size: usize) -> Vec<FsckFinding> {
+    let mut findings = Vec::new();
+    let mut owners: HashMap<ClusterIndex, Uuid> = HashMap::new();
+
+    for entry in pool.entries() {
+        let Some(start) = entry.start_cluster() else {
+            continue;
+        };
+
+        let mut chain = Vec::new();
+        let mut broken = false;
+        for step in table.chain(start) {
+            match step {
+                Ok(cluster) => chain.push(cluster),
+                Err(cause) => {
+                    findings.push(FsckFinding::BrokenChain {
+                        entry: *entry.uuid(),
+                        cause,
+                    });
+                    broken = true;
+                    break;
+                }
+            }
+        }
+
+        for &cluster in &chain {
+            match owners.get(&cluster) {
+                Some(&first) => findings.push(FsckFinding::CrossLinkedCluster {
+                    cluster,
+                    first,
+                    second: *entry.uuid(),
+                }),
+                None => {
+                    owners.insert(cluster, *entry.uuid());
+                }
+            }
+        }
+
+        if broken {
+            continue;
+        }
+
+        let expected_clusters = usize::max(1, entry.file_size().div_ceil(cluster_size));
+        if expected_clusters != chain.len() {
+            findings.push(FsckFinding::SizeMismatch {
+                entry: *entry.uuid(),
+                recorded_size: entry.file_size(),
+                chain_size: chain.len() * cluster_size,
+            });
+        }
+    }
+
+    let visited: HashSet<ClusterIndex> = owners.keys().copied().collect();
+    for (&cluster, value) in table.clusters() {
+        let is_chain_link = matches!(value, ClusterValue::Next(_) | ClusterValue::EndOfChain);
+        if is_chain_link && !visited.contains(&cluster) {
+            findings.push(FsckFinding::LostCluster { cluster });
+        }
+    }
+
+    findings
+}
@@ -0,0 +1,345 @@
+//! A read-only FUSE view onto a [`Pool`], letting a built-up directory tree be
+//! browsed and copied from with ordinary tools (`ls`, `cp`, ...) before it's
+//! ever serialized to a `.img`. This takes the same approach the fossil
+//! `mount` binary uses to serve an in-memory tree: FUSE requests are answered
+//! directly from the tree's own data structures, with no filesystem image in
+//! between.
+//!
+//! Gated behind the `fuse` feature, since it's the only part of this crate
+//! that needs `fuser`.
+
+use std::collections::HashMap;
+use std::ffi::OsStr;
+use std::time::Duration;
+
+use fuser::{
+    FileAttr, FileType, Filesystem, ReplyAttr, ReplyData, ReplyDirectory, ReplyEntry, Request,
+};
+use uuid::Uuid;
+
+use crate::{direntry::DirEntry, pool::Pool, ClusterIndex};
+
+/// How long the kernel may cache an inode's attributes or a directory's
+/// listing before asking again. A mounted [`PoolFs`] never changes for the
+/// life of the mount, so this is a generous, purely performance-motivated
+/// value rather than a correctness one.
+const ATTR_TTL: Duration = Duration::from_secs(3600);
+
+/// The inode FUSE reserves for the mount's root directory.
+const ROOT_INODE: u64 = 1;
+
+/// Reads the raw bytes of one cluster from whatever disk or volume backs the
+/// mounted [`Pool`].
+///
+/// This is supplied by the caller rather than `PoolFs` reaching into a
+/// concrete filesystem type itself: `Fat12<'a, D>` carries a borrowed
+/// `Volume` and a disk type parameter that don't fit FUSE's `'static`,
+/// `Send` session model, so the caller bridges the two with a closure
+/// instead.
+pub type ClusterReader = Box<dyn FnMut(ClusterIndex) -> std::io::Result<Vec<u8>> + Send>;
+
+/// Exposes a [`Pool`] as a read-only FUSE filesystem.
+///
+/// Each [`DirEntry`]'s UUID is assigned a stable inode number the first time
+/// it's looked up, with the pool's root directory fixed at FUSE's
+/// conventional root inode (1). Only `lookup`, `getattr`, `readdir`, and
+/// `read` are implemented; every operation that would mutate the tree
+/// (`write`, `setattr`, `mkdir`, `unlink`, `rmdir`, `rename`, `create`)
+/// replies `EROFS` instead of attempting anything.
+pub struct PoolFs {
+    pool: Pool,
+    read_cluster: ClusterReader,
+    inode_to_uuid: HashMap<u64, Uuid>,
+    uuid_to_inode: HashMap<Uuid, u64>,
+    next_inode: u64,
+}
+
+impl PoolFs {
+    /// Builds a `PoolFs` serving `pool`, reading file contents a cluster at a
+    /// time via `read_cluster`.
+    pub fn new(pool: Pool, read_cluster: ClusterReader) -> Self {
+        let mut fs = Self {
+            pool,
+            read_cluster,
+            inode_to_uuid: HashMap::new(),
+            uuid_to_inode: HashMap::new(),
+            next_inode: ROOT_INODE + 1,
+        };
+
+        if let Some(root) = fs.pool.root_entry() {
+            let root_uuid = *root.uuid();
+            fs.inode_to_uuid.insert(ROOT_INODE, root_uuid);
+            fs.uuid_to_inode.insert(root_uuid, ROOT_INODE);
+        }
+
+        fs
+    }
+
+    /// Returns the stable inode number for `uuid`, assigning it a fresh one
+    /// the first time it's seen.
+    fn inode_for(&mut self, uuid: Uuid) -> u64 {
+        if let Some(ino) = self.uuid_to_inode.get(&uuid) {
+            return *ino;
+        }
+
+        let ino = self.next_inode;
+        self.next_inode += 1;
+        self.inode_to_uuid.insert(ino, uuid);
+        self.uuid_to_inode.insert(uuid, ino);
+        ino
+    }
+
+    fn entry_for_inode(&self, ino: u64) -> Option<&DirEntry> {
+        let uuid = self.inode_to_uuid.get(&ino)?;
+        self.pool.entry(uuid)
+    }
+
+    /// Builds the FUSE attribute record for `entry`, already known to live at
+    /// inode `ino`.
+    fn file_attr(entry: &DirEntry, ino: u64) -> FileAttr {
+        let kind = if entry.is_directory() {
+            FileType::Directory
+        } else {
+            FileType::RegularFile
+        };
+        let size = entry.file_size() as u64;
+        let created = entry
+            .creation_time()
+            .and_utc()
+            .into();
+
+        FileAttr {
+            ino,
+            size,
+            blocks: size.div_ceil(512),
+            atime: created,
+            mtime: created,
+            ctime: created,
+            crtime: created,
+            kind,
+            perm: if entry.is_directory() { 0o555 } else { 0o444 },
+            nlink: 1,
+            uid: 0,
+            gid: 0,
+            rdev: 0,
+            blksize: 512,
+            flags: 0,
+        }
+    }
+
+    /// Reads a file's full contents, one cluster at a time, truncated to its
+    /// recorded file size.
+    fn read_file_data(
+        &mut self,
+        clusters: &[ClusterIndex],
+        file_size: usize,
+    ) -> std::io::Result<Vec<u8>> {
+        let mut data = Vec::new();
+        for cluster in clusters {
+            data.extend((self.read_cluster)(*cluster)?);
+        }
+        data.truncate(file_size);
+        Ok(data)
+    }
+}
+
+impl Filesystem for PoolFs {
+    fn lookup(&mut self, _req: &Request<'_>, parent: u64, name: &OsStr, reply: ReplyEntry) {
+        let Some(name) = name.to_str() else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        let Some(parent_entry) = self.entry_for_inode(parent) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        let Ok(Some(child)) = self.pool.entry_by_name(name, parent_entry) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+
+        let uuid = *child.uuid();
+        let attr = Self::file_attr(child, self.inode_for(uuid));
+        reply.entry(&ATTR_TTL, &attr, 0);
+    }
+
+    fn getattr(&mut self, _req: &Request<'_>, ino: u64, _fh: Option<u64>, reply: ReplyAttr) {
+        match self.entry_for_inode(ino) {
+            Some(entry) => reply.attr(&ATTR_TTL, &Self::file_attr(entry, ino)),
+            None => reply.error(libc::ENOENT),
+        }
+    }
+
+    fn readdir(
+        &mut self,
+        _req: &Request<'_>,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        mut reply: ReplyDirectory,
+    ) {
+        let Some(parent_entry) = self.entry_for_inode(ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+
+        let children: Vec<Uuid> = self
+            .pool
+            .children(parent_entry)
+            .into_iter()
+            .map(|child| *child.uuid())
+            .collect();
+
+        let mut rows: Vec<(u64, FileType, String)> = vec![
+            (ino, FileType::Directory, ".".to_string()),
+            (ino, FileType::Directory, "..".to_string()),
+        ];
+        for uuid in children {
+            let child_ino = self.inode_for(uuid);
+            let Some(child) = self.pool.entry(&uuid) else {
+                continue;
+            };
+            let kind = if child.is_directory() {
+                FileType::Directory
+            } else {
+                FileType::RegularFile
+            };
+            let name = child
+                .long_name()
+                .map(ToOwned::to_owned)
+                .or_else(|| child.name().map(ToString::to_string))
+                .unwrap_or_default();
+            rows.push((child_ino, kind, name));
+        }
+
+        for (i, (row_ino, kind, name)) in rows.into_iter().enumerate().skip(offset as usize) {
+            if reply.add(row_ino, (i + 1) as i64, kind, name) {
+                break;
+            }
+        }
+        reply.ok();
+    }
+
+    fn read(
+        &mut self,
+        _req: &Request<'_>,
+        ino: u64,
+        _fh: u64,
+        offset: i64,
+        size: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: ReplyData,
+    ) {
+        let Some(entry) = self.entry_for_inode(ino) else {
+            reply.error(libc::ENOENT);
+            return;
+        };
+        let clusters = entry.cluster_map().to_vec();
+        let file_size = entry.file_size();
+
+        match self.read_file_data(&clusters, file_size) {
+            Ok(data) => {
+                let start = (offset as usize).min(data.len());
+                let end = start.saturating_add(size as usize).min(data.len());
+                reply.data(&data[start..end]);
+            }
+            Err(_) => reply.error(libc::EIO),
+        }
+    }
+
+    fn write(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        _fh: u64,
+        _offset: i64,
+        _data: &[u8],
+        _write_flags: u32,
+        _flags: i32,
+        _lock_owner: Option<u64>,
+        reply: fuser::ReplyWrite,
+    ) {
+        reply.error(libc::EROFS);
+    }
+
+    fn setattr(
+        &mut self,
+        _req: &Request<'_>,
+        _ino: u64,
+        _mode: Option<u32>,
+        _uid: Option<u32>,
+        _gid: Option<u32>,
+        _size: Option<u64>,
+        _atime: Option<fuser::TimeOrNow>,
+        _mtime: Option<fuser::TimeOrNow>,
+        _ctime: Option<std::time::SystemTime>,
+        _fh: Option<u64>,
+        _crtime: Option<std::time::SystemTime>,
+        _chgtime: Option<std::time::SystemTime>,
+        _bkuptime: Option<std::time::SystemTime>,
+        _flags: Option<u32>,
+        reply: ReplyAttr,
+    ) {
+        reply.error(libc::EROFS);
+    }
+
+    fn mkdir(
+        &mut self,
+        _req: &Request<'_>,
+        _parent: u64,
+        _name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+        reply: ReplyEntry,
+    ) {
+        reply.error(libc::EROFS);
+    }
+
+    fn unlink(&mut self, _req: &Request<'_>, _parent: u64, _name: &OsStr, reply: fuser::ReplyEmpty) {
+        reply.error(libc::EROFS);
+    }
+
+    fn rmdir(&mut self, _req: &Request<'_>, _parent: u64, _name: &OsStr, reply: fuser::ReplyEmpty) {
+        reply.error(libc::EROFS);
+    }
+
+    fn rename(
+        &mut self,
+        _req: &Request<'_>,
+        _parent: u64,
+        _name: &OsStr,
+        _newparent: u64,
+        _newname: &OsStr,
+        _flags: u32,
+        reply: fuser::ReplyEmpty,
+    ) {
+        reply.error(libc::EROFS);
+    }
+
+    fn create(
+        &mut self,
+        _req: &Request<'_>,
+        _parent: u64,
+        _name: &OsStr,
+        _mode: u32,
+        _umask: u32,
+        _flags: i32,
+        reply: fuser::ReplyCreate,
+    ) {
+        reply.error(libc::EROFS);
+    }
+}
+
+/// Mounts `pool` read-only at `mountpoint`, blocking until it's unmounted.
+pub fn mount(
+    mountpoint: impl AsRef<std::path::Path>,
+    pool: Pool,
+    read_cluster: ClusterReader,
+) -> std::io::Result<()> {
+    let options = [
+        fuser::MountOption::RO,
+        fuser::MountOption::FSName("doscontainer".to_string()),
+    ];
+    fuser::mount2(PoolFs::new(pool, read_cluster), mountpoint, &options)
+}
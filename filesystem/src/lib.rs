@@ -5,13 +5,22 @@ use error::FileSystemError;
 
 mod allocationtable;
 mod attributes;
+pub mod bootsector;
 mod bpb;
+pub mod deserializer;
 mod direntry;
 mod error;
 pub mod fat12;
+pub mod format;
+pub mod formatoptions;
+mod fsck;
+#[cfg(feature = "fuse")]
+pub mod fuse;
+mod name;
 mod names;
 mod pool;
 pub mod serializer;
+pub mod vfs;
 
 // Cluster index into the FAT
 pub type ClusterIndex = usize;
@@ -21,6 +30,7 @@ mod tests;
 
 pub(crate) trait ClusterIO {
     fn write_cluster(&mut self, index: ClusterIndex, data: &[u8]) -> Result<(), FileSystemError>;
+    fn read_cluster(&mut self, index: ClusterIndex) -> Result<Vec<u8>, FileSystemError>;
     fn cluster_to_sector(&self, index: ClusterIndex) -> usize;
     fn data_region_start(&self) -> usize;
 }
@@ -49,4 +59,14 @@ pub trait FileSystem {
         entries_count: usize,
         creation_time: Option<NaiveDateTime>,
     ) -> Result<(), FileSystemError>;
+
+    /// Reads a file's full contents back from disk, following its cluster
+    /// chain and truncating the result to its recorded file size.
+    fn read_file(&mut self, path: &str) -> Result<Vec<u8>, FileSystemError>;
+
+    /// Number of clusters not currently allocated to any file or directory.
+    fn free_clusters(&self) -> usize;
+
+    /// Number of clusters currently allocated to some file or directory.
+    fn used_clusters(&self) -> usize;
 }
@@ -1,20 +1,156 @@
+use std::fmt;
+
 use crate::error::FileSystemError;
 
-/// Struct to represent FAT12-compatible file names in a
-/// type-safe manner.
+/// Maximum length of the base (file-stem) portion of a FAT short name.
+const MAX_BASE_LEN: usize = 8;
+
+/// Maximum length of the extension portion of a FAT short name.
+const MAX_EXT_LEN: usize = 3;
+
+/// A generated FAT short (8.3) name: `name` and `extension` are always
+/// space-padded to their fixed widths, ready to be written straight into a
+/// directory entry's name field.
+///
+/// Unlike [`crate::names::EntryName`], which rejects anything that doesn't
+/// already conform, `DirEntryName` implements the actual FAT short-name
+/// *generation* algorithm: it normalizes whatever it's given into something
+/// valid, tracking whether that normalization was lossy, and resolves
+/// collisions with existing siblings by appending a numeric tail.
 pub struct DirEntryName {
     name: String,
     extension: String,
+    lossy: bool,
 }
 
 impl DirEntryName {
-    /// Create a new isntance of an EntryName. This struct enforces guarantees that
-    /// all names strictly coform to FAT's short filename limitations.
-    pub fn new(name: String, extension: Option<String>) -> Result<Self, FileSystemError> {
-        let mut normalized_name = name.trim().to_ascii_uppercase();
+    /// Generates a FAT short name for `desired`, deduplicated against
+    /// `existing` sibling `(name, extension)` pairs already present in the
+    /// target directory.
+    ///
+    /// Leading periods and spaces are stripped, the remainder is uppercased,
+    /// and any byte FAT disallows in a short name (`+,;=[]`, control
+    /// characters, and anything outside ASCII) is replaced with `_`. The
+    /// result is split on its final `.` into a base of up to 8 characters and
+    /// an extension of up to 3; truncation, character replacement, or extra
+    /// dots all set `lossy`. A name that collides with `existing` gets a
+    /// numeric tail (`~1`, `~2`, ...) spliced in before the 8-character
+    /// boundary, shrinking the base to make room.
+    pub fn new(desired: &str, existing: &[(&str, &str)]) -> Result<Self, FileSystemError> {
+        let trimmed = desired.trim_start_matches(['.', ' ']);
+        if trimmed.is_empty() {
+            return Err(FileSystemError::EmptyFileName);
+        }
+
+        let (raw_base, raw_ext) = match trimmed.rsplit_once('.') {
+            Some((base, ext)) => (base, ext),
+            None => (trimmed, ""),
+        };
+
+        let (base, base_lossy) = Self::sanitize(raw_base, MAX_BASE_LEN);
+        let (extension, ext_lossy) = Self::sanitize(raw_ext, MAX_EXT_LEN);
+        let lossy = base_lossy || ext_lossy || trimmed.matches('.').count() > 1;
+
+        if base.is_empty() {
+            return Err(FileSystemError::EmptyFileName);
+        }
+
+        let base = Self::dedupe(&base, &extension, existing);
+
         Ok(Self {
-            name: String::new(),
-            extension: String::new(),
+            name: Self::pad(&base, MAX_BASE_LEN),
+            extension: Self::pad(&extension, MAX_EXT_LEN),
+            lossy,
         })
     }
+
+    /// Uppercases `input`, replaces disallowed bytes with `_`, and truncates
+    /// to `max_len`, reporting whether truncation or character replacement
+    /// occurred.
+    fn sanitize(input: &str, max_len: usize) -> (String, bool) {
+        let mut lossy = false;
+        let mut out = String::with_capacity(max_len.min(input.len()));
+
+        for c in input.chars() {
+            if out.chars().count() == max_len {
+                lossy = true;
+                break;
+            }
+
+            let upper = c.to_ascii_uppercase();
+            if upper.is_ascii_alphanumeric() {
+                out.push(upper);
+            } else if matches!(upper, '+' | ',' | ';' | '=' | '[' | ']') || upper.is_ascii_control()
+            {
+                out.push('_');
+                lossy = true;
+            } else if upper.is_ascii() {
+                out.push(upper);
+            } else {
+                out.push('_');
+                lossy = true;
+            }
+        }
+
+        (out, lossy)
+    }
+
+    fn pad(field: &str, width: usize) -> String {
+        format!("{field:<width$}")
+    }
+
+    /// Appends a `~N` numeric tail to `base` until the combination of `base`
+    /// and `extension` no longer collides with `existing`, fitting the tail
+    /// before the 8-character boundary so longer stems shrink to make room.
+    fn dedupe(base: &str, extension: &str, existing: &[(&str, &str)]) -> String {
+        if !Self::collides(base, extension, existing) {
+            return base.to_string();
+        }
+
+        for n in 1usize.. {
+            let suffix = format!("~{n}");
+            let keep = MAX_BASE_LEN.saturating_sub(suffix.len());
+            let stem: String = base.chars().take(keep).collect();
+            let candidate = format!("{stem}{suffix}");
+            if !Self::collides(&candidate, extension, existing) {
+                return candidate;
+            }
+        }
+
+        unreachable!("numeric tail search never terminates")
+    }
+
+    fn collides(base: &str, extension: &str, existing: &[(&str, &str)]) -> bool {
+        existing
+            .iter()
+            .any(|(name, ext)| *name == base && *ext == extension)
+    }
+
+    /// The 8-character (space-padded) base name.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// The 3-character (space-padded) extension.
+    pub fn extension(&self) -> &str {
+        &self.extension
+    }
+
+    /// Whether generating this name required lossy normalization: truncation,
+    /// illegal characters, or more than one dot in the input.
+    pub fn lossy(&self) -> bool {
+        self.lossy
+    }
+}
+
+impl fmt::Display for DirEntryName {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let name = self.name.trim_end();
+        let extension = self.extension.trim_end();
+        if extension.is_empty() {
+            write!(f, "{name}")
+        } else {
+            write!(f, "{name}.{extension}")
+        }
+    }
 }
@@ -2,6 +2,15 @@ use std::{fmt, str::FromStr};
 
 use crate::error::FileSystemError;
 
+/// A name that already conforms to FAT's 8.3 short-name rules.
+///
+/// `from_str` uppercases its input and otherwise only *validates*; it rejects
+/// anything longer than 8.3 or with more than one dot rather than shortening
+/// it. Turning
+/// an arbitrary long name into a conforming one (and recording the original
+/// under `DirEntry::long_name` for VFAT LFN entries) is
+/// [`crate::name::DirEntryName`]'s job, driven through
+/// [`crate::pool::Pool::generate_short_name`].
 #[derive(Debug, PartialEq)]
 pub struct EntryName {
     pub filename: String,
@@ -2,7 +2,7 @@ use std::{path::Path, str::FromStr};
 
 use uuid::Uuid;
 
-use crate::{direntry::DirEntry, error::FileSystemError, names::EntryName};
+use crate::{direntry::DirEntry, error::FileSystemError, name::DirEntryName, names::EntryName};
 
 #[derive(Debug)]
 pub struct Pool {
@@ -72,6 +72,112 @@ impl Pool {
         Ok(())
     }
 
+    /// Creates a new subdirectory named `name` under `parent`, along with the
+    /// `.` and `..` entries every FAT subdirectory stores as its own first
+    /// two entries (see [`DirEntry::new_dot`], [`DirEntry::new_dotdot`]).
+    ///
+    /// `parent` is taken by UUID rather than `&DirEntry`, since this is a
+    /// mutating call: a reference into the pool's own entries can't survive
+    /// alongside it the way it can for `&self` lookups like
+    /// [`Pool::entry_by_name`].
+    ///
+    /// Subject to the same validation as [`Pool::add_entry`]: `parent` must
+    /// already be in the pool and be a directory, and no sibling may already
+    /// use `name`.
+    ///
+    /// # Returns
+    ///
+    /// The new subdirectory's UUID.
+    pub fn create_dir(&mut self, parent: &Uuid, name: &str) -> Result<Uuid, FileSystemError> {
+        let parent_entry = self.entry(parent).ok_or(FileSystemError::ParentNotFound)?;
+        let mut dir = DirEntry::new_directory(name)?;
+        dir.set_parent(parent_entry);
+        let dir_uuid = *dir.uuid();
+        self.add_entry(dir)?;
+
+        self.entries.push(DirEntry::new_dot(dir_uuid));
+        self.entries.push(DirEntry::new_dotdot(dir_uuid));
+
+        Ok(dir_uuid)
+    }
+
+    /// Removes the entry identified by `uuid` from the pool.
+    ///
+    /// # Errors
+    ///
+    /// Returns `FileSystemError::EntryNotFound` if no entry with `uuid`
+    /// exists, or `FileSystemError::DirectoryNotEmpty` if it's a directory
+    /// that still has children (the `.` and `..` entries a directory creates
+    /// for itself don't count, since they belong to it rather than being
+    /// children of it).
+    pub fn remove_entry(&mut self, uuid: &Uuid) -> Result<(), FileSystemError> {
+        let entry = self.entry(uuid).ok_or(FileSystemError::EntryNotFound)?;
+
+        if entry.is_directory()
+            && self
+                .children(entry)
+                .iter()
+                .any(|child| !Self::is_dot_entry(child))
+        {
+            return Err(FileSystemError::DirectoryNotEmpty);
+        }
+
+        self.entries
+            .retain(|e| e.uuid() != uuid && e.parent() != Some(uuid));
+        Ok(())
+    }
+
+    /// Renames and/or moves the entry identified by `uuid` to `new_name`
+    /// under `new_parent`, re-checking the same invariants
+    /// [`Pool::add_entry`] enforces for a freshly added entry: `new_parent`
+    /// must be a directory, and no other entry may already use `new_name`
+    /// there.
+    ///
+    /// `new_parent` is taken by UUID rather than `&DirEntry`, for the same
+    /// reason as [`Pool::create_dir`].
+    pub fn rename(
+        &mut self,
+        uuid: &Uuid,
+        new_name: &str,
+        new_parent: &Uuid,
+    ) -> Result<(), FileSystemError> {
+        let new_parent_entry = self
+            .entry(new_parent)
+            .ok_or(FileSystemError::ParentNotFound)?;
+
+        if !new_parent_entry.is_directory() {
+            return Err(FileSystemError::EntryCannotHaveChildren);
+        }
+
+        let new_entry_name = EntryName::from_str(new_name)?;
+        if self
+            .children(new_parent_entry)
+            .iter()
+            .any(|e| e.uuid() != uuid && e.name() == Some(&new_entry_name))
+        {
+            return Err(FileSystemError::DuplicateEntry);
+        }
+
+        let entry = self
+            .entries
+            .iter_mut()
+            .find(|e| e.uuid() == uuid)
+            .ok_or(FileSystemError::EntryNotFound)?;
+
+        entry.set_name(new_entry_name);
+        entry.set_parent_uuid(*new_parent);
+        Ok(())
+    }
+
+    /// Whether `entry`'s name is the literal `.` or `..` a directory creates
+    /// for itself, as opposed to a name a caller chose.
+    fn is_dot_entry(entry: &DirEntry) -> bool {
+        matches!(
+            entry.name().map(|name| name.filename.as_str()),
+            Some(".") | Some("..")
+        )
+    }
+
     /// Return an entry by its Uuid
     ///
     /// # Arguments
@@ -87,6 +193,12 @@ impl Pool {
 
     /// Finds a directory entry by its name within the children of a given parent directory.
     ///
+    /// `name` is matched case-insensitively against either form an entry may be known
+    /// by: its 8.3 short name, or its VFAT long name (if it has one because its short
+    /// name had to be generated lossily). A `name` that isn't itself a valid short name
+    /// (too long, too many dots, ...) simply can't match on the short form and falls
+    /// through to the long-name comparison, rather than being rejected outright.
+    ///
     /// # Arguments
     ///
     /// * `name` - The name of the entry to find (as a string slice).
@@ -94,26 +206,50 @@ impl Pool {
     ///
     /// # Returns
     ///
-    /// Returns a `Result` containing:
-    /// - `Ok(Some(&DirEntry))` if an entry with the specified name exists among the parent's children.
-    /// - `Ok(None)` if no matching entry is found.
-    /// - `Err(FileSystemError)` if the provided name is invalid or cannot be parsed into an `EntryName`.
-    ///
-    /// # Errors
-    ///
-    /// This function returns an error if `name` is not a valid entry name as defined by `EntryName::from_str`.
+    /// Returns `Ok(Some(&DirEntry))` if a matching entry exists among the parent's
+    /// children, or `Ok(None)` if none does.
     pub fn entry_by_name(
         &self,
         name: &str,
         parent: &DirEntry,
     ) -> Result<Option<&DirEntry>, FileSystemError> {
-        let entry_name = EntryName::from_str(name)?;
         let children = self.children(parent);
-        let entry = children
+
+        if let Ok(entry_name) = EntryName::from_str(name) {
+            if let Some(entry) = children
+                .iter()
+                .find(|entry| entry.name() == Some(&entry_name))
+            {
+                return Ok(Some(entry));
+            }
+        }
+
+        Ok(children.into_iter().find(|entry| {
+            entry
+                .long_name()
+                .is_some_and(|long| long.eq_ignore_ascii_case(name))
+        }))
+    }
+
+    /// Generates a unique 8.3 short name for `desired`, deduplicated against the
+    /// short names already present under `parent`, using the `NAME~N` numeric-tail
+    /// algorithm (see [`DirEntryName`]). Callers building a [`DirEntry`] for `desired`
+    /// should pass its [`DirEntryName::to_string`] as the entry's name, and record
+    /// `desired` as the entry's long name (via [`DirEntry::set_long_name`]) when
+    /// [`DirEntryName::lossy`] reports the short form couldn't represent it exactly.
+    pub fn generate_short_name(
+        &self,
+        desired: &str,
+        parent: &DirEntry,
+    ) -> Result<DirEntryName, FileSystemError> {
+        let siblings: Vec<(&str, &str)> = self
+            .children(parent)
             .iter()
-            .find(|entry| entry.name() == Some(&entry_name))
-            .copied();
-        Ok(entry)
+            .filter_map(|entry| entry.name())
+            .map(|name| (name.filename.as_str(), name.extension.as_str()))
+            .collect();
+
+        DirEntryName::new(desired, &siblings)
     }
 
     /// Returns all directory entries that are direct children of the given parent entry.
@@ -137,6 +273,23 @@ impl Pool {
             .collect()
     }
 
+    /// Iterates every entry currently in the pool, in no particular order.
+    ///
+    /// Crate-internal: callers outside the pool should reach entries
+    /// through [`Pool::entry`], [`Pool::children`], or [`Pool::entry_by_path`]
+    /// instead of walking the whole pool themselves. Used by
+    /// [`crate::fsck::check`] to cross-reference every entry's cluster
+    /// chain against the allocation table.
+    pub(crate) fn entries(&self) -> impl Iterator<Item = &DirEntry> {
+        self.entries.iter()
+    }
+
+    /// Looks up the entry identified by `uuid` for mutation, e.g. to rewrite
+    /// its recorded file size during [`crate::fat12::Fat12::repair`].
+    pub(crate) fn entry_mut(&mut self, uuid: &Uuid) -> Option<&mut DirEntry> {
+        self.entries.iter_mut().find(|entry| entry.uuid() == uuid)
+    }
+
     /// Returns a reference to the root entry (if any)
     ///
     /// This method traverses the pool to find the root entry and returns either a reference
@@ -1,14 +1,25 @@
 use chrono::{Datelike, NaiveDateTime, Timelike};
 
 use crate::{
-    allocationtable::{AllocationTable, ClusterValue},
+    allocationtable::{AllocationTable, ClusterValue, FatType},
     direntry::DirEntry,
     error::FileSystemError,
     names::EntryName,
     pool::Pool,
 };
 
-use super::{DirEntrySerializer, DirectorySerializer, Fat12Serializer, NameSerializer};
+use super::{
+    DirEntrySerializer, DirectorySerializer, Fat12Serializer, Fat16Serializer, Fat32Serializer,
+    FatTableSerializer, LfnSerializer, NameSerializer, VfatDirectorySerializer,
+};
+
+/// Computes the one-byte checksum VFAT LFN entries store alongside the
+/// short name they belong to, so a reader can confirm the two match.
+fn lfn_checksum(short_name: &[u8; 11]) -> u8 {
+    short_name.iter().fold(0u8, |sum, &b| {
+        (((sum & 1) << 7) | (sum >> 1)).wrapping_add(b)
+    })
+}
 
 #[allow(dead_code)]
 pub struct IbmDos100 {}
@@ -75,8 +86,23 @@ impl DirEntrySerializer for IbmDos100 {
     }
 }
 
-impl DirectorySerializer for IbmDos100 {
-    fn serialize_directory(pool: &Pool, directory: &DirEntry) -> Result<Vec<u8>, FileSystemError> {
+/// Root directory entry count PC-DOS 1.00's formatter stamps on a 160KB
+/// floppy; `serialize_directory`/`serialize_directory_vfat` pad the root
+/// directory out to this many entries unless a caller asks for a different
+/// count via `serialize_directory_with_capacity`/
+/// `serialize_directory_vfat_with_capacity`.
+const DEFAULT_ROOT_DIR_ENTRIES: usize = 64;
+
+impl IbmDos100 {
+    /// Same as `DirectorySerializer::serialize_directory`, but pads the root
+    /// directory out to `root_dir_entries` entries instead of the PC-DOS 1.00
+    /// default of 64, for callers reproducing a nonstandard root directory
+    /// size.
+    pub fn serialize_directory_with_capacity(
+        pool: &Pool,
+        directory: &DirEntry,
+        root_dir_entries: usize,
+    ) -> Result<Vec<u8>, FileSystemError> {
         let mut bytes: Vec<u8> = Vec::new();
         let children: Vec<_> = pool
             .iter()
@@ -85,6 +111,7 @@ impl DirectorySerializer for IbmDos100 {
 
         for child in &children {
             let child_bytes = <IbmDos100 as DirEntrySerializer>::serialize_direntry(child)?;
+            super::try_reserve(&mut bytes, child_bytes.len())?;
             bytes.extend(child_bytes);
         }
 
@@ -95,8 +122,9 @@ impl DirectorySerializer for IbmDos100 {
                 0xF6, 0xF6, 0xF6, 0xF6,
             ];
 
-            if children.len() < 64 {
-                let placeholders_needed = 64 - children.len();
+            if children.len() < root_dir_entries {
+                let placeholders_needed = root_dir_entries - children.len();
+                super::try_reserve(&mut bytes, placeholders_needed * placeholder_bytes.len())?;
                 for _ in 0..placeholders_needed {
                     bytes.extend(&placeholder_bytes);
                 }
@@ -106,6 +134,12 @@ impl DirectorySerializer for IbmDos100 {
     }
 }
 
+impl DirectorySerializer for IbmDos100 {
+    fn serialize_directory(pool: &Pool, directory: &DirEntry) -> Result<Vec<u8>, FileSystemError> {
+        Self::serialize_directory_with_capacity(pool, directory, DEFAULT_ROOT_DIR_ENTRIES)
+    }
+}
+
 impl Fat12Serializer for IbmDos100 {
     fn serialize_fat12(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError> {
         const FAT12_MASK: u16 = 0x0FFF;
@@ -122,6 +156,7 @@ impl Fat12Serializer for IbmDos100 {
         // Serialize clusters from 2 to max
         let max_cluster = *clusters.keys().max().unwrap_or(&1); // at least 1 because we pushed two entries
 
+        super::try_reserve(&mut fat_entries, max_cluster.saturating_sub(1))?;
         for i in 2..=max_cluster {
             let entry = match clusters.get(&i) {
                 Some(ClusterValue::Next(n)) => {
@@ -138,7 +173,8 @@ impl Fat12Serializer for IbmDos100 {
             fat_entries.push(entry);
         }
 
-        let mut bytes = Vec::with_capacity((fat_entries.len() * 3).div_ceil(2));
+        let mut bytes = Vec::new();
+        super::try_reserve(&mut bytes, (fat_entries.len() * 3).div_ceil(2))?;
         let mut i = 0;
 
         while i + 1 < fat_entries.len() {
@@ -162,11 +198,219 @@ impl Fat12Serializer for IbmDos100 {
                     bytes.push(((a >> 8) as u8) & 0x0F);
                 }
         */
-        bytes.resize(fat.cluster_size(), 0);
+        let cluster_size = fat.cluster_size();
+        super::try_reserve(&mut bytes, cluster_size.saturating_sub(bytes.len()))?;
+        bytes.resize(cluster_size, 0);
+        Ok(bytes)
+    }
+}
+
+impl Fat16Serializer for IbmDos100 {
+    fn serialize_fat16(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError> {
+        const FAT16_EOC: u16 = 0xFFFF;
+        let clusters = fat.clusters();
+
+        let mut fat_entries: Vec<u16> = Vec::new();
+
+        // Cluster 0 reserved for the media descriptor (0xF8 is the
+        // conventional fixed-media value), cluster 1 is end-of-chain.
+        fat_entries.push(0xFFF8);
+        fat_entries.push(FAT16_EOC);
+
+        let max_cluster = *clusters.keys().max().unwrap_or(&1);
+
+        super::try_reserve(&mut fat_entries, max_cluster.saturating_sub(1))?;
+        for i in 2..=max_cluster {
+            let entry = match clusters.get(&i) {
+                Some(ClusterValue::Next(n)) => {
+                    if *n > u16::MAX as usize {
+                        return Err(FileSystemError::ClusterOutOfBounds);
+                    }
+                    *n as u16
+                }
+                Some(ClusterValue::EndOfChain) => FAT16_EOC,
+                Some(ClusterValue::Free) | None => 0x0000,
+                Some(ClusterValue::Bad) => 0xFFF7,
+                Some(ClusterValue::Reserved) => 0xFFF0,
+            };
+            fat_entries.push(entry);
+        }
+
+        let mut bytes = Vec::new();
+        super::try_reserve(&mut bytes, fat_entries.len() * 2)?;
+        for entry in fat_entries {
+            bytes.extend_from_slice(&entry.to_le_bytes());
+        }
+
+        let cluster_size = fat.cluster_size();
+        super::try_reserve(&mut bytes, cluster_size.saturating_sub(bytes.len()))?;
+        bytes.resize(cluster_size, 0);
+        Ok(bytes)
+    }
+}
+
+impl Fat32Serializer for IbmDos100 {
+    fn serialize_fat32(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError> {
+        const FAT32_MASK: u32 = 0x0FFF_FFFF;
+        const FAT32_EOC: u32 = 0x0FFF_FFFF;
+        let clusters = fat.clusters();
+
+        let mut fat_entries: Vec<u32> = Vec::new();
+
+        // Cluster 0 reserved for the media descriptor, cluster 1 is
+        // end-of-chain; the top nibble of every entry stays zero.
+        fat_entries.push(0x0FFF_FFF8);
+        fat_entries.push(FAT32_EOC);
+
+        let max_cluster = *clusters.keys().max().unwrap_or(&1);
+
+        super::try_reserve(&mut fat_entries, max_cluster.saturating_sub(1))?;
+        for i in 2..=max_cluster {
+            let entry = match clusters.get(&i) {
+                Some(ClusterValue::Next(n)) => {
+                    if *n > FAT32_MASK as usize {
+                        return Err(FileSystemError::ClusterOutOfBounds);
+                    }
+                    *n as u32
+                }
+                Some(ClusterValue::EndOfChain) => FAT32_EOC,
+                Some(ClusterValue::Free) | None => 0x0000_0000,
+                Some(ClusterValue::Bad) => 0x0FFF_FFF7,
+                Some(ClusterValue::Reserved) => 0x0FFF_FFF0,
+            };
+            fat_entries.push(entry & FAT32_MASK);
+        }
+
+        let mut bytes = Vec::new();
+        super::try_reserve(&mut bytes, fat_entries.len() * 4)?;
+        for entry in fat_entries {
+            bytes.extend_from_slice(&entry.to_le_bytes());
+        }
+
+        let cluster_size = fat.cluster_size();
+        super::try_reserve(&mut bytes, cluster_size.saturating_sub(bytes.len()))?;
+        bytes.resize(cluster_size, 0);
+        Ok(bytes)
+    }
+}
+
+impl FatTableSerializer for IbmDos100 {
+    fn serialize_fat_table(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError> {
+        match fat.fat_type() {
+            FatType::Fat12 => <IbmDos100 as Fat12Serializer>::serialize_fat12(fat),
+            FatType::Fat16 => <IbmDos100 as Fat16Serializer>::serialize_fat16(fat),
+            FatType::Fat32 => <IbmDos100 as Fat32Serializer>::serialize_fat32(fat),
+        }
+    }
+}
+
+impl LfnSerializer for IbmDos100 {
+    fn serialize_lfn_entries(entry: &DirEntry) -> Result<Vec<u8>, FileSystemError> {
+        let Some(long_name) = entry.long_name() else {
+            return Ok(Vec::new());
+        };
+
+        let short_name_bytes = match entry.name() {
+            Some(name) => IbmDos100::serialize_entryname(name)?,
+            None => return Err(FileSystemError::EmptyFileName),
+        };
+        let checksum = lfn_checksum(&short_name_bytes);
+
+        let units: Vec<u16> = long_name.encode_utf16().collect();
+        let chunks: Vec<&[u16]> = units.chunks(13).collect();
+        let last = chunks.len().saturating_sub(1);
+
+        let mut bytes = Vec::new();
+        super::try_reserve(&mut bytes, chunks.len() * 32)?;
+
+        // Slots are written in reverse order: the logically-last slot (OR'd
+        // with 0x40) first, counting down to sequence number 1.
+        for (i, chunk) in chunks.iter().enumerate().rev() {
+            let mut sequence = (i + 1) as u8;
+            if i == last {
+                sequence |= 0x40;
+            }
+
+            let mut slot = [0xFFu8; 32];
+            slot[0] = sequence;
+            slot[11] = 0x0F; // attribute: marks this as an LFN slot, not a real entry
+            slot[12] = 0x00; // type: always zero for VFAT LFN entries
+            slot[13] = checksum;
+            slot[26] = 0x00; // first cluster: always zero for LFN slots
+            slot[27] = 0x00;
+
+            let mut units_padded = chunk.to_vec();
+            if i == last && units_padded.len() < 13 {
+                units_padded.push(0x0000);
+                units_padded.resize(13, 0xFFFF);
+            }
+
+            for (idx, unit) in units_padded.iter().enumerate() {
+                let offset = match idx {
+                    0..=4 => 1 + idx * 2,
+                    5..=10 => 14 + (idx - 5) * 2,
+                    11..=12 => 28 + (idx - 11) * 2,
+                    _ => unreachable!("an LFN slot only ever holds 13 code units"),
+                };
+                slot[offset..offset + 2].copy_from_slice(&unit.to_le_bytes());
+            }
+
+            bytes.extend_from_slice(&slot);
+        }
+
+        Ok(bytes)
+    }
+}
+
+impl IbmDos100 {
+    /// Same as `VfatDirectorySerializer::serialize_directory_vfat`, but pads
+    /// the root directory out to `root_dir_entries` entries instead of the
+    /// PC-DOS 1.00 default of 64.
+    pub fn serialize_directory_vfat_with_capacity(
+        pool: &Pool,
+        directory: &DirEntry,
+        root_dir_entries: usize,
+    ) -> Result<Vec<u8>, FileSystemError> {
+        let mut bytes: Vec<u8> = Vec::new();
+        let children = pool.children(directory);
+
+        for child in &children {
+            let lfn_bytes = Self::serialize_lfn_entries(child)?;
+            let direntry_bytes = <IbmDos100 as DirEntrySerializer>::serialize_direntry(child)?;
+            super::try_reserve(&mut bytes, lfn_bytes.len() + direntry_bytes.len())?;
+            bytes.extend(lfn_bytes);
+            bytes.extend(direntry_bytes);
+        }
+
+        if directory.is_root() {
+            let placeholder_bytes: Vec<u8> = vec![
+                0xE5, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6,
+                0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6, 0xF6,
+                0xF6, 0xF6, 0xF6, 0xF6,
+            ];
+
+            if children.len() < root_dir_entries {
+                let placeholders_needed = root_dir_entries - children.len();
+                super::try_reserve(&mut bytes, placeholders_needed * placeholder_bytes.len())?;
+                for _ in 0..placeholders_needed {
+                    bytes.extend(&placeholder_bytes);
+                }
+            }
+        }
+
         Ok(bytes)
     }
 }
 
+impl VfatDirectorySerializer for IbmDos100 {
+    fn serialize_directory_vfat(
+        pool: &Pool,
+        directory: &DirEntry,
+    ) -> Result<Vec<u8>, FileSystemError> {
+        Self::serialize_directory_vfat_with_capacity(pool, directory, DEFAULT_ROOT_DIR_ENTRIES)
+    }
+}
+
 impl NameSerializer for IbmDos100 {
     fn serialize_entryname(name: &EntryName) -> Result<[u8; 11], FileSystemError> {
         let mut raw = [b' '; 11];
@@ -204,6 +448,51 @@ mod tests {
         }
     }
 
+    #[test]
+    fn fat16_packs_one_little_endian_u16_per_entry() {
+        let mut fat = AllocationTable::new(FatType::Fat16, 512, 10).unwrap();
+        fat.allocate(2, Some(3)).unwrap();
+        fat.allocate(3, None).unwrap();
+
+        let bytes = IbmDos100::serialize_fat16(&fat).unwrap();
+
+        assert_eq!(&bytes[0..2], &0xFFF8u16.to_le_bytes());
+        assert_eq!(&bytes[2..4], &0xFFFFu16.to_le_bytes());
+        assert_eq!(&bytes[4..6], &3u16.to_le_bytes());
+        assert_eq!(&bytes[6..8], &0xFFFFu16.to_le_bytes());
+    }
+
+    #[test]
+    fn fat32_packs_one_masked_little_endian_u32_per_entry() {
+        let mut fat = AllocationTable::new(FatType::Fat32, 512, 10).unwrap();
+        fat.allocate(2, Some(3)).unwrap();
+        fat.allocate(3, None).unwrap();
+        fat.reserve(4).unwrap();
+
+        let bytes = IbmDos100::serialize_fat32(&fat).unwrap();
+
+        assert_eq!(&bytes[0..4], &0x0FFF_FFF8u32.to_le_bytes());
+        assert_eq!(&bytes[4..8], &0x0FFF_FFFFu32.to_le_bytes());
+        assert_eq!(&bytes[8..12], &3u32.to_le_bytes());
+        assert_eq!(&bytes[12..16], &0x0FFF_FFFFu32.to_le_bytes());
+        assert_eq!(&bytes[16..20], &0x0FFF_FFF0u32.to_le_bytes());
+    }
+
+    #[test]
+    fn fat_table_serializer_dispatches_by_fat_type() {
+        let fat16 = AllocationTable::new(FatType::Fat16, 512, 10).unwrap();
+        let fat32 = AllocationTable::new(FatType::Fat32, 512, 10).unwrap();
+
+        assert_eq!(
+            IbmDos100::serialize_fat_table(&fat16).unwrap(),
+            IbmDos100::serialize_fat16(&fat16).unwrap()
+        );
+        assert_eq!(
+            IbmDos100::serialize_fat_table(&fat32).unwrap(),
+            IbmDos100::serialize_fat32(&fat32).unwrap()
+        );
+    }
+
     #[test]
     fn test_valid_short_name() {
         let name = make_name("FOO", "TXT");
@@ -246,6 +535,52 @@ mod tests {
         assert!(matches!(err, FileSystemError::FileNameTooLong));
     }
 
+    #[test]
+    fn entry_without_a_long_name_gets_no_lfn_slots() {
+        let entry = DirEntry::new_file("README.TXT").unwrap();
+        assert!(IbmDos100::serialize_lfn_entries(&entry).unwrap().is_empty());
+    }
+
+    #[test]
+    fn lfn_entries_split_into_13_char_slots_with_a_shared_checksum() {
+        let mut entry = DirEntry::new_file("README.TXT").unwrap();
+        entry.set_long_name("Readme Notes.txt".to_string());
+
+        let bytes = IbmDos100::serialize_lfn_entries(&entry).unwrap();
+        assert_eq!(bytes.len(), 64); // 16 UTF-16 code units -> two 13-char slots
+
+        let short_name = IbmDos100::serialize_entryname(entry.name().unwrap()).unwrap();
+        let checksum = lfn_checksum(&short_name);
+
+        // Slots are written topmost-first: the logically-last slot (sequence 2, OR'd
+        // with 0x40) comes first in the byte stream, then sequence 1.
+        assert_eq!(bytes[0], 0x02 | 0x40);
+        assert_eq!(bytes[32], 0x01);
+
+        for slot in bytes.chunks(32) {
+            assert_eq!(slot[11], 0x0F, "attribute byte must mark an LFN slot");
+            assert_eq!(slot[12], 0x00);
+            assert_eq!(slot[13], checksum);
+            assert_eq!(&slot[26..28], &[0x00, 0x00], "first cluster is always zero");
+        }
+    }
+
+    #[test]
+    fn a_short_final_lfn_slot_is_null_terminated_and_0xffff_padded() {
+        let mut entry = DirEntry::new_file("HI.TXT").unwrap();
+        entry.set_long_name("Hi.txt".to_string()); // 6 UTF-16 code units: fits in one slot
+
+        let bytes = IbmDos100::serialize_lfn_entries(&entry).unwrap();
+        assert_eq!(
+            bytes,
+            vec![
+                0x41, 0x48, 0x00, 0x69, 0x00, 0x2E, 0x00, 0x74, 0x00, 0x78, 0x00, 0x0F, 0x00,
+                0x2B, 0x74, 0x00, 0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+                0x00, 0x00, 0xFF, 0xFF, 0xFF, 0xFF,
+            ]
+        );
+    }
+
     #[test]
     /// This test recreates a DirEntry for a system file named IBMBIO.COM, which was on the
     /// original release floppy for PC-DOS 1.00. It had a creation date/time of July 23 1981
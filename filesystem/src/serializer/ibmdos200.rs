@@ -0,0 +1,168 @@
+use crate::{direntry::DirEntry, error::FileSystemError, pool::Pool};
+
+use super::ibmdos100::IbmDos100;
+use super::{DirEntrySerializer, DirectorySerializer, NameSerializer};
+
+/// Raw 8.3 name field for a "." entry: a single dot padded with spaces like
+/// any other short name.
+const DOT_NAME: [u8; 11] = [
+    b'.', b' ', b' ', b' ', b' ', b' ', b' ', b' ', b' ', b' ', b' ',
+];
+
+/// Raw 8.3 name field for a ".." entry.
+const DOTDOT_NAME: [u8; 11] = [
+    b'.', b'.', b' ', b' ', b' ', b' ', b' ', b' ', b' ', b' ', b' ',
+];
+
+/// Directory attribute bit (0x10), set on every entry this serializer emits
+/// for a directory, including the synthesized "." and ".." entries.
+const DIRECTORY_ATTRIBUTE: u8 = 0x10;
+
+#[allow(dead_code)]
+pub struct IbmDos200 {}
+
+impl IbmDos200 {
+    /// Builds the 32-byte "." or ".." entry pointing at `start_cluster`, the
+    /// only two directory entries that never go through `DirEntry`/`EntryName`
+    /// since those reject dotfile names outright.
+    fn synthetic_dir_entry(
+        raw_name: [u8; 11],
+        start_cluster: usize,
+    ) -> Result<[u8; 32], FileSystemError> {
+        let mut buf = [0u8; 32];
+        buf[0..11].copy_from_slice(&raw_name);
+        buf[11] = DIRECTORY_ATTRIBUTE;
+
+        let start_cluster: u16 = start_cluster
+            .try_into()
+            .map_err(|_| FileSystemError::ClusterOutOfBounds)?;
+        buf[26..28].copy_from_slice(&start_cluster.to_le_bytes());
+
+        Ok(buf)
+    }
+}
+
+impl DirEntrySerializer for IbmDos200 {
+    fn serialize_direntry(entry: &DirEntry) -> Result<Vec<u8>, FileSystemError> {
+        let mut buf = [0u8; 32];
+
+        let name_bytes = match &entry.name() {
+            Some(name) => <IbmDos100 as NameSerializer>::serialize_entryname(name)?,
+            None => return Err(FileSystemError::EmptyFileName),
+        };
+        buf[0..11].copy_from_slice(&name_bytes);
+
+        buf[11] = entry.attributes().as_byte();
+
+        let time = IbmDos100::encode_time(entry.creation_time());
+        buf[22..24].copy_from_slice(&time.to_le_bytes());
+
+        let date = IbmDos100::encode_date(entry.creation_time());
+        buf[24..26].copy_from_slice(&date.to_le_bytes());
+
+        let start_cluster = match entry.start_cluster() {
+            Some(cluster) if cluster <= 0xFFFF => cluster as u16,
+            Some(_) => return Err(FileSystemError::ClusterOutOfBounds),
+            None => 0,
+        };
+        buf[26..28].copy_from_slice(&start_cluster.to_le_bytes());
+
+        if entry.file_size() > u32::MAX as usize {
+            return Err(FileSystemError::FileTooLarge);
+        }
+        buf[28..32].copy_from_slice(&(entry.file_size() as u32).to_le_bytes());
+
+        Ok(buf.to_vec())
+    }
+}
+
+impl DirectorySerializer for IbmDos200 {
+    fn serialize_directory(pool: &Pool, directory: &DirEntry) -> Result<Vec<u8>, FileSystemError> {
+        let mut bytes: Vec<u8> = Vec::new();
+
+        if !directory.is_root() {
+            let own_cluster = directory.start_cluster().unwrap_or(0);
+            let parent_cluster = directory
+                .parent()
+                .and_then(|parent_uuid| pool.entry(parent_uuid))
+                .filter(|parent| !parent.is_root())
+                .and_then(|parent| parent.start_cluster())
+                .unwrap_or(0);
+
+            let dot = Self::synthetic_dir_entry(DOT_NAME, own_cluster)?;
+            let dotdot = Self::synthetic_dir_entry(DOTDOT_NAME, parent_cluster)?;
+            super::try_reserve(&mut bytes, dot.len() + dotdot.len())?;
+            bytes.extend(dot);
+            bytes.extend(dotdot);
+        }
+
+        for child in pool.children(directory) {
+            let child_bytes = <IbmDos200 as DirEntrySerializer>::serialize_direntry(child)?;
+            super::try_reserve(&mut bytes, child_bytes.len())?;
+            bytes.extend(child_bytes);
+        }
+
+        Ok(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::{NaiveDate, NaiveTime};
+
+    use super::*;
+    use crate::direntry::DirEntry;
+
+    #[test]
+    fn dot_and_dotdot_point_at_own_and_parent_cluster() {
+        let dot = IbmDos200::synthetic_dir_entry(DOT_NAME, 5).unwrap();
+        assert_eq!(&dot[0..11], b".          ");
+        assert_eq!(dot[11], DIRECTORY_ATTRIBUTE);
+        assert_eq!(&dot[26..28], &5u16.to_le_bytes());
+
+        let dotdot = IbmDos200::synthetic_dir_entry(DOTDOT_NAME, 0).unwrap();
+        assert_eq!(&dotdot[0..11], b"..         ");
+        assert_eq!(dotdot[11], DIRECTORY_ATTRIBUTE);
+        assert_eq!(&dotdot[26..28], &0u16.to_le_bytes());
+    }
+
+    #[test]
+    fn serialize_directory_prefixes_non_root_with_dot_and_dotdot() {
+        let mut pool = Pool::default();
+        let root_uuid = *pool.root_entry().unwrap().uuid();
+
+        let mut subdir = DirEntry::new_directory("SUBDIR").unwrap();
+        subdir.set_parent(pool.root_entry().unwrap());
+        subdir.set_start_cluster(2);
+        pool.add_entry(subdir).unwrap();
+
+        let subdir = pool
+            .entry_by_name("SUBDIR", pool.entry(&root_uuid).unwrap())
+            .unwrap()
+            .unwrap();
+
+        let bytes = IbmDos200::serialize_directory(&pool, subdir).unwrap();
+        assert_eq!(bytes.len(), 64);
+        assert_eq!(&bytes[0..11], b".          ");
+        assert_eq!(&bytes[26..28], &2u16.to_le_bytes());
+        assert_eq!(&bytes[32..43], b"..         ");
+        assert_eq!(&bytes[32 + 26..32 + 28], &0u16.to_le_bytes());
+    }
+
+    #[test]
+    fn serialize_directory_skips_dot_entries_for_root() {
+        let date = NaiveDate::from_ymd_opt(1983, 3, 8).unwrap();
+        let time = NaiveTime::from_hms_opt(0, 0, 0).unwrap();
+        let mut pool = Pool::default();
+
+        let mut file = DirEntry::new_file("README.TXT").unwrap();
+        file.set_parent(pool.root_entry().unwrap());
+        file.set_creation_time(chrono::NaiveDateTime::new(date, time));
+        file.set_start_cluster(2);
+        pool.add_entry(file).unwrap();
+
+        let bytes = IbmDos200::serialize_directory(&pool, pool.root_entry().unwrap()).unwrap();
+        assert_eq!(bytes.len(), 32);
+        assert_eq!(&bytes[0..11], b"README  TXT");
+    }
+}
@@ -4,6 +4,17 @@ use crate::{
 };
 
 pub mod ibmdos100;
+pub mod ibmdos200;
+
+/// Reserves `additional` capacity in `vec`, turning an allocation failure into
+/// a recoverable `FileSystemError::AllocationFailed` instead of letting the
+/// global allocator abort the process. Serializers call this ahead of any
+/// loop whose iteration count scales with volume size, so a huge FAT16/FAT32
+/// image runs out of memory gracefully instead of crashing.
+pub(crate) fn try_reserve<T>(vec: &mut Vec<T>, additional: usize) -> Result<(), FileSystemError> {
+    vec.try_reserve_exact(additional)
+        .map_err(|_| FileSystemError::AllocationFailed)
+}
 
 pub trait DirEntrySerializer {
     fn serialize_direntry(entry: &DirEntry) -> Result<Vec<u8>, FileSystemError>;
@@ -21,6 +32,44 @@ pub trait Fat12Serializer {
     fn serialize_fat12(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError>;
 }
 
+/// Packs an `AllocationTable` as FAT16 does: one little-endian `u16` per
+/// entry, with cluster 0 carrying the reserved media-descriptor marker and
+/// cluster 1 marked end-of-chain.
+pub trait Fat16Serializer {
+    fn serialize_fat16(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError>;
+}
+
+/// Packs an `AllocationTable` as FAT32 does: one little-endian `u32` per
+/// entry masked to 28 bits, with the top nibble always zero.
+pub trait Fat32Serializer {
+    fn serialize_fat32(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError>;
+}
+
+/// Serializes an `AllocationTable` to its on-disk bytes regardless of FAT
+/// width, dispatching to the packing rules (12-, 16-, or 32-bit entries) the
+/// table's own `FatType` calls for.
+pub trait FatTableSerializer {
+    fn serialize_fat_table(fat: &AllocationTable) -> Result<Vec<u8>, FileSystemError>;
+}
+
 pub trait NameSerializer {
     fn serialize_entryname(name: &EntryName) -> Result<[u8; 11], FileSystemError>;
 }
+
+/// Same as `DirectorySerializer`, but prefixes each child whose requested
+/// name didn't losslessly fit into its short name with the VFAT LFN entries
+/// needed to recover that long name. Targets that predate VFAT (PC-DOS 1.00)
+/// should keep using `DirectorySerializer::serialize_directory` instead.
+pub trait VfatDirectorySerializer {
+    fn serialize_directory_vfat(
+        fat: &Pool,
+        directory: &DirEntry,
+    ) -> Result<Vec<u8>, FileSystemError>;
+}
+
+/// Produces the VFAT LFN slots that must precede a short entry's own 32
+/// bytes to recover its long name, or an empty `Vec` if the entry has no
+/// long name on record.
+pub trait LfnSerializer {
+    fn serialize_lfn_entries(entry: &DirEntry) -> Result<Vec<u8>, FileSystemError>;
+}
@@ -1,18 +1,21 @@
 #[cfg(test)]
 mod tests {
     use crate::{
-        allocationtable::AllocationTable,
+        allocationtable::{AllocationTable, FatInconsistency, FatType},
         direntry::DirEntry,
         error::FileSystemError,
         fat12::Fat12,
+        format::{self, FatLayout, FatWidth},
         names::EntryName,
         pool::Pool,
-        serializer::{ibmdos100::IbmDos100, Fat12Serializer},
+        serializer::{ibmdos100::IbmDos100, DirectorySerializer, Fat12Serializer, FatTableSerializer},
         FileSystem,
     };
-    use disk::{error::DiskError, volume::Volume, Disk};
+    use chrono::NaiveDateTime;
+    use disk::{error::DiskError, geometry::Geometry, raw::RawImage, volume::Volume, Disk};
     use operatingsystem::OperatingSystem;
     use std::{path::Path, str::FromStr};
+    use tempfile::tempdir;
 
     struct DummyDisk;
 
@@ -144,6 +147,133 @@ mod tests {
         assert_eq!(table.allocate_entry(16385).unwrap().len(), 33);
     }
 
+    #[test]
+    fn allocationtable_allocate_chain() {
+        let mut table = AllocationTable::default();
+        let chain = table.allocate_chain(3).unwrap();
+        assert_eq!(chain.len(), 3);
+        assert!(table.chain(chain[0]).collect::<Result<Vec<_>, _>>().unwrap() == chain);
+    }
+
+    #[test]
+    fn allocationtable_free_chain_returns_clusters_to_the_pool() {
+        let mut table = AllocationTable::default();
+        let chain = table.allocate_chain(3).unwrap();
+        let free_before = table.free_clusters();
+
+        let freed = table.free_chain(chain[0]).unwrap();
+
+        assert_eq!(freed, 3);
+        assert_eq!(table.free_clusters(), free_before + 3);
+        for index in chain {
+            assert!(table.is_free(index).unwrap());
+        }
+    }
+
+    #[test]
+    fn allocationtable_allocate_chain_resumes_from_the_next_free_hint() {
+        let mut table = AllocationTable::default();
+        let first = table.allocate_chain(2).unwrap();
+        let second = table.allocate_chain(2).unwrap();
+
+        // The second allocation shouldn't rescan clusters the first one took.
+        assert!(second.iter().all(|c| !first.contains(c)));
+        assert_eq!(table.count_free_clusters(), table.free_clusters());
+    }
+
+    #[test]
+    fn allocationtable_check_finds_a_dangling_link() {
+        let mut table = AllocationTable::default();
+        table.set_cluster(3, crate::allocationtable::ClusterValue::Next(9999)).unwrap();
+
+        let findings = table.check(&[3]);
+
+        assert_eq!(
+            findings,
+            vec![FatInconsistency::DanglingLink { from: 3, to: 9999 }]
+        );
+    }
+
+    #[test]
+    fn allocationtable_check_finds_a_cross_linked_cluster() {
+        let mut table = AllocationTable::default();
+        table.allocate(5, Some(6)).unwrap();
+        table.allocate(6, None).unwrap();
+        table.allocate(10, Some(6)).unwrap();
+
+        let findings = table.check(&[5, 10]);
+
+        assert_eq!(
+            findings,
+            vec![FatInconsistency::CrossLinked {
+                cluster: 6,
+                first_head: 5,
+                second_head: 10,
+                via: Some(10),
+            }]
+        );
+    }
+
+    #[test]
+    fn allocationtable_check_finds_a_lost_chain() {
+        let mut table = AllocationTable::default();
+        table.allocate(7, None).unwrap();
+
+        let findings = table.check(&[]);
+        assert!(findings.is_empty(), "an empty chain_heads list skips the lost-chain check");
+
+        let findings = table.check(&[0]);
+        assert_eq!(findings, vec![FatInconsistency::LostChain { cluster: 7 }]);
+    }
+
+    #[test]
+    fn allocationtable_repair_truncates_a_dangling_link_and_frees_a_lost_chain() {
+        let mut table = AllocationTable::default();
+        table.set_cluster(3, crate::allocationtable::ClusterValue::Next(9999)).unwrap();
+        table.allocate(7, None).unwrap();
+
+        let findings = table.check(&[3]);
+        let repaired = table.repair(&findings);
+
+        assert_eq!(repaired.len(), 2);
+        assert!(table.chain(3).collect::<Result<Vec<_>, _>>().unwrap() == vec![3]);
+        assert!(table.is_free(7).unwrap());
+    }
+
+    #[test]
+    fn allocationtable_write_all_mirrors_every_fat_copy() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("mirror.img");
+        let mut disk = RawImage::new(&path, disk::sectorsize::SectorSize::S512, 20).unwrap();
+        let mut volume = Volume::new(&mut disk, 0, 20);
+
+        let mut table = AllocationTable::default();
+        table.allocate(3, Some(4)).unwrap();
+        table.allocate(4, None).unwrap();
+
+        table.write_all(&mut volume, 1, 1).unwrap();
+
+        assert_eq!(table.read_verify(&mut volume, 1, 1).unwrap(), None);
+    }
+
+    #[test]
+    fn allocationtable_read_verify_reports_the_diverging_copy() {
+        let dir = tempdir().unwrap();
+        let path = dir.path().join("diverge.img");
+        let mut disk = RawImage::new(&path, disk::sectorsize::SectorSize::S512, 20).unwrap();
+        let mut volume = Volume::new(&mut disk, 0, 20);
+
+        let mut table = AllocationTable::default();
+        table.allocate(3, Some(4)).unwrap();
+        table.allocate(4, None).unwrap();
+        table.write_all(&mut volume, 1, 1).unwrap();
+
+        // Corrupt the second FAT copy directly.
+        volume.write_sector(2, &[0xFF; 512]).unwrap();
+
+        assert_eq!(table.read_verify(&mut volume, 1, 1).unwrap(), Some(1));
+    }
+
     #[test]
     fn allocationtable_out_of_clusters() {
         let mut table = AllocationTable::default();
@@ -162,6 +292,25 @@ mod tests {
         );
     }
 
+    #[test]
+    fn fat_type_auto_selection_picks_narrowest_width() {
+        assert_eq!(FatType::for_cluster_count(4084), FatType::Fat12);
+        assert_eq!(FatType::for_cluster_count(4085), FatType::Fat16);
+        assert_eq!(FatType::for_cluster_count(65524), FatType::Fat16);
+        assert_eq!(FatType::for_cluster_count(65525), FatType::Fat32);
+    }
+
+    #[test]
+    fn fat_type_for_volume_derives_cluster_count_from_geometry() {
+        // A 360KB floppy: 720 sectors, 1 reserved, 2 FATs of 2 sectors each,
+        // 7 root dir sectors, 2 sectors per cluster.
+        assert_eq!(FatType::for_volume(720, 1, 2, 2, 7, 2), FatType::Fat12);
+        // 5000 data clusters of 1 sector each: past the FAT12 ceiling, within FAT16's.
+        assert_eq!(FatType::for_volume(5_049, 1, 2, 8, 32, 1), FatType::Fat16);
+        // 70000 data clusters: past the FAT16 ceiling.
+        assert_eq!(FatType::for_volume(70_049, 1, 2, 8, 32, 1), FatType::Fat32);
+    }
+
     #[test]
     fn new_fat12() {
         let mut disk = DummyDisk;
@@ -171,7 +320,8 @@ mod tests {
             1,
             340,
             &mut volume,
-            OperatingSystem::from_osshortname(&operatingsystem::OsShortName::IBMDOS100), None
+            OperatingSystem::from_osshortname(&operatingsystem::OsShortName::IBMDOS100),
+            None,
         )
         .unwrap();
         assert!(fat.mkfile("/COMMAND.COM", &[0u8; 10], None).is_ok());
@@ -189,7 +339,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ), None
+            ),
+            None,
         )
         .unwrap();
         assert!(fat.mkfile("/COMMAND.COM", &data, None).is_ok());
@@ -207,7 +358,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ),None
+            ),
+            None,
         )
         .unwrap();
         assert_eq!(
@@ -227,7 +379,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ), None
+            ),
+            None,
         )
         .unwrap();
         assert_eq!(
@@ -340,6 +493,72 @@ mod tests {
         );
     }
 
+    #[test]
+    fn pool_create_dir_adds_dot_entries() {
+        let mut pool = Pool::default();
+        let root_uuid = pool.root_entry().unwrap().uuid().clone();
+        let dos_uuid = pool.create_dir(&root_uuid, "DOS").unwrap();
+        let dos = pool.entry(&dos_uuid).unwrap();
+
+        assert_eq!(
+            pool.entry_by_name(".", dos).unwrap().unwrap().uuid(),
+            &dos_uuid
+        );
+        assert!(pool.entry_by_name("..", dos).unwrap().is_some());
+    }
+
+    #[test]
+    fn pool_remove_entry_rejects_nonempty_dir() {
+        let mut pool = Pool::default();
+        let root_uuid = pool.root_entry().unwrap().uuid().clone();
+        let dos_uuid = pool.create_dir(&root_uuid, "DOS").unwrap();
+        let mut edit_exe = DirEntry::new_file("EDIT.EXE").unwrap();
+        edit_exe.set_parent(pool.entry(&dos_uuid).unwrap());
+        assert!(pool.add_entry(edit_exe).is_ok());
+
+        assert_eq!(
+            pool.remove_entry(&dos_uuid),
+            Err(FileSystemError::DirectoryNotEmpty)
+        );
+    }
+
+    #[test]
+    fn pool_remove_entry_allows_empty_dir() {
+        let mut pool = Pool::default();
+        let root_uuid = pool.root_entry().unwrap().uuid().clone();
+        let dos_uuid = pool.create_dir(&root_uuid, "DOS").unwrap();
+
+        assert!(pool.remove_entry(&dos_uuid).is_ok());
+        assert!(pool.entry(&dos_uuid).is_none());
+    }
+
+    #[test]
+    fn pool_rename_moves_entry_and_rejects_duplicate() {
+        let mut pool = Pool::default();
+        let root_uuid = pool.root_entry().unwrap().uuid().clone();
+        let mut command_com = DirEntry::new_file("COMMAND.COM").unwrap();
+        command_com.set_parent(pool.root_entry().unwrap());
+        let command_uuid = command_com.uuid().clone();
+        assert!(pool.add_entry(command_com).is_ok());
+
+        let dos_uuid = pool.create_dir(&root_uuid, "DOS").unwrap();
+
+        assert!(pool
+            .rename(&command_uuid, "COMMAND.COM", &dos_uuid)
+            .is_ok());
+        let renamed = pool.entry(&command_uuid).unwrap();
+        assert_eq!(renamed.parent(), Some(&dos_uuid));
+
+        let mut autoexec_bat = DirEntry::new_file("AUTOEXEC.BAT").unwrap();
+        autoexec_bat.set_parent(pool.root_entry().unwrap());
+        assert!(pool.add_entry(autoexec_bat).is_ok());
+
+        assert_eq!(
+            pool.rename(&command_uuid, "AUTOEXEC.BAT", &root_uuid),
+            Err(FileSystemError::DuplicateEntry)
+        );
+    }
+
     #[test]
     fn fat12_mkdir() {
         let mut disk = DummyDisk;
@@ -351,7 +570,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ), None
+            ),
+            None,
         )
         .unwrap();
         assert!(filesystem.mkdir("/DOS", 2, None).is_ok());
@@ -370,7 +590,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ), None
+            ),
+            None,
         )
         .unwrap();
         assert!(fat.mkdir("/DOS", 600, None).is_ok());
@@ -387,7 +608,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ), None
+            ),
+            None,
         )
         .unwrap();
         let serializer = IbmDos100::serialize_fat12(fat.allocation_table()).unwrap();
@@ -429,7 +651,8 @@ mod tests {
             &mut volume,
             operatingsystem::OperatingSystem::from_osshortname(
                 &operatingsystem::OsShortName::IBMDOS100,
-            ), None
+            ),
+            None,
         )
         .unwrap();
         let os = OperatingSystem::from_vendor_version("ibm", "1.00").unwrap();
@@ -463,4 +686,199 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn fat_layout_computes_the_standard_region_offsets() {
+        let layout = FatLayout::new(
+            FatWidth::Fat12,
+            disk::sectorsize::SectorSize::S512,
+            2_880,
+            224,
+            2,
+        )
+        .unwrap();
+
+        assert_eq!(
+            layout.root_dir_start_sector(),
+            1 + 2 * layout.sectors_per_fat()
+        );
+        assert_eq!(
+            layout.data_region_start(),
+            1 + 2 * layout.sectors_per_fat() + layout.root_dir_sectors()
+        );
+    }
+
+    #[test]
+    fn format_writes_a_valid_boot_sector_and_reserved_fat_entries() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("floppy.img");
+        let mut disk = RawImage::new(&path, disk::sectorsize::SectorSize::S512, 2_880).unwrap();
+        let geometry = Geometry::new(80, 2, 18).unwrap();
+        let layout = FatLayout::new(
+            FatWidth::Fat12,
+            disk::sectorsize::SectorSize::S512,
+            2_880,
+            224,
+            2,
+        )
+        .unwrap();
+
+        format::format(&mut disk, 0, &geometry, &layout, 0xF0).unwrap();
+
+        let mut boot_sector = [0u8; 512];
+        disk.read_sector(0, &mut boot_sector).unwrap();
+        assert_eq!(&boot_sector[510..512], &[0x55, 0xAA]);
+        assert_eq!(&boot_sector[11..13], &512u16.to_le_bytes());
+
+        let mut first_fat_sector = [0u8; 512];
+        disk.read_sector(1, &mut first_fat_sector).unwrap();
+        assert_eq!(&first_fat_sector[0..3], &[0xF0, 0xFF, 0xFF]);
+    }
+
+    #[test]
+    fn write_root_entries_rejects_more_entries_than_the_layout_allows() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("floppy.img");
+        let mut disk = RawImage::new(&path, disk::sectorsize::SectorSize::S512, 2_880).unwrap();
+        let layout = FatLayout::new(
+            FatWidth::Fat12,
+            disk::sectorsize::SectorSize::S512,
+            2_880,
+            1,
+            2,
+        )
+        .unwrap();
+
+        let entry = format::root_directory_entry_bytes(
+            "README.TXT",
+            NaiveDateTime::parse_from_str("2026-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            Some(2),
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(
+            format::write_root_entries(&mut disk, 0, &layout, &[entry, entry]),
+            Err(FileSystemError::TooManyRootEntries)
+        );
+    }
+
+    #[test]
+    fn root_directory_entry_bytes_roundtrips_through_the_ibmdos100_serializer() {
+        let entry_bytes = format::root_directory_entry_bytes(
+            "README.TXT",
+            NaiveDateTime::parse_from_str("2026-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap(),
+            Some(2),
+            100,
+        )
+        .unwrap();
+
+        assert_eq!(&entry_bytes[0..11], b"README  TXT");
+        assert_eq!(&entry_bytes[26..28], &2u16.to_le_bytes());
+        assert_eq!(&entry_bytes[28..32], &100u32.to_le_bytes());
+    }
+
+    #[test]
+    fn from_volume_reconstructs_an_entry_written_through_the_real_bpb_and_fat_table() {
+        let tmpdir = tempdir().unwrap();
+        let path = tmpdir.path().join("floppy.img");
+        let mut disk = RawImage::new(&path, disk::sectorsize::SectorSize::S512, 2_880).unwrap();
+
+        {
+            let mut volume = Volume::new(&mut disk, 0, 2_880);
+            let mut fat =
+                Fat12::new(disk::sectorsize::SectorSize::S512, 1, 2_840, &mut volume).unwrap();
+            fat.mkfile("/README.TXT", b"hello disk", None).unwrap();
+
+            volume.write_sector(0, &fat.bpb().to_bytes()).unwrap();
+
+            let fat_bytes =
+                <IbmDos100 as FatTableSerializer>::serialize_fat_table(fat.allocation_table())
+                    .unwrap();
+            for i in 0..fat.bpb().fat_count() {
+                let start =
+                    fat.bpb().reserved_sectors() as u64 + (i * fat.bpb().sectors_per_fat()) as u64;
+                volume.write_sector(start, &fat_bytes).unwrap();
+            }
+
+            let root = fat.pool().root_entry().unwrap();
+            let dir_bytes =
+                <IbmDos100 as DirectorySerializer>::serialize_directory(fat.pool(), root).unwrap();
+            let dir_start = fat.bpb().root_dir_start_sector() as u64;
+            for (i, chunk) in dir_bytes.chunks(512).enumerate() {
+                volume.write_sector(dir_start + i as u64, chunk).unwrap();
+            }
+        }
+
+        let mut volume = Volume::new(&mut disk, 0, 2_880);
+        let fat = Fat12::from_volume(&mut volume).unwrap();
+
+        let root = fat.pool().root_entry().unwrap();
+        let entry = fat
+            .pool()
+            .entry_by_name("README.TXT", root)
+            .unwrap()
+            .unwrap();
+        assert_eq!(entry.file_size(), 10);
+        assert_eq!(entry.cluster_map(), &[2]);
+        assert!(fat.check().is_empty());
+    }
+
+    #[test]
+    fn new_entries_default_to_the_dos_epoch_not_the_wall_clock() {
+        // Images built without an explicit creation_time must come out
+        // byte-identical between runs, so the default can't be `Local::now()`.
+        let file = DirEntry::new_file("README.TXT").unwrap();
+        assert_eq!(
+            file.creation_time(),
+            NaiveDateTime::parse_from_str("1980-01-01 00:00:00", "%Y-%m-%d %H:%M:%S").unwrap()
+        );
+    }
+
+    #[test]
+    fn bootsector_carries_the_signature_and_the_chosen_stub() {
+        use crate::bootsector::{BootCode, BootSector};
+        use crate::bpb::BiosParameterBlock;
+
+        let non_system = BootSector::new(BiosParameterBlock::default(), BootCode::NonSystem).to_bytes();
+        assert_eq!(&non_system[510..512], &[0x55, 0xAA]);
+        assert!(non_system[62..].windows(3).any(|w| w == b"Non"));
+
+        let ipl = BootSector::new(BiosParameterBlock::default(), BootCode::Ipl).to_bytes();
+        assert_eq!(&ipl[510..512], &[0x55, 0xAA]);
+        assert_eq!(&ipl[62..67], &[0xEA, 0x00, 0x00, 0x00, 0x07]);
+    }
+
+    #[test]
+    fn bootsector_parse_is_the_inverse_of_to_bytes() {
+        use crate::bootsector::{BootCode, BootSector};
+        use crate::bpb::BiosParameterBlock;
+
+        for boot_code in [BootCode::NonSystem, BootCode::Ipl] {
+            let original = BootSector::new(BiosParameterBlock::default(), boot_code);
+            let bytes = original.to_bytes();
+            let parsed = BootSector::parse(&bytes).unwrap();
+
+            assert_eq!(parsed.boot_code(), boot_code);
+            assert_eq!(parsed.bpb().bytes_per_sector(), original.bpb().bytes_per_sector());
+            assert_eq!(
+                parsed.bpb().sectors_per_cluster(),
+                original.bpb().sectors_per_cluster()
+            );
+            assert_eq!(parsed.to_bytes(), bytes);
+        }
+    }
+
+    #[test]
+    fn bpb_from_bytes_rejects_a_zero_bytes_per_sector() {
+        use crate::bpb::BiosParameterBlock;
+
+        let mut sector = BiosParameterBlock::default().to_bytes();
+        sector[11..13].copy_from_slice(&0u16.to_le_bytes());
+
+        assert_eq!(
+            BiosParameterBlock::from_bytes(&sector),
+            Err(FileSystemError::InvalidBytesPerSector)
+        );
+    }
 }
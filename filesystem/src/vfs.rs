@@ -0,0 +1,173 @@
+use std::path::{Component, Path};
+
+use crate::{direntry::DirEntry, error::FileSystemError, names::EntryName, pool::Pool};
+
+/// Maximum number of directory components a path may resolve through.
+///
+/// This exists purely to stop a malformed or cyclic host path from recursing forever;
+/// nothing in real DOS-era hardware nested directories anywhere near this deep.
+const MAX_PATH_DEPTH: usize = 32;
+
+/// A thin virtual-filesystem layer that turns host paths into DOS-legal paths and
+/// resolves them against a [`Pool`].
+///
+/// This is what staging a host directory tree into a FAT12 volume walks: each host
+/// path is normalized to an absolute path, split into components, and each component
+/// is either resolved against an existing directory entry or mangled into a fresh
+/// uppercase 8.3 name.
+pub struct Vfs;
+
+impl Vfs {
+    /// Mangles a host filename into a DOS-legal uppercase 8.3 [`EntryName`].
+    ///
+    /// If the name already fits 8.3 once uppercased, it's used as-is. Otherwise it's
+    /// truncated and given a `~N` numeric tail, same as Windows' long-filename
+    /// mangling. `existing` should list the short names already present in the
+    /// destination directory so collisions can be detected and resolved by bumping
+    /// `N` until a free name is found.
+    pub fn mangle_83(
+        host_name: &str,
+        existing: &[EntryName],
+    ) -> Result<EntryName, FileSystemError> {
+        if host_name.is_empty() {
+            return Err(FileSystemError::EmptyFileName);
+        }
+
+        let (raw_base, raw_ext) = match host_name.rsplit_once('.') {
+            Some((base, ext)) if !base.is_empty() => (base, ext),
+            _ => (host_name, ""),
+        };
+
+        let filter = |s: &str| -> String {
+            s.chars()
+                .map(|c| c.to_ascii_uppercase())
+                .filter(|&c| EntryName::is_valid_char(c))
+                .collect()
+        };
+
+        let stripped_base: String = raw_base.chars().filter(|&c| c != ' ').collect();
+        let base_filtered = filter(&stripped_base);
+        let ext_filtered = filter(raw_ext);
+
+        let was_lossy = base_filtered.chars().count() != stripped_base.chars().count()
+            || base_filtered.chars().count() > 8
+            || ext_filtered.chars().count() > 3;
+
+        let base8: String = base_filtered.chars().take(8).collect();
+        let ext3: String = ext_filtered.chars().take(3).collect();
+
+        if !was_lossy {
+            let candidate = EntryName {
+                filename: base8.clone(),
+                extension: ext3.clone(),
+            };
+            if !existing.iter().any(|e| *e == candidate) {
+                return Ok(candidate);
+            }
+        }
+
+        for n in 1..=999_999u32 {
+            let suffix = format!("~{n}");
+            let keep = 8usize.saturating_sub(suffix.len());
+            let truncated_base: String = base8.chars().take(keep).collect();
+            let candidate = EntryName {
+                filename: format!("{truncated_base}{suffix}"),
+                extension: ext3.clone(),
+            };
+            if !existing.iter().any(|e| *e == candidate) {
+                return Ok(candidate);
+            }
+        }
+
+        Err(FileSystemError::DuplicateEntry)
+    }
+
+    /// Resolves an absolute host path against `pool`, returning the [`DirEntry`] it
+    /// refers to.
+    ///
+    /// # Errors
+    ///
+    /// - `FileSystemError::NotAbsolute` if `path` is not absolute.
+    /// - `FileSystemError::NotADirectory` if a non-final component isn't a directory.
+    /// - `FileSystemError::Recursion` if the path nests deeper than [`MAX_PATH_DEPTH`].
+    /// - `FileSystemError::InvalidPath` if a component can't be found, or isn't valid
+    ///   UTF-8, or the pool has no root directory.
+    pub fn resolve<'a>(pool: &'a Pool, path: &Path) -> Result<&'a DirEntry, FileSystemError> {
+        if !path.is_absolute() {
+            return Err(FileSystemError::NotAbsolute);
+        }
+
+        let mut current = pool.root_entry().ok_or(FileSystemError::InvalidPath)?;
+        let mut depth = 0;
+
+        for component in path.components() {
+            let name = match component {
+                Component::RootDir | Component::CurDir => continue,
+                Component::Normal(os_str) => os_str.to_str().ok_or(FileSystemError::InvalidPath)?,
+                Component::ParentDir | Component::Prefix(_) => {
+                    return Err(FileSystemError::InvalidPath)
+                }
+            };
+
+            depth += 1;
+            if depth > MAX_PATH_DEPTH {
+                return Err(FileSystemError::Recursion);
+            }
+
+            if !current.is_directory() {
+                return Err(FileSystemError::NotADirectory);
+            }
+
+            current = pool
+                .entry_by_name(name, current)?
+                .ok_or(FileSystemError::InvalidPath)?;
+        }
+
+        Ok(current)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn name(filename: &str, extension: &str) -> EntryName {
+        EntryName {
+            filename: filename.to_string(),
+            extension: extension.to_string(),
+        }
+    }
+
+    #[test]
+    fn short_name_passes_through_unchanged() {
+        let mangled = Vfs::mangle_83("readme.txt", &[]).unwrap();
+        assert_eq!(mangled, name("README", "TXT"));
+    }
+
+    #[test]
+    fn long_name_gets_truncated_and_tilded() {
+        let mangled = Vfs::mangle_83("configuration.txt", &[]).unwrap();
+        assert_eq!(mangled, name("CONFIG~1", "TXT"));
+    }
+
+    #[test]
+    fn colliding_long_names_bump_the_tilde_counter() {
+        let existing = vec![name("CONFIG~1", "TXT")];
+        let mangled = Vfs::mangle_83("configuration.txt", &existing).unwrap();
+        assert_eq!(mangled, name("CONFIG~2", "TXT"));
+    }
+
+    #[test]
+    fn resolve_rejects_relative_paths() {
+        let pool = Pool::default();
+        let err = Vfs::resolve(&pool, Path::new("games/doom")).unwrap_err();
+        assert!(matches!(err, FileSystemError::NotAbsolute));
+    }
+
+    #[test]
+    fn resolve_root_succeeds() {
+        let pool = Pool::default();
+        let root = Vfs::resolve(&pool, Path::new("/")).unwrap();
+        assert!(root.is_root());
+    }
+}
@@ -1,6 +1,8 @@
+use common::storage::Floppy;
 use disk::disktype::DiskType;
+use disk::geometry::Geometry;
 
-use crate::{error::OsError, OperatingSystem};
+use crate::{error::OsError, OperatingSystem, OsShortName};
 
 /// BIOS Parameter Block structure. Intuitively this should live with
 /// Disk, but there's a lot more dependency on the operating system that
@@ -24,16 +26,18 @@ impl BPB {
     /// The method returns the corresponding byte sequence for the specified operating system's BPB,
     /// or an error if the BPB is not applicable or the operating system is unsupported.
     ///
+    /// This struct only ever writes the 13-byte DOS 2.00 field set; it doesn't
+    /// carry the DOS 3.0+ geometry fields or the DOS 3.4 EBPB tail (volume
+    /// serial, label, filesystem type). `filesystem::bpb::BiosParameterBlock`
+    /// is the struct that covers those — see its `to_bytes` for a full
+    /// 512-byte boot sector with the extended and EBPB fields included.
+    ///
     /// # Errors:
     /// - `OsError::BpbNotApplicable` if the BPB is not relevant for the operating system (e.g., IBM PC-DOS 1.00 or 1.10).
-    /// - `OsError::UnsupportedOs` if the operating system is not supported.
     pub fn as_bytes(&self, operating_system: &OperatingSystem) -> Result<Vec<u8>, OsError> {
-        match operating_system {
-            OperatingSystem::IBMDOS200 => Ok(self.as_pcdos_200_bytes()),
-            OperatingSystem::IBMDOS100 | OperatingSystem::IBMDOS110 => {
-                Err(OsError::BpbNotApplicable)
-            }
-            _ => Err(OsError::UnsupportedOs),
+        match operating_system.shortname() {
+            OsShortName::IBMDOS200 => Ok(self.as_pcdos_200_bytes()),
+            OsShortName::IBMDOS100 | OsShortName::IBMDOS110 => Err(OsError::BpbNotApplicable),
         }
     }
 
@@ -52,9 +56,10 @@ impl BPB {
     }
 
     /// Instantiate a BIOS Parameter Block from a given disk type and OS combination.
-    /// This only works for floppies. Hard disks get a similar function based on their
-    /// geometry and OS. Floppies have a fixed, known geometry making this interface a
-    /// more logical choice for them.
+    /// This only works for floppies, since they have a fixed, known geometry that
+    /// makes a lookup table the more logical choice. Hard disks go through
+    /// `BPB::from_geometry` instead, which sizes everything from the disk's
+    /// actual geometry rather than a table.
     pub fn from_floppy(disktype: &DiskType) -> Result<Self, OsError> {
         match disktype {
             DiskType::F525_160 => Ok(BPB {
@@ -100,4 +105,87 @@ impl BPB {
             _ => Err(OsError::NotAFloppy),
         }
     }
+
+    /// Instantiate a BIOS Parameter Block for an arbitrary hard disk geometry,
+    /// the counterpart to `from_floppy` that `from_floppy`'s doc comment
+    /// points hard disks to. Derives `sectors_per_fat`,
+    /// `sectors_per_cluster`, and `rootdir_entries` from the disk's actual size
+    /// via the Microsoft "fatgen" recurrence instead of a fixed per-`DiskType`
+    /// lookup table, so a `HardDiskType::CUSTOM` volume formats correctly
+    /// instead of hitting `from_floppy`'s `OsError::NotAFloppy`.
+    ///
+    /// Assumes 512-byte sectors, matching every other constructor on this
+    /// struct. Only IBM PC-DOS 2.00 writes a BPB at all (`as_bytes` rejects
+    /// everything else), so `from_geometry` rejects any other `OperatingSystem`
+    /// the same way.
+    ///
+    /// `sectors_per_cluster` only steps up as far as FAT16 addressing
+    /// requires, since `as_pcdos_200_bytes` never carries a FAT32 BPB to begin
+    /// with. A FAT32-aware equivalent with the full DOS 3.4 EBPB already
+    /// exists at `filesystem::bpb::BiosParameterBlock::for_volume`; this crate
+    /// sits below `filesystem` in the dependency graph (`filesystem` already
+    /// depends on `operatingsystem`), so the two can't share a `FatType`
+    /// parameter without introducing a cycle.
+    pub fn from_geometry(geometry: &Geometry, os: &OperatingSystem) -> Result<Self, OsError> {
+        if !matches!(os.shortname(), OsShortName::IBMDOS200) {
+            return Err(OsError::BpbNotApplicable);
+        }
+
+        const BYTES_PER_SECTOR: usize = 512;
+        const FAT_COPIES: usize = 2;
+        const ROOTDIR_ENTRIES: usize = 512;
+        const DIRENTRY_SIZE: usize = 32;
+        const RESERVED_SECTORS: usize = 1;
+        const FAT16_CLUSTER_LIMIT: usize = 65525;
+        const FIXED_DISK_MEDIA_DESCRIPTOR: u8 = 0xF8;
+
+        let total_sectors = geometry.cylinders() * geometry.heads() * geometry.sectors();
+        let root_dir_sectors = (ROOTDIR_ENTRIES * DIRENTRY_SIZE).div_ceil(BYTES_PER_SECTOR);
+
+        let mut sectors_per_cluster = 1usize;
+        let sectors_per_fat = loop {
+            let tmp1 = total_sectors.saturating_sub(RESERVED_SECTORS + root_dir_sectors);
+            let tmp2 = 256 * sectors_per_cluster + FAT_COPIES;
+            let sectors_per_fat = tmp1.div_ceil(tmp2);
+
+            let non_data_sectors =
+                RESERVED_SECTORS + FAT_COPIES * sectors_per_fat + root_dir_sectors;
+            let data_sectors = total_sectors.saturating_sub(non_data_sectors);
+            let cluster_count = data_sectors / sectors_per_cluster;
+
+            if cluster_count < FAT16_CLUSTER_LIMIT || sectors_per_cluster >= 128 {
+                break sectors_per_fat;
+            }
+            sectors_per_cluster *= 2;
+        };
+
+        Ok(BPB {
+            bytes_per_sector: BYTES_PER_SECTOR as u16,
+            sectors_per_cluster: sectors_per_cluster as u8,
+            reserved_sectors: RESERVED_SECTORS as u16,
+            fat_copies: FAT_COPIES as u8,
+            rootdir_entries: ROOTDIR_ENTRIES as u16,
+            sector_count: u16::try_from(total_sectors).unwrap_or(0),
+            media_descriptor: FIXED_DISK_MEDIA_DESCRIPTOR,
+            sectors_per_fat: sectors_per_fat as u16,
+        })
+    }
+
+    /// Builds a BPB from one of the `Floppy` formats an `OsSupport` entry
+    /// advertises, reusing its geometry table instead of the fixed set
+    /// `from_floppy` covers. Unlike `from_floppy`, this never fails: every
+    /// `Floppy` variant carries a full geometry.
+    pub fn from_common_floppy(floppy: &Floppy) -> Self {
+        let geometry = floppy.geometry();
+        BPB {
+            bytes_per_sector: floppy.sector_size() as u16,
+            sectors_per_cluster: geometry.sectors_per_cluster as u8,
+            reserved_sectors: 1,
+            fat_copies: 2,
+            rootdir_entries: geometry.root_dir_entries as u16,
+            sector_count: floppy.sector_count() as u16,
+            media_descriptor: geometry.media_descriptor,
+            sectors_per_fat: geometry.sectors_per_fat as u16,
+        }
+    }
 }
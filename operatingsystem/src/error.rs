@@ -7,6 +7,13 @@ pub enum OsError {
     /// Indicates an attempt was made to use a BPB (BIOS Parameter Block) on an
     /// incompatible operating system or file system where it is not applicable.
     BpbNotApplicable,
+    /// A downloaded bootstrap archive's digest didn't match the value recorded
+    /// for this OS variant, meaning it's corrupted or tampered with.
+    ChecksumMismatch { expected: String, found: String },
+    /// This OS variant's registry entry doesn't carry a recorded digest yet, so
+    /// [`crate::OperatingSystem::verify`] has nothing to check a downloaded archive
+    /// against.
+    ChecksumNotPinned,
     /// Error when the OS product string is invalid. This can occur if the
     /// product identifier doesn't match known valid formats.
     InvalidOsProduct(String),
@@ -36,6 +43,15 @@ impl fmt::Display for OsError {
         use OsError::*;
         match self {
             BpbNotApplicable => write!(f, "BPB not applicable for this OS."),
+            ChecksumMismatch { expected, found } => write!(
+                f,
+                "Checksum mismatch on downloaded bootstrap archive: expected {}, found {}",
+                expected, found
+            ),
+            ChecksumNotPinned => write!(
+                f,
+                "No digest is recorded for this OS variant yet, so its bootstrap archive cannot be verified"
+            ),
             InvalidOsProduct(err) => write!(f, "Invalid OS product: {}", err),
             InvalidOsVendor(err) => write!(f, "Invalid OS vendor : {}", err),
             InvalidOsVersionFormat(err) => write!(f, "Invalid OS version format: {}", err),
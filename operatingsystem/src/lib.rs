@@ -4,12 +4,14 @@ use std::str::FromStr;
 use error::OsError;
 use product::OsProduct;
 use serde::Deserialize;
+use sha2::{Digest, Sha256};
 use url::Url;
 use vendor::OsVendor;
 use version::OsVersion;
 
 pub mod error;
 pub mod product;
+mod registry;
 pub mod vendor;
 pub mod version;
 
@@ -29,6 +31,8 @@ pub struct OperatingSystem {
     url: Url,
     vendor: OsVendor,
     version: OsVersion,
+    checksum_sha256: Option<&'static str>,
+    checksum_crc32: Option<u32>,
 }
 
 #[derive(Clone, Copy, Debug)]
@@ -56,6 +60,28 @@ impl OsShortName {
             Self::IBMDOS200 => OsVendor::IBM,
         }
     }
+
+    /// The registry key this variant is looked up by in `os_registry.toml`.
+    fn registry_key(&self) -> &'static str {
+        match self {
+            Self::IBMDOS100 => "IBMDOS100",
+            Self::IBMDOS110 => "IBMDOS110",
+            Self::IBMDOS200 => "IBMDOS200",
+        }
+    }
+}
+
+impl FromStr for OsShortName {
+    type Err = OsError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "IBMDOS100" => Ok(Self::IBMDOS100),
+            "IBMDOS110" => Ok(Self::IBMDOS110),
+            "IBMDOS200" => Ok(Self::IBMDOS200),
+            _ => Err(OsError::UnsupportedOs),
+        }
+    }
 }
 
 impl fmt::Display for OperatingSystem {
@@ -88,11 +114,8 @@ impl<'de> Deserialize<'de> for OsVersion {
 
 impl OperatingSystem {
     pub fn from_osshortname(shortname: &OsShortName) -> Self {
-        match shortname {
-            OsShortName::IBMDOS100 => Self::from_vendor_version("ibm", "1.00").unwrap(),
-            OsShortName::IBMDOS110 => Self::from_vendor_version("ibm", "1.10").unwrap(),
-            OsShortName::IBMDOS200 => Self::from_vendor_version("ibm", "2.00").unwrap(),
-        }
+        Self::from_shortname(shortname.registry_key())
+            .expect("every built-in OsShortName variant has a matching registry entry")
     }
 
     pub fn version(&self) -> OsVersion {
@@ -105,9 +128,9 @@ impl OperatingSystem {
 
     /// Constructs a specific `OperatingSystem` instance from a vendor and version string.
     ///
-    /// This method attempts to match the provided vendor and version against known supported
-    /// DOS variants. If a matching combination is found, it returns a fully initialized
-    /// `OperatingSystem` struct.
+    /// This method looks the provided vendor and version up in the built-in registry of
+    /// supported DOS variants (see [`registry`]). If a matching entry is found, it returns a
+    /// fully initialized `OperatingSystem` struct.
     ///
     /// # Arguments
     ///
@@ -117,7 +140,7 @@ impl OperatingSystem {
     /// # Errors
     ///
     /// Returns [`OsError::InvalidOsVendor`] or [`OsError::InvalidOsVersionFormat`] if the inputs
-    /// can't be parsed, or [`OsError::UnsupportedOs`] if the combination is not recognized.
+    /// can't be parsed, or [`OsError::UnsupportedOs`] if no registry entry matches.
     ///
     /// [`OsError::InvalidOsVendor`]: crate::error::OsError::InvalidOsVendor
     /// [`OsError::InvalidOsVersionFormat`]: crate::error::OsError::InvalidOsVersionFormat
@@ -126,56 +149,78 @@ impl OperatingSystem {
         let vendor = OsVendor::from_str(vendor)?;
         let version = OsVersion::from_str(version)?;
 
-        match (vendor, version) {
-            // IBM PC-DOS 1.00
-            (OsVendor::IBM, v) if v == OsVersion::new(1, 0) => Ok(Self {
-                bootsector: *include_bytes!("bootsectors/pcdos-100.bin"),
-                iosys: "IBMBIO.COM".to_string(),
-                iosys_bytes: (*include_bytes!("sysfiles/ibmdos100/IBMBIO.COM")).to_vec(),
-                msdossys: "IBMDOS.COM".to_string(),
-                msdossys_bytes: (*include_bytes!("sysfiles/ibmdos100/IBMDOS.COM")).to_vec(),
-                commandcom_bytes: (*include_bytes!("sysfiles/ibmdos100/COMMAND.COM")).to_vec(),
-                product: OsProduct::PcDos,
-                shortname: OsShortName::IBMDOS100,
-                url: Url::from_str("https://dosk8s-dist.area536.com/ibm-pc-dos-100-bootstrap.zip")
-                    .map_err(|_| OsError::InvalidUrl)?,
-                vendor,
-                version,
-                jumpcode: [0xEB, 0x2F, 0x14],
-            }),
-            // IBM PC-DOS 1.10
-            (OsVendor::IBM, v) if v == OsVersion::new(1, 10) => Ok(Self {
-                bootsector: *include_bytes!("bootsectors/pcdos-110.bin"),
-                iosys: "IBMBIO.COM".to_string(),
-                msdossys: "IBMDOS.COM".to_string(),
-                product: OsProduct::PcDos,
-                shortname: OsShortName::IBMDOS110,
-                url: Url::from_str("https://dosk8s-dist.area536.com/ibm-pc-dos-110-bootstrap.zip")
-                    .map_err(|_| OsError::InvalidUrl)?,
-                vendor,
-                version,
-                jumpcode: [0xEB, 0x27, 0x90],
-                msdossys_bytes: Vec::new(), // TODO
-                iosys_bytes: Vec::new(), // TODO
-                commandcom_bytes: Vec::new(), // TODO
-            }),
-            // IBM PC-DOS 2.00
-            (OsVendor::IBM, v) if v == OsVersion::new(2, 0) => Ok(Self {
-                bootsector: *include_bytes!("bootsectors/pcdos-200.bin"),
-                iosys: "IBMBIO.COM".to_string(),
-                msdossys: "IBMDOS.COM".to_string(),
-                product: OsProduct::PcDos,
-                shortname: OsShortName::IBMDOS200,
-                url: Url::from_str("https://dosk8s-dist.area536.com/ibm-pc-dos-200-bootstrap.zip")
-                    .map_err(|_| OsError::InvalidUrl)?,
-                vendor,
-                version,
-                jumpcode: [0xEB, 0x27, 0x90],
-                msdossys_bytes: Vec::new(), // TODO
-                iosys_bytes: Vec::new(), // TODO
-                commandcom_bytes: Vec::new(), // TODO
-            }),
-            _ => Err(OsError::UnsupportedOs),
+        let def = registry::find(vendor, version).ok_or(OsError::UnsupportedOs)?;
+        Self::from_definition(def)
+    }
+
+    /// Constructs an `OperatingSystem` from its registry shortname (e.g. `"IBMDOS100"`),
+    /// as opposed to [`Self::from_osshortname`] which takes the typed [`OsShortName`] enum
+    /// used elsewhere in the workspace.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OsError::UnsupportedOs`] if no registry entry has that shortname.
+    pub fn from_shortname(shortname: &str) -> Result<Self, OsError> {
+        let def = registry::find_by_shortname(shortname).ok_or(OsError::UnsupportedOs)?;
+        Self::from_definition(def)
+    }
+
+    /// Assembles an `OperatingSystem` from a registry entry's "soft" metadata plus the
+    /// variant's static bootsector/system-file bytes.
+    fn from_definition(def: &'static registry::OsDefinition) -> Result<Self, OsError> {
+        let vendor = def.vendor()?;
+        let version = def.version()?;
+        let product = def.product()?;
+        let shortname = def.osshortname()?;
+        let url = Url::from_str(&def.download_url).map_err(|_| OsError::InvalidUrl)?;
+        let (bootsector, iosys_bytes, msdossys_bytes, commandcom_bytes) =
+            Self::static_bytes(shortname);
+
+        Ok(Self {
+            bootsector,
+            jumpcode: def.jumpcode,
+            msdossys: def.msdossys.clone(),
+            msdossys_bytes,
+            iosys: def.iosys.clone(),
+            iosys_bytes,
+            commandcom_bytes,
+            product,
+            shortname,
+            url,
+            vendor,
+            version,
+            checksum_sha256: def.checksum_sha256.as_deref(),
+            checksum_crc32: def.checksum_crc32,
+        })
+    }
+
+    /// The bootsector and system-file bytes for each built-in variant.
+    ///
+    /// These can't be data-driven the way the rest of [`registry::OsDefinition`] is:
+    /// `include_bytes!` requires a compile-time string literal path, so there's no way to
+    /// resolve a path read out of `os_registry.toml` at runtime without a build script.
+    /// Adding a new DOS release therefore still needs one match arm here, alongside its
+    /// `[[os]]` entry in the TOML table.
+    fn static_bytes(shortname: OsShortName) -> ([u8; 512], Vec<u8>, Vec<u8>, Vec<u8>) {
+        match shortname {
+            OsShortName::IBMDOS100 => (
+                *include_bytes!("bootsectors/pcdos-100.bin"),
+                include_bytes!("sysfiles/ibmdos100/IBMBIO.COM").to_vec(),
+                include_bytes!("sysfiles/ibmdos100/IBMDOS.COM").to_vec(),
+                include_bytes!("sysfiles/ibmdos100/COMMAND.COM").to_vec(),
+            ),
+            OsShortName::IBMDOS110 => (
+                *include_bytes!("bootsectors/pcdos-110.bin"),
+                Vec::new(), // TODO
+                Vec::new(), // TODO
+                Vec::new(), // TODO
+            ),
+            OsShortName::IBMDOS200 => (
+                *include_bytes!("bootsectors/pcdos-200.bin"),
+                Vec::new(), // TODO
+                Vec::new(), // TODO
+                Vec::new(), // TODO
+            ),
         }
     }
 
@@ -211,6 +256,57 @@ impl OperatingSystem {
         self.url.as_str()
     }
 
+    /// The expected SHA-256 digest (lowercase hex) of this variant's bootstrap archive,
+    /// or `None` if no digest has been recorded for it yet.
+    pub fn expected_sha256(&self) -> Option<&str> {
+        self.checksum_sha256
+    }
+
+    /// The expected CRC-32 of this variant's bootstrap archive, for a cheap pre-check
+    /// before paying for a full SHA-256 pass, or `None` if no digest has been recorded
+    /// for it yet.
+    pub fn expected_crc32(&self) -> Option<u32> {
+        self.checksum_crc32
+    }
+
+    /// Verifies that `bytes` (a freshly downloaded bootstrap archive) matches this
+    /// variant's recorded digest.
+    ///
+    /// Checks the CRC-32 first since it's essentially free, then the SHA-256, so a
+    /// caller streaming the download can run the cheap check incrementally and only
+    /// pay for the full digest once the whole archive is in hand.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`OsError::ChecksumNotPinned`] if this variant's registry entry doesn't
+    /// carry a recorded digest yet, and [`OsError::ChecksumMismatch`] if either digest
+    /// doesn't match a digest that is recorded.
+    pub fn verify(&self, bytes: &[u8]) -> Result<(), OsError> {
+        let (Some(expected_crc32), Some(expected_sha256)) =
+            (self.checksum_crc32, self.checksum_sha256)
+        else {
+            return Err(OsError::ChecksumNotPinned);
+        };
+
+        let crc32 = crc32fast::hash(bytes);
+        if crc32 != expected_crc32 {
+            return Err(OsError::ChecksumMismatch {
+                expected: format!("{:08x}", expected_crc32),
+                found: format!("{:08x}", crc32),
+            });
+        }
+
+        let sha256 = format!("{:x}", Sha256::digest(bytes));
+        if sha256 != expected_sha256 {
+            return Err(OsError::ChecksumMismatch {
+                expected: expected_sha256.to_string(),
+                found: sha256,
+            });
+        }
+
+        Ok(())
+    }
+
     /// Return the filename this OS uses for the COMMAND.COM equivalent system file.
     pub fn commandcom(&self) -> String {
         "COMMAND.COM".to_string()
@@ -0,0 +1,108 @@
+//! A data-driven table of known DOS variants, replacing the hard-coded
+//! `match (vendor, version)` that used to live directly in
+//! [`crate::OperatingSystem::from_vendor_version`].
+//!
+//! Each entry's "soft" metadata (vendor, version, product, filenames, jump
+//! code, download URL, checksum) is parsed once from the TOML document
+//! embedded at `os_registry.toml`, using the plain `toml` crate -- not the
+//! `config` crate that `HwSpec::from_toml` and `Manifest::from_toml` load
+//! through, since `config` is built for merging a directory tree of
+//! files discovered at runtime, and there's only ever one of these to
+//! parse.
+//!
+//! The actual bootsector and system-file bytes can't come from the same
+//! document: `include_bytes!` requires a compile-time string literal path,
+//! so there's no way to resolve a path read out of a TOML file at runtime
+//! without a build script. Those blobs stay in a small static table in
+//! [`crate::OperatingSystem::static_bytes`], keyed by the same shortname
+//! the TOML entry carries. Adding a DOS release is therefore a data edit
+//! plus one match arm for its binary blobs, rather than the three
+//! lockstep edits `from_vendor_version` used to require.
+
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use serde::Deserialize;
+
+use crate::error::OsError;
+use crate::product::OsProduct;
+use crate::vendor::OsVendor;
+use crate::version::OsVersion;
+use crate::OsShortName;
+
+/// The "soft" metadata for one supported DOS variant: everything about it
+/// that doesn't require a compile-time `include_bytes!` path.
+#[derive(Debug, Deserialize)]
+pub struct OsDefinition {
+    pub vendor: String,
+    pub version: String,
+    pub product: String,
+    pub shortname: String,
+    pub iosys: String,
+    pub msdossys: String,
+    pub jumpcode: [u8; 3],
+    pub download_url: String,
+    /// The archive's published SHA-256 digest, if one has been recorded for this
+    /// variant yet. `None` (rather than an empty placeholder string) when it hasn't,
+    /// so [`crate::OperatingSystem::verify`] can tell "not checked yet" apart from
+    /// "checked and failed".
+    #[serde(default)]
+    pub checksum_sha256: Option<String>,
+    /// The archive's published CRC-32, under the same "recorded or not" convention
+    /// as `checksum_sha256`.
+    #[serde(default)]
+    pub checksum_crc32: Option<u32>,
+}
+
+impl OsDefinition {
+    pub fn vendor(&self) -> Result<OsVendor, OsError> {
+        OsVendor::from_str(&self.vendor)
+    }
+
+    pub fn version(&self) -> Result<OsVersion, OsError> {
+        OsVersion::from_str(&self.version)
+    }
+
+    pub fn product(&self) -> Result<OsProduct, OsError> {
+        OsProduct::from_str(&self.product)
+    }
+
+    pub fn osshortname(&self) -> Result<OsShortName, OsError> {
+        OsShortName::from_str(&self.shortname)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+struct OsRegistry {
+    #[serde(rename = "os")]
+    entries: Vec<OsDefinition>,
+}
+
+static REGISTRY: OnceLock<Vec<OsDefinition>> = OnceLock::new();
+
+fn registry() -> &'static [OsDefinition] {
+    REGISTRY
+        .get_or_init(|| {
+            let parsed: OsRegistry = toml::from_str(include_str!("os_registry.toml"))
+                .expect("os_registry.toml is embedded at build time and must parse");
+            parsed.entries
+        })
+        .as_slice()
+}
+
+/// Looks up the registry entry for a given vendor/version pair.
+pub fn find(vendor: OsVendor, version: OsVersion) -> Option<&'static OsDefinition> {
+    registry().iter().find(|def| {
+        match (def.vendor(), def.version()) {
+            (Ok(v), Ok(ver)) => v == vendor && ver == version,
+            _ => false,
+        }
+    })
+}
+
+/// Looks up the registry entry for a given shortname (e.g. `"IBMDOS100"`).
+pub fn find_by_shortname(shortname: &str) -> Option<&'static OsDefinition> {
+    registry()
+        .iter()
+        .find(|def| def.shortname.eq_ignore_ascii_case(shortname))
+}
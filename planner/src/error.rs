@@ -0,0 +1,20 @@
+use std::fmt;
+
+#[derive(Debug)]
+pub enum PlanError {
+    /// None of the operating systems this planner knows about are compatible with the
+    /// given hardware spec and manifest (CPU family/features, RAM, floppy drive type,
+    /// video, or a layer's DOS version/vendor/FPU/CPU requirements ruled out every
+    /// candidate).
+    NoCompatibleOS,
+}
+
+impl fmt::Display for PlanError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            PlanError::NoCompatibleOS => {
+                write!(f, "No supported operating system is compatible with this hardware spec and manifest")
+            }
+        }
+    }
+}
@@ -31,7 +31,8 @@ impl InstallationPlanner {
     /// Determine if a specific OS is compatible with given hardware
     fn is_compatible(hwspec: &HwSpec, os: &OsSupport) -> bool {
         hwspec.ram() >= os.min_ram_kib
-            && os.supported_cpu_families.contains(&hwspec.cpu().family())
+            && os.supported_cpu_families.contains(hwspec.cpu().family())
+            && hwspec.cpu().features().satisfies(&os.required_cpu_features)
             && hwspec
                 .floppy_type()
                 .as_ref()
@@ -59,7 +60,18 @@ impl InstallationPlanner {
                 let vendors = layer.1.dos_vendors();
                 let vendor_ok = vendors.is_empty() || vendors.contains(&os.shortname.vendor());
 
-                version_ok && vendor_ok
+                let fpu_ok = !layer.1.requires_fpu() || hwspec.cpu().has_fpu();
+
+                let cpu_vendors = layer.1.cpu_vendors();
+                let cpu_vendor_ok =
+                    cpu_vendors.is_empty() || cpu_vendors.contains(&hwspec.cpu().family().vendor());
+
+                let cpu_features_ok = hwspec
+                    .cpu()
+                    .features()
+                    .satisfies(&layer.1.required_cpu_features());
+
+                version_ok && vendor_ok && fpu_ok && cpu_vendor_ok && cpu_features_ok
             })
         });
 
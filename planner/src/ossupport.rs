@@ -1,5 +1,7 @@
-use common::{cpu::CpuFamily, storage::Floppy, video::VideoDevice};
-use operatingsystem::{vendor::OsVendor, version::OsVersion, OsShortName};
+use common::storage::Floppy;
+use operatingsystem::{error::OsError, vendor::OsVendor, version::OsVersion, OsShortName};
+use specs::types::cpu::{CpuFamily, CpuFeatures};
+use specs::types::video::VideoDevice;
 
 #[derive(Debug)]
 pub struct OsSupport {
@@ -8,10 +10,34 @@ pub struct OsSupport {
     pub version: OsVersion,
     pub min_ram_kib: u32,
     pub supported_cpu_families: &'static [CpuFamily],
+    /// Capabilities the CPU must have on top of belonging to `supported_cpu_families` —
+    /// e.g. "needs protected mode" — matched via [`CpuFeatures::satisfies`] so a new
+    /// family can be added to the list above without also auditing every capability
+    /// check by hand.
+    pub required_cpu_features: CpuFeatures,
     pub supported_floppies: &'static [Floppy],
     pub supported_video: &'static [VideoDevice],
 }
 
+impl OsSupport {
+    /// Picks the smallest of this OS's `supported_floppies` whose usable
+    /// capacity, after FAT/root-directory/reserved-sector overhead, can hold
+    /// `required_bytes`.
+    ///
+    /// # Errors
+    ///
+    /// Returns `OsError::UnsupportedDiskType` if none of `supported_floppies`
+    /// is large enough.
+    pub fn select_floppy(&self, required_bytes: u64) -> Result<Floppy, OsError> {
+        self.supported_floppies
+            .iter()
+            .filter(|floppy| floppy.usable_capacity_bytes() >= required_bytes)
+            .min_by_key(|floppy| floppy.usable_capacity_bytes())
+            .copied()
+            .ok_or(OsError::UnsupportedDiskType)
+    }
+}
+
 pub static SUPPORTED_OS: &[OsSupport] = &[
     OsSupport {
         shortname: OsShortName::IBMDOS100,
@@ -19,6 +45,7 @@ pub static SUPPORTED_OS: &[OsSupport] = &[
         version: OsVersion::new(1, 0),
         min_ram_kib: 64,
         supported_cpu_families: &[CpuFamily::I8088],
+        required_cpu_features: CpuFeatures::none(),
         supported_floppies: &[Floppy::F525_160],
         supported_video: &[VideoDevice::CGA, VideoDevice::MDA, VideoDevice::HGC],
     },
@@ -28,6 +55,7 @@ pub static SUPPORTED_OS: &[OsSupport] = &[
         version: OsVersion::new(1, 10),
         min_ram_kib: 64,
         supported_cpu_families: &[CpuFamily::I8088],
+        required_cpu_features: CpuFeatures::none(),
         supported_floppies: &[Floppy::F525_160, Floppy::F525_180],
         supported_video: &[VideoDevice::CGA, VideoDevice::MDA, VideoDevice::HGC],
     },
@@ -37,11 +65,8 @@ pub static SUPPORTED_OS: &[OsSupport] = &[
         version: OsVersion::new(2, 0),
         min_ram_kib: 128,
         supported_cpu_families: &[CpuFamily::I8088, CpuFamily::I8086],
-        supported_floppies: &[
-            Floppy::F525_160,
-            Floppy::F525_180,
-            Floppy::F525_360,
-        ],
+        required_cpu_features: CpuFeatures::none(),
+        supported_floppies: &[Floppy::F525_160, Floppy::F525_180, Floppy::F525_360],
         supported_video: &[VideoDevice::CGA, VideoDevice::MDA, VideoDevice::HGC],
     },
 ];
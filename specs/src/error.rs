@@ -1,22 +1,37 @@
 use std::fmt;
+use url::Url;
 
 #[derive(Debug)]
 pub enum SpecError {
+    AudioResourceConflict(String),
+    CacheError,
     ChecksumVerificationFailed,
     ClockTooLow,
     ClockTooHigh,
     ConfigBuild(config::ConfigError),
     Deserialize(config::ConfigError),
     DownloadError,
+    DownloadTooLarge {
+        limit: u64,
+        actual: u64,
+    },
     DuplicateAudioDevice,
     DuplicateVideoDevice,
     FileOpenError,
     FtpAuthenticationError,
     FtpConnectionError,
+    FtpTlsError,
     FtpTransferTypeError,
-    HttpRequestError,
+    HttpRequestError {
+        url: Url,
+        status: Option<u16>,
+        via_proxy: bool,
+    },
+    IncompatibleFpu,
     InvalidAudioDevice(String),
+    InvalidClockRate(String),
     InvalidCpu,
+    InvalidDiskSpaceString,
     InvalidFileSystemType,
     InvalidFloppyType,
     InvalidUrl,
@@ -27,33 +42,63 @@ pub enum SpecError {
     TooManyHeads,
     TooManySectors,
     TooMuchRamSpecified,
+    UnsafeArchiveEntryPath(String),
+    UnsupportedArchiveFormat,
+    UnsupportedAudioResource(String),
     UnsupportedUrlScheme,
     InvalidRamString,
     InvalidStorageClass,
     InvalidVideoDevice,
     TomlLoadError(String),
+    TomlSaveError(String),
     ValueMayNotBeZero,
     ZipFileCorrupt,
     ZipFileNotSet,
+    ZipPasswordIncorrect,
+    ZipPasswordRequired,
 }
 
 impl fmt::Display for SpecError {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
+            SpecError::AudioResourceConflict(msg) => write!(f, "Audio resource conflict: {}", msg),
+            SpecError::CacheError => write!(f, "Download cache error."),
             SpecError::ChecksumVerificationFailed => write!(f, "Checksum verification failed."),
             SpecError::ClockTooLow => write!(f, "Specified clock speed is too low"),
             SpecError::ClockTooHigh => write!(f, "Specified clock speed is too high"),
             SpecError::ConfigBuild(err) => write!(f, "Failed parsing configuration: {err}"),
             SpecError::Deserialize(err) => write!(f, "Failed deserializing configuration: {err}"),
             SpecError::DownloadError => write!(f, "Download error."),
+            SpecError::DownloadTooLarge { limit, actual } => write!(
+                f,
+                "Download size {} bytes exceeds the configured limit of {} bytes",
+                actual, limit
+            ),
             SpecError::DuplicateAudioDevice => write!(f, "Duplicate audio device specified"),
             SpecError::DuplicateVideoDevice => write!(f, "Duplicate video device specified"),
             SpecError::FileOpenError => write!(f, "Error opening file."),
             SpecError::FtpAuthenticationError => write!(f, "FTP authentication error."),
             SpecError::FtpConnectionError => write!(f, "FTP connection error."),
+            SpecError::FtpTlsError => write!(f, "FTP TLS error."),
             SpecError::FtpTransferTypeError => write!(f, "FTP transfer type error."),
-            SpecError::HttpRequestError => write!(f, "HTTP request error."),
+            SpecError::HttpRequestError {
+                url,
+                status,
+                via_proxy,
+            } => match (status, via_proxy) {
+                (Some(status), true) => {
+                    write!(f, "HTTP request error: proxy returned {status} for {url}")
+                }
+                (Some(status), false) => write!(f, "HTTP request error: {status} for {url}"),
+                (None, true) => write!(f, "HTTP request error: could not reach proxy for {url}"),
+                (None, false) => write!(f, "HTTP request error: could not reach {url}"),
+            },
+            SpecError::IncompatibleFpu => {
+                write!(f, "That floating-point unit is not available for this CPU family")
+            }
+            SpecError::InvalidClockRate(msg) => write!(f, "Invalid clock rate: {}", msg),
             SpecError::InvalidCpu => write!(f, "Invalid CPU model specified"),
+            SpecError::InvalidDiskSpaceString => write!(f, "Invalid disk space string format"),
             SpecError::InvalidFloppyType => write!(f, "Invalid floppy drive type specified"),
             SpecError::InvalidUrl => write!(f, "Invalid URL"),
             SpecError::InvalidFileSystemType => write!(f, "Invalid file system type."),
@@ -68,6 +113,15 @@ impl fmt::Display for SpecError {
             SpecError::TooMuchRamSpecified => {
                 write!(f, "Too much RAM specified (maximum is 4 GiB)")
             }
+            SpecError::UnsafeArchiveEntryPath(entry) => {
+                write!(f, "Archive entry \"{}\" escapes the staging directory.", entry)
+            }
+            SpecError::UnsupportedArchiveFormat => {
+                write!(f, "Unsupported archive format.")
+            }
+            SpecError::UnsupportedAudioResource(msg) => {
+                write!(f, "Unsupported audio device resource: {}", msg)
+            }
             SpecError::UnsupportedUrlScheme => write!(f, "Unsupported URL scheme"),
             SpecError::InvalidAudioDevice(msg) => {
                 write!(f, "Invalid audio device specified: {}.", msg)
@@ -76,9 +130,14 @@ impl fmt::Display for SpecError {
             SpecError::InvalidStorageClass => write!(f, "Invalid storage class specified"),
             SpecError::InvalidVideoDevice => write!(f, "Invalid video device specified"),
             SpecError::TomlLoadError(msg) => write!(f, "TOML load error: {}", msg),
+            SpecError::TomlSaveError(msg) => write!(f, "TOML save error: {}", msg),
             SpecError::ValueMayNotBeZero => write!(f, "Value may not be zero"),
             SpecError::ZipFileCorrupt => write!(f, "ZIP file corruption error."),
             SpecError::ZipFileNotSet => write!(f, "ZIP file not set."),
+            SpecError::ZipPasswordIncorrect => write!(f, "ZIP password is incorrect."),
+            SpecError::ZipPasswordRequired => {
+                write!(f, "ZIP file is password-protected but no password was set.")
+            }
         }
     }
 }
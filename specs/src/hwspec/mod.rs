@@ -6,29 +6,26 @@ use byte_unit::Byte;
 use config::Config;
 use config::File;
 use config::FileFormat;
-use cpu::Cpu;
-use serde::{Deserialize, Deserializer};
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
 use serde_with::serde_as;
 use serde_with::OneOrMany;
-use storage::Floppy;
-use storage::FloppyType;
-use video::VideoDevice;
 
 use crate::error::SpecError;
+use crate::types::audio::check_resource_conflicts;
 use crate::types::audio::AudioDevice;
 use crate::types::audio::AudioDeviceType;
+use crate::types::cpu::Cpu;
+use crate::types::storage::Floppy;
+use crate::types::video::VideoDevice;
 
-pub mod cpu;
-pub mod storage;
 mod tests;
-pub mod video;
 
 /// Represents the hardware configuration of an MS-DOS compatible PC system.
 #[serde_as]
-#[derive(Debug, Deserialize)]
+#[derive(Debug, Deserialize, Serialize)]
 pub struct HwSpec {
     cpu: Cpu,
-    #[serde(deserialize_with = "deserialize_ram")]
+    #[serde(serialize_with = "serialize_ram", deserialize_with = "deserialize_ram")]
     ram: u32,
     #[serde(default)]
     audio: Vec<AudioDevice>,
@@ -85,6 +82,19 @@ impl HwSpec {
             .collect()
     }
 
+    /// Checks every audio device currently configured on this system for a shared
+    /// IRQ line, DMA channel, or overlapping I/O port window, so a spec with two
+    /// cards fighting over the same resource is caught before it's used to build a
+    /// system that won't boot cleanly. See [`check_resource_conflicts`].
+    ///
+    /// # Errors
+    ///
+    /// Returns `SpecError::AudioResourceConflict` naming the two conflicting devices
+    /// and the resource they both claim.
+    pub fn validate_audio(&self) -> Result<(), SpecError> {
+        check_resource_conflicts(&self.audio)
+    }
+
     /// Adds a video device to the system.
     ///
     /// This method inserts a new [`VideoDevice`] into the list of video devices.
@@ -143,6 +153,29 @@ impl HwSpec {
             .try_deserialize::<HwSpec>()
             .map_err(SpecError::Deserialize)
     }
+
+    /// Writes this `HwSpec` to disk as a canonical TOML document.
+    ///
+    /// This is the inverse of [`HwSpec::from_toml`]: it lets a spec that was built up
+    /// programmatically (via [`HwSpec::set_cpu`], [`HwSpec::add_audio_device`],
+    /// [`HwSpec::set_ram`], ...) be saved back to a TOML file, so that it can later be
+    /// reloaded with `from_toml`.
+    ///
+    /// # Type Parameters
+    /// - `P`: A type that can be referenced as a `Path`, such as `&str` or `PathBuf`.
+    ///
+    /// # Arguments
+    /// - `path`: The path to write the TOML file to.
+    ///
+    /// # Errors
+    /// - Returns `SpecError::TomlSaveError` if the spec cannot be serialized to TOML, or if
+    ///   the resulting document cannot be written to `path`.
+    pub fn to_toml<P: AsRef<Path>>(&self, path: P) -> Result<(), SpecError> {
+        let document = toml::to_string_pretty(self)
+            .map_err(|err| SpecError::TomlSaveError(err.to_string()))?;
+        std::fs::write(path, document).map_err(|err| SpecError::TomlSaveError(err.to_string()))
+    }
+
     /// Sets the amount of system RAM.
     ///
     /// The `ram` parameter must be a human-readable string representing a memory size,
@@ -177,12 +210,8 @@ impl HwSpec {
         self.ram
     }
 
-    pub fn floppy_type(&self) -> Option<FloppyType> {
-        if let Some(disk) = &self.floppy {
-            Some(disk.floppy_type())
-        } else {
-            None
-        }
+    pub fn floppy_type(&self) -> Option<Floppy> {
+        self.floppy
     }
 }
 
@@ -199,6 +228,35 @@ where
         .map_err(|_| serde::de::Error::custom("RAM size too large for x86 system."))
 }
 
+/// Serializes a RAM size in bytes as a human-readable string, the inverse of
+/// [`deserialize_ram`].
+///
+/// Picks the largest binary unit (GiB/MiB/KiB) that divides `ram` evenly and renders a
+/// whole number in it, falling back to plain bytes otherwise. This is deliberately exact
+/// rather than going through `byte_unit`'s `get_appropriate_unit`, whose rounded decimal
+/// rendering isn't guaranteed to come back as the same byte count through
+/// [`deserialize_ram`] -- a requirement for `HwSpec::to_toml`/`from_toml` round-trips.
+pub fn serialize_ram<S>(ram: &u32, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    const KIB: u64 = 1024;
+    const MIB: u64 = KIB * 1024;
+    const GIB: u64 = MIB * 1024;
+
+    let ram = u64::from(*ram);
+    let rendered = if ram != 0 && ram % GIB == 0 {
+        format!("{} GiB", ram / GIB)
+    } else if ram != 0 && ram % MIB == 0 {
+        format!("{} MiB", ram / MIB)
+    } else if ram != 0 && ram % KIB == 0 {
+        format!("{} KiB", ram / KIB)
+    } else {
+        format!("{} B", ram)
+    };
+    serializer.serialize_str(&rendered)
+}
+
 impl fmt::Display for HwSpec {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         writeln!(f, "DOSContainer hardware specification")?;
@@ -0,0 +1,4 @@
+pub mod error;
+pub mod hwspec;
+pub mod manifest;
+pub mod types;
@@ -1,10 +1,15 @@
+use flate2::read::GzDecoder;
+#[cfg(feature = "ftps")]
+use ftp::openssl::ssl::{SslConnector, SslMethod, SslVerifyMode};
 use ftp::{FtpError, FtpStream};
 use log::info;
 use operatingsystem::vendor::OsVendor;
 use operatingsystem::version::OsVersion;
 use serde::Deserialize;
 use sha2::{Digest, Sha256};
+use std::fs::OpenOptions;
 use std::io::{BufReader, Seek, Write};
+use std::path::{Path, PathBuf};
 use std::{fmt, fs};
 use std::{fs::File, io::Read};
 use tempfile::{tempdir, NamedTempFile, TempDir};
@@ -13,8 +18,21 @@ use zip::ZipArchive;
 
 use crate::error::SpecError;
 use crate::types::audio::AudioDevice;
+use crate::types::cpu::{CpuFeatures, CpuVendor};
 use crate::types::video::VideoDevice;
 
+/// The archive formats a layer's downloaded file can be staged from, detected by
+/// [`Layer::detect_archive_format`].
+#[derive(Debug, PartialEq, Eq)]
+enum ArchiveFormat {
+    Zip,
+    Tar,
+    TarGz,
+    TarZstd,
+    Gz,
+    Zstd,
+}
+
 #[derive(Debug, Default, Deserialize)]
 pub struct Layer {
     comment: Option<String>,
@@ -25,6 +43,12 @@ pub struct Layer {
     #[serde(default)]
     dos_vendors: Vec<OsVendor>,
     #[serde(default)]
+    requires_fpu: bool,
+    #[serde(default)]
+    cpu_vendors: Vec<CpuVendor>,
+    #[serde(default)]
+    required_cpu_features: CpuFeatures,
+    #[serde(default)]
     graphics: Vec<VideoDevice>,
     #[serde(default)]
     audio: Vec<AudioDevice>,
@@ -34,12 +58,34 @@ pub struct Layer {
     autoexec_bat_lines: Vec<String>,
     #[serde(default)]
     config_sys_lines: Vec<String>,
+    #[serde(default)]
+    max_size: Option<u64>,
+    #[serde(default)]
+    resume: bool,
+    #[serde(skip)]
+    bypass_cache: bool,
+    #[serde(default)]
+    ftps_accept_invalid_certs: bool,
+    #[serde(default)]
+    zip_password: Option<String>,
+    #[serde(default)]
+    proxy: Option<Url>,
+    #[serde(default)]
+    max_redirects: Option<u32>,
     #[serde(skip_deserializing)]
     zipfile_path: Option<NamedTempFile>,
     #[serde(skip_deserializing)]
     staging_path: Option<TempDir>,
 }
 
+/// Name of the environment variable that overrides the default download cache
+/// directory used by [`Layer::download`].
+const CACHE_DIR_ENV_VAR: &str = "DOSK8S_CACHE_DIR";
+
+/// Default maximum number of redirects `download_http` will follow before giving up,
+/// used when [`Layer::set_max_redirects`] hasn't been called.
+const DEFAULT_MAX_REDIRECTS: u32 = 5;
+
 impl Layer {
     pub fn dos_vendors(&self) -> Vec<OsVendor> {
         self.dos_vendors.clone()
@@ -53,6 +99,27 @@ impl Layer {
         self.max_dos
     }
 
+    /// Whether this layer's content needs real or emulated floating-point math to run,
+    /// matched against [`crate::types::cpu::Cpu::has_fpu`] when picking a compatible OS
+    /// for a given [`crate::hwspec::HwSpec`].
+    pub fn requires_fpu(&self) -> bool {
+        self.requires_fpu
+    }
+
+    /// CPU manufacturers this layer is restricted to, mirroring [`Layer::dos_vendors`]:
+    /// an empty list means any [`crate::types::cpu::CpuFamily::vendor`] is acceptable.
+    pub fn cpu_vendors(&self) -> Vec<CpuVendor> {
+        self.cpu_vendors.clone()
+    }
+
+    /// Capabilities this layer needs the CPU to have, matched against
+    /// [`crate::types::cpu::Cpu::features`] via [`CpuFeatures::satisfies`] — e.g. a
+    /// layer that needs protected mode, without caring which [`crate::types::cpu::CpuFamily`]
+    /// provides it.
+    pub fn required_cpu_features(&self) -> CpuFeatures {
+        self.required_cpu_features
+    }
+
     pub fn set_url(&mut self, url: &str) -> Result<(), SpecError> {
         match Url::parse(url) {
             Ok(_) => {
@@ -67,6 +134,150 @@ impl Layer {
         &self.url
     }
 
+    /// Sets a cap, in bytes, on the size of the file `download` is allowed to fetch.
+    ///
+    /// Once set, an HTTP download fails with [`SpecError::DownloadTooLarge`] as soon as
+    /// either the server's `Content-Length` header or the actual downloaded byte count
+    /// exceeds this limit, whichever is detected first.
+    pub fn set_max_size(&mut self, max_size: u64) {
+        self.max_size = Some(max_size);
+    }
+
+    pub fn max_size(&self) -> Option<u64> {
+        self.max_size
+    }
+
+    /// Enables resuming an interrupted HTTP download rather than restarting it from zero.
+    ///
+    /// When set, `download_http` keeps its partially downloaded bytes in a file keyed off
+    /// this layer's URL, and on the next `download` call asks the server for a `Range`
+    /// continuation from where it left off. See `download_http` for the fallback behavior
+    /// when the server doesn't cooperate.
+    pub fn set_resume(&mut self, resume: bool) {
+        self.resume = resume;
+    }
+
+    pub fn resume(&self) -> bool {
+        self.resume
+    }
+
+    /// Forces `download()` to skip the content-addressable cache entirely, both for
+    /// lookups and for storing the freshly downloaded archive, regardless of
+    /// [`CACHE_DIR_ENV_VAR`]. Useful for one-off runs where a cached copy would be
+    /// unwelcome, e.g. re-testing a URL that's known to have changed server-side.
+    pub fn set_bypass_cache(&mut self, bypass: bool) {
+        self.bypass_cache = bypass;
+    }
+
+    pub fn bypass_cache(&self) -> bool {
+        self.bypass_cache
+    }
+
+    /// Accepts self-signed or otherwise unverifiable certificates on an `ftps` control and
+    /// data connection, for legacy mirrors that don't carry a CA-signed certificate.
+    ///
+    /// Has no effect on plain `ftp` URLs.
+    pub fn set_ftps_accept_invalid_certs(&mut self, accept: bool) {
+        self.ftps_accept_invalid_certs = accept;
+    }
+
+    pub fn ftps_accept_invalid_certs(&self) -> bool {
+        self.ftps_accept_invalid_certs
+    }
+
+    /// Sets the password used to decrypt this layer's archive, if it's a password-protected
+    /// ZIP. Both the traditional ZipCrypto scheme and AES (AE-1/AE-2) are supported, as the
+    /// `zip` crate picks the right decryption reader for each entry automatically.
+    pub fn set_zip_password(&mut self, password: &str) {
+        self.zip_password = Some(password.to_owned());
+    }
+
+    pub fn zip_password(&self) -> &Option<String> {
+        &self.zip_password
+    }
+
+    /// Forces `download_http` through this proxy, overriding `HTTP_PROXY`/`HTTPS_PROXY`/
+    /// `NO_PROXY`. See [`Self::resolve_proxy`] for how the two interact.
+    pub fn set_proxy(&mut self, proxy: &str) -> Result<(), SpecError> {
+        self.proxy = Some(Url::parse(proxy).map_err(|_| SpecError::InvalidUrl)?);
+        Ok(())
+    }
+
+    pub fn proxy(&self) -> &Option<Url> {
+        &self.proxy
+    }
+
+    /// Caps the number of redirects `download_http` will follow for this layer. Defaults to
+    /// [`DEFAULT_MAX_REDIRECTS`] when unset, which is enough for a typical mirror redirect
+    /// chain without risking a loop to an untrusted host.
+    pub fn set_max_redirects(&mut self, max_redirects: u32) {
+        self.max_redirects = Some(max_redirects);
+    }
+
+    pub fn max_redirects(&self) -> Option<u32> {
+        self.max_redirects
+    }
+
+    /// Resolves the directory downloaded archives are cached in: [`CACHE_DIR_ENV_VAR`]
+    /// if set, otherwise a `dosk8s-cache` directory under the system temp directory.
+    fn cache_dir() -> PathBuf {
+        if let Ok(dir) = std::env::var(CACHE_DIR_ENV_VAR) {
+            return PathBuf::from(dir);
+        }
+        std::env::temp_dir().join("dosk8s-cache")
+    }
+
+    /// Derives this layer's cache key from its declared checksum, falling back to a
+    /// hash of the URL when no checksum is declared, so that two layers pointing at
+    /// the same URL with different checksums never collide on the same cache entry.
+    fn cache_key(&self, url: &Url) -> String {
+        match &self.checksum {
+            Some(checksum) => checksum.to_lowercase(),
+            None => {
+                let mut hasher = Sha256::new();
+                hasher.update(url.as_str().as_bytes());
+                format!("{:x}", hasher.finalize())
+            }
+        }
+    }
+
+    /// Resolves the proxy `download_http` should route `url` through, if any.
+    ///
+    /// [`Self::proxy`] always wins when set. Otherwise this follows the convention general
+    /// download tooling uses for the `HTTP_PROXY`/`HTTPS_PROXY`/`NO_PROXY` environment
+    /// variables (both upper- and lowercase names are checked, lowercase taking precedence,
+    /// matching `curl`): `NO_PROXY` is a comma-separated list of hostnames (suffix-matched,
+    /// so `example.com` also matches `mirror.example.com`) that bypass proxying entirely,
+    /// and the scheme-appropriate `*_PROXY` variable supplies the proxy URL otherwise.
+    fn resolve_proxy(&self, url: &Url) -> Option<Url> {
+        if self.proxy.is_some() {
+            return self.proxy.clone();
+        }
+
+        let host = url.host_str()?;
+        let no_proxy = std::env::var("no_proxy")
+            .or_else(|_| std::env::var("NO_PROXY"))
+            .unwrap_or_default();
+        let bypassed = no_proxy
+            .split(',')
+            .map(str::trim)
+            .filter(|pattern| !pattern.is_empty())
+            .any(|pattern| host == pattern || host.ends_with(&format!(".{pattern}")));
+        if bypassed {
+            return None;
+        }
+
+        let var_name = if url.scheme() == "https" {
+            "https_proxy"
+        } else {
+            "http_proxy"
+        };
+        let proxy_url = std::env::var(var_name)
+            .or_else(|_| std::env::var(var_name.to_uppercase()))
+            .ok()?;
+        Url::parse(&proxy_url).ok()
+    }
+
     /// Downloads and stages the source file for this layer.
     ///
     /// This method is only valid for layers of type [`Software`]. It attempts to download
@@ -74,7 +285,15 @@ impl Layer {
     /// the URL scheme:
     ///
     /// - `http` and `https` are handled via [`download_http`].
-    /// - `ftp` is handled via [`download_ftp`].
+    /// - `ftp` and `ftps` are handled via [`download_ftp`]; `ftps` upgrades the control and
+    ///   data channels to TLS with `AUTH TLS` right after connecting.
+    ///
+    /// Before touching the network, the layer's cache key (see [`Self::cache_key`]) is
+    /// looked up in [`Self::cache_dir`], unless [`Self::set_bypass_cache`] disabled
+    /// caching. A hit is verified against `self.checksum` just like a fresh download and,
+    /// if it passes, skips straight to `stage()`. A miss downloads as usual and, once the
+    /// checksum check below passes, atomically moves the verified archive into the cache
+    /// for next time.
     ///
     /// On successful download, the local path to the downloaded file is stored in `self.zipfile_path`.
     ///
@@ -86,13 +305,31 @@ impl Layer {
     /// - No URL is present for the layer (`MissingUrl`).
     /// - The URL scheme is unsupported (`UnsupportedUrlScheme`).
     /// - The actual download operation fails, as reported by `download_http` or `download_ftp`.
+    /// - The cache directory or entry can't be read or written (`CacheError`).
     pub fn download(&mut self) -> Result<(), SpecError> {
-        let url = self.url.as_ref().ok_or(SpecError::MissingUrl)?;
+        let url = self.url.as_ref().ok_or(SpecError::MissingUrl)?.clone();
+
+        let cache_path = if self.bypass_cache {
+            None
+        } else {
+            Some(Self::cache_dir().join(self.cache_key(&url)))
+        };
 
-        let zipfile_path = match url.scheme() {
-            "http" | "https" => self.download_http()?,
-            "ftp" => self.download_ftp()?,
-            _ => return Err(SpecError::UnsupportedUrlScheme),
+        let (zipfile_path, from_cache) = match cache_path.as_deref().filter(|p| p.exists()) {
+            Some(cached) => {
+                let mut zipfile_path = NamedTempFile::new().map_err(|_| SpecError::TempDirError)?;
+                let mut source = File::open(cached).map_err(|_| SpecError::CacheError)?;
+                std::io::copy(&mut source, &mut zipfile_path).map_err(|_| SpecError::CacheError)?;
+                (zipfile_path, true)
+            }
+            None => {
+                let zipfile_path = match url.scheme() {
+                    "http" | "https" => self.download_http()?,
+                    "ftp" | "ftps" => self.download_ftp()?,
+                    _ => return Err(SpecError::UnsupportedUrlScheme),
+                };
+                (zipfile_path, false)
+            }
         };
 
         if let Some(checksum) = &self.checksum {
@@ -121,32 +358,96 @@ impl Layer {
             }
         }
 
+        if !from_cache {
+            if let Some(cache_path) = &cache_path {
+                if let Some(parent) = cache_path.parent() {
+                    fs::create_dir_all(parent).map_err(|_| SpecError::CacheError)?;
+                }
+                let staging_name = format!("{}.tmp", cache_path.display());
+                fs::copy(zipfile_path.path(), &staging_name).map_err(|_| SpecError::CacheError)?;
+                fs::rename(&staging_name, cache_path).map_err(|_| SpecError::CacheError)?;
+            }
+        }
+
         self.zipfile_path = Some(zipfile_path);
         self.stage()?;
         Ok(())
     }
 
+    /// Opens a ZIP entry by index, transparently decrypting it with `password` if the entry
+    /// is flagged encrypted. Both the traditional ZipCrypto scheme and AES (AE-1/AE-2) are
+    /// handled by the `zip` crate's own decrypting reader, so the caller doesn't need to know
+    /// which one a given entry uses.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpecError::ZipPasswordRequired`] if the entry is encrypted and `password` is
+    /// `None`, or [`SpecError::ZipPasswordIncorrect`] if decryption fails with the password
+    /// given.
+    fn open_zip_entry<'a, R: Read + Seek>(
+        archive: &'a mut ZipArchive<R>,
+        index: usize,
+        password: &Option<String>,
+    ) -> Result<zip::read::ZipFile<'a>, SpecError> {
+        let encrypted = archive
+            .by_index_raw(index)
+            .map_err(|_| SpecError::ZipFileCorrupt)?
+            .encrypted();
+
+        if !encrypted {
+            return archive
+                .by_index(index)
+                .map_err(|_| SpecError::ZipFileCorrupt);
+        }
+
+        let password = password.as_ref().ok_or(SpecError::ZipPasswordRequired)?;
+        archive
+            .by_index_decrypt(index, password.as_bytes())
+            .map_err(|_| SpecError::ZipFileCorrupt)?
+            .map_err(|_| SpecError::ZipPasswordIncorrect)
+    }
+
     fn stage(&mut self) -> Result<(), SpecError> {
+        let format = self.detect_archive_format()?;
         let zipfile = self.zipfile_path.as_ref().ok_or(SpecError::TempDirError)?;
         let staging_path = tempdir().map_err(|_| SpecError::TempDirError)?;
-        let mut archive = ZipArchive::new(zipfile).map_err(|_| SpecError::ZipFileCorrupt)?;
-        let zipfile_logdisplay = zipfile.path();
+        let zipfile_logdisplay = zipfile.path().to_path_buf();
         info!(target: "dosk8s_events", "Start extracting archive {zipfile_logdisplay:?}.");
 
-        for i in 0..archive.len() {
-            let mut file = archive.by_index(i).map_err(|_| SpecError::ZipFileCorrupt)?;
-            let target = staging_path.path().join(file.name());
-
-            if file.is_dir() {
-                fs::create_dir_all(&target).map_err(|_| SpecError::FileOpenError)?;
-            } else {
-                if let Some(parent) = target.parent() {
-                    fs::create_dir_all(parent).map_err(|_| SpecError::FileOpenError)?;
-                }
-
-                let mut outfile =
-                    fs::File::create(&target).map_err(|_| SpecError::FileOpenError)?;
-                std::io::copy(&mut file, &mut outfile).map_err(|_| SpecError::FileOpenError)?;
+        match format {
+            ArchiveFormat::Zip => {
+                self.stage_zip(zipfile, staging_path.path())?;
+            }
+            ArchiveFormat::Tar => {
+                let file = File::open(zipfile).map_err(|_| SpecError::FileOpenError)?;
+                Self::stage_tar(file, staging_path.path())?;
+            }
+            ArchiveFormat::TarGz => {
+                let file = File::open(zipfile).map_err(|_| SpecError::FileOpenError)?;
+                Self::stage_tar(GzDecoder::new(file), staging_path.path())?;
+            }
+            ArchiveFormat::TarZstd => {
+                let file = File::open(zipfile).map_err(|_| SpecError::FileOpenError)?;
+                let decoder = zstd::stream::read::Decoder::new(file)
+                    .map_err(|_| SpecError::UnsupportedArchiveFormat)?;
+                Self::stage_tar(decoder, staging_path.path())?;
+            }
+            ArchiveFormat::Gz => {
+                let file = File::open(zipfile).map_err(|_| SpecError::FileOpenError)?;
+                let file_name = Self::decompressed_output_name(self.url.as_ref(), ".gz");
+                Self::stage_entry(
+                    staging_path.path(),
+                    &file_name,
+                    false,
+                    &mut GzDecoder::new(file),
+                )?;
+            }
+            ArchiveFormat::Zstd => {
+                let file = File::open(zipfile).map_err(|_| SpecError::FileOpenError)?;
+                let file_name = Self::decompressed_output_name(self.url.as_ref(), ".zst");
+                let mut decoder = zstd::stream::read::Decoder::new(file)
+                    .map_err(|_| SpecError::UnsupportedArchiveFormat)?;
+                Self::stage_entry(staging_path.path(), &file_name, false, &mut decoder)?;
             }
         }
 
@@ -156,11 +457,154 @@ impl Layer {
         Ok(())
     }
 
+    /// Figures out which archive format a downloaded layer used, first from the URL's file
+    /// extension and, failing that, by sniffing the file's magic bytes. Raw `.tar` archives
+    /// have no magic bytes of their own, so they can only be recognized by extension; a file
+    /// that matches neither a known extension nor a known magic is reported as
+    /// [`SpecError::UnsupportedArchiveFormat`].
+    fn detect_archive_format(&self) -> Result<ArchiveFormat, SpecError> {
+        if let Some(url) = &self.url {
+            let path = url.path().to_ascii_lowercase();
+            if path.ends_with(".tar.gz") || path.ends_with(".tgz") {
+                return Ok(ArchiveFormat::TarGz);
+            }
+            if path.ends_with(".tar.zst") || path.ends_with(".tzst") {
+                return Ok(ArchiveFormat::TarZstd);
+            }
+            if path.ends_with(".tar") {
+                return Ok(ArchiveFormat::Tar);
+            }
+            if path.ends_with(".gz") {
+                return Ok(ArchiveFormat::Gz);
+            }
+            if path.ends_with(".zst") {
+                return Ok(ArchiveFormat::Zstd);
+            }
+            if path.ends_with(".zip") {
+                return Ok(ArchiveFormat::Zip);
+            }
+        }
+
+        let zipfile = self.zipfile_path.as_ref().ok_or(SpecError::TempDirError)?;
+        let mut header = [0u8; 4];
+        let mut file = File::open(zipfile).map_err(|_| SpecError::FileOpenError)?;
+        let read = file.read(&mut header).unwrap_or(0);
+        match &header[..read] {
+            [0x50, 0x4B, ..] => Ok(ArchiveFormat::Zip),
+            [0x1F, 0x8B, ..] => Ok(ArchiveFormat::TarGz),
+            [0x28, 0xB5, 0x2F, 0xFD] => Ok(ArchiveFormat::TarZstd),
+            _ => Err(SpecError::UnsupportedArchiveFormat),
+        }
+    }
+
+    /// Extracts every entry of a ZIP archive into `staging_root`, transparently decrypting
+    /// password-protected entries via [`Self::open_zip_entry`].
+    fn stage_zip(&self, zipfile: &NamedTempFile, staging_root: &Path) -> Result<(), SpecError> {
+        let mut archive = ZipArchive::new(zipfile).map_err(|_| SpecError::ZipFileCorrupt)?;
+        for i in 0..archive.len() {
+            let mut file = Self::open_zip_entry(&mut archive, i, &self.zip_password)?;
+            let is_dir = file.is_dir();
+            let name = file.name().to_owned();
+            Self::stage_entry(staging_root, &name, is_dir, &mut file)?;
+        }
+        Ok(())
+    }
+
+    /// Extracts every entry of a tar archive (optionally itself wrapped in a gzip or zstd
+    /// decoder, for `.tar.gz`/`.tgz`/`.tar.zst`/`.tzst`) into `staging_root`.
+    fn stage_tar<R: Read>(reader: R, staging_root: &Path) -> Result<(), SpecError> {
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries().map_err(|_| SpecError::ZipFileCorrupt)? {
+            let mut entry = entry.map_err(|_| SpecError::ZipFileCorrupt)?;
+            let is_dir = entry.header().entry_type().is_dir();
+            let name = entry
+                .path()
+                .map_err(|_| SpecError::ZipFileCorrupt)?
+                .to_string_lossy()
+                .into_owned();
+            Self::stage_entry(staging_root, &name, is_dir, &mut entry)?;
+        }
+        Ok(())
+    }
+
+    /// Derives the output file name for a raw (non-tar) compressed layer by stripping
+    /// `suffix` off the URL's file name, falling back to a generic name if the URL doesn't
+    /// end in one.
+    fn decompressed_output_name(url: Option<&Url>, suffix: &str) -> String {
+        url.and_then(|url| url.path().rsplit('/').next())
+            .and_then(|name| name.strip_suffix(suffix))
+            .filter(|name| !name.is_empty())
+            .unwrap_or("archive")
+            .to_owned()
+    }
+
+    /// Resolves an archive entry's name against `staging_root`, rejecting any entry that
+    /// would land outside it (an absolute path, or a path carrying a `..` component) so a
+    /// malicious archive can't write outside the staging directory ("zip slip").
+    fn resolve_entry_path(staging_root: &Path, entry_name: &str) -> Result<PathBuf, SpecError> {
+        use std::path::Component;
+
+        if Path::new(entry_name)
+            .components()
+            .any(|component| !matches!(component, Component::Normal(_) | Component::CurDir))
+        {
+            return Err(SpecError::UnsafeArchiveEntryPath(entry_name.to_owned()));
+        }
+
+        Ok(staging_root.join(entry_name))
+    }
+
+    /// Writes a single archive entry (file or directory) under `staging_root`, creating any
+    /// parent directories as needed. Shared by the ZIP, tar, gz, and zstd staging paths.
+    fn stage_entry<R: Read>(
+        staging_root: &Path,
+        entry_name: &str,
+        is_dir: bool,
+        reader: &mut R,
+    ) -> Result<(), SpecError> {
+        let target = Self::resolve_entry_path(staging_root, entry_name)?;
+
+        if is_dir {
+            fs::create_dir_all(&target).map_err(|_| SpecError::FileOpenError)?;
+        } else {
+            if let Some(parent) = target.parent() {
+                fs::create_dir_all(parent).map_err(|_| SpecError::FileOpenError)?;
+            }
+
+            let mut outfile = fs::File::create(&target).map_err(|_| SpecError::FileOpenError)?;
+            std::io::copy(reader, &mut outfile).map_err(|_| SpecError::FileOpenError)?;
+        }
+
+        Ok(())
+    }
+
+    /// Path of the partial-download file kept in the system's temp directory while a
+    /// resumable download for `url` is in progress, keyed off a hash of the URL so that
+    /// repeated `download` calls for the same layer land on the same file.
+    fn partial_download_path(url: &Url) -> PathBuf {
+        let mut hasher = Sha256::new();
+        hasher.update(url.as_str().as_bytes());
+        let digest = format!("{:x}", hasher.finalize());
+        std::env::temp_dir().join(format!("dosk8s-layer-download-{digest}.partial"))
+    }
+
     /// Downloads the file from the Layer's HTTP(S) URL into a temporary directory.
     ///
     /// This method attempts to download the file specified by `self.url` over HTTP or HTTPS.
-    /// The downloaded file is saved in a newly created temporary directory, and its path
-    /// is returned on success.
+    /// The response body is copied to the temporary file in fixed-size chunks rather than
+    /// buffered into memory whole, so memory use stays flat regardless of archive size. If
+    /// `self.max_size` is set, the server's `Content-Length` header is checked up front, and
+    /// the running byte count is checked after every chunk, so an oversized download is
+    /// rejected as early as possible either way.
+    ///
+    /// When `self.resume` is set, a partial download is kept at a path keyed off `self.url`
+    /// (see [`Self::partial_download_path`]). If that file already holds bytes from a
+    /// previous attempt, the request is sent with a `Range: bytes=<len>-` header asking the
+    /// server to continue from there. The continuation is only trusted if the server answers
+    /// `206 Partial Content` and advertises `Accept-Ranges: bytes`; any other response (a
+    /// plain `200`, or a server that ignores the range) causes the partial file to be
+    /// discarded and the download to restart from zero. Once the body is fully received, the
+    /// partial file is copied into the anonymous temp file this method returns and removed.
     ///
     /// # Errors
     ///
@@ -172,6 +616,7 @@ impl Layer {
     /// - The HTTP request fails to send (`HttpRequestError`)
     /// - The HTTP response indicates a non-success status (`HttpRequestError`)
     /// - The response body cannot be read (`HttpRequestError`)
+    /// - The advertised or actual download size exceeds `self.max_size` (`DownloadTooLarge`)
     /// - The file cannot be created locally (`DownloadError`)
     /// - The response body cannot be written to disk (`DownloadError`)
     ///
@@ -180,24 +625,138 @@ impl Layer {
     /// On success, returns the full path to the downloaded file within the temporary directory.
     #[allow(clippy::manual_next_back)]
     fn download_http(&mut self) -> Result<NamedTempFile, SpecError> {
-        let url = self.url.as_ref().ok_or(SpecError::InvalidUrl)?;
+        let url = self.url.as_ref().ok_or(SpecError::InvalidUrl)?.clone();
         info!(target: "dosk8s_events", "Starting HTTP(S) download for {url}.");
 
-        let response = attohttpc::get(url)
-            .send()
-            .map_err(|_| SpecError::HttpRequestError)?;
+        let partial_path = self.resume.then(|| Self::partial_download_path(&url));
+        let resume_from = partial_path
+            .as_ref()
+            .map(|path| fs::metadata(path).map(|meta| meta.len()).unwrap_or(0))
+            .unwrap_or(0);
+
+        let proxy = self.resolve_proxy(&url);
+        let via_proxy = proxy.is_some();
+
+        let mut request = attohttpc::get(&url)
+            .max_redirections(self.max_redirects.unwrap_or(DEFAULT_MAX_REDIRECTS));
+        if let Some(proxy) = &proxy {
+            request = request.proxy(proxy.clone());
+        }
+        if resume_from > 0 {
+            request = request.header("Range", format!("bytes={resume_from}-"));
+        }
+
+        let mut response = request.send().map_err(|_| SpecError::HttpRequestError {
+            url: url.clone(),
+            status: None,
+            via_proxy,
+        })?;
 
         if !response.is_success() {
-            return Err(SpecError::HttpRequestError);
+            return Err(SpecError::HttpRequestError {
+                url: url.clone(),
+                status: Some(response.status().as_u16()),
+                via_proxy,
+            });
         }
 
-        let content = response.bytes().map_err(|_| SpecError::HttpRequestError)?;
+        let accepts_ranges = response
+            .headers()
+            .get(attohttpc::header::ACCEPT_RANGES)
+            .and_then(|value| value.to_str().ok())
+            == Some("bytes");
+        let is_partial_reply = response.status() == attohttpc::StatusCode::PARTIAL_CONTENT;
+        let appending = resume_from > 0 && is_partial_reply && accepts_ranges;
 
-        let mut tempfile = NamedTempFile::new().map_err(|_| SpecError::TempDirError)?;
+        if let Some(limit) = self.max_size {
+            if let Some(content_length) = response
+                .headers()
+                .get(attohttpc::header::CONTENT_LENGTH)
+                .and_then(|value| value.to_str().ok())
+                .and_then(|value| value.parse::<u64>().ok())
+            {
+                let actual = if appending {
+                    resume_from + content_length
+                } else {
+                    content_length
+                };
+                if actual > limit {
+                    return Err(SpecError::DownloadTooLarge { limit, actual });
+                }
+            }
+        }
 
-        tempfile
-            .write_all(&content)
-            .map_err(|_| SpecError::DownloadError)?;
+        let mut downloaded: u64 = if appending { resume_from } else { 0 };
+        let mut buffer = [0u8; 8192];
+
+        if let Some(path) = &partial_path {
+            let mut file = OpenOptions::new()
+                .create(true)
+                .append(appending)
+                .write(true)
+                .truncate(!appending)
+                .open(path)
+                .map_err(|_| SpecError::TempDirError)?;
+
+            loop {
+                let bytes_read =
+                    response
+                        .read(&mut buffer)
+                        .map_err(|_| SpecError::HttpRequestError {
+                            url: url.clone(),
+                            status: None,
+                            via_proxy,
+                        })?;
+                if bytes_read == 0 {
+                    break;
+                }
+                downloaded += bytes_read as u64;
+                if let Some(limit) = self.max_size {
+                    if downloaded > limit {
+                        return Err(SpecError::DownloadTooLarge {
+                            limit,
+                            actual: downloaded,
+                        });
+                    }
+                }
+                file.write_all(&buffer[..bytes_read])
+                    .map_err(|_| SpecError::DownloadError)?;
+            }
+
+            let mut finished = File::open(path).map_err(|_| SpecError::DownloadError)?;
+            let mut tempfile = NamedTempFile::new().map_err(|_| SpecError::TempDirError)?;
+            std::io::copy(&mut finished, &mut tempfile).map_err(|_| SpecError::DownloadError)?;
+            let _ = fs::remove_file(path);
+            info!(target: "dosk8s_events", "Finished HTTP(S) download for {url}.");
+            return Ok(tempfile);
+        }
+
+        let mut tempfile = NamedTempFile::new().map_err(|_| SpecError::TempDirError)?;
+        loop {
+            let bytes_read =
+                response
+                    .read(&mut buffer)
+                    .map_err(|_| SpecError::HttpRequestError {
+                        url: url.clone(),
+                        status: None,
+                        via_proxy,
+                    })?;
+            if bytes_read == 0 {
+                break;
+            }
+            downloaded += bytes_read as u64;
+            if let Some(limit) = self.max_size {
+                if downloaded > limit {
+                    return Err(SpecError::DownloadTooLarge {
+                        limit,
+                        actual: downloaded,
+                    });
+                }
+            }
+            tempfile
+                .write_all(&buffer[..bytes_read])
+                .map_err(|_| SpecError::DownloadError)?;
+        }
         info!(target: "dosk8s_events", "Finished HTTP(S) download for {url}.");
         Ok(tempfile)
     }
@@ -208,6 +767,13 @@ impl Layer {
     /// credentials provided in the URL or anonymous login if none are present, and retrieves the file
     /// located at the URL's path. The file is saved in a newly created temporary directory.
     ///
+    /// If `self.url`'s scheme is `ftps`, the control channel (and every data channel opened
+    /// afterwards) is upgraded to TLS via `AUTH TLS` before login, mirroring the `enable_secure()`
+    /// switch other FTP clients expose. This requires the crate's `ftps` feature; without it, an
+    /// `ftps` URL fails closed with [`SpecError::FtpTlsError`] rather than falling back to plaintext.
+    /// [`Self::set_ftps_accept_invalid_certs`] relaxes certificate validation for legacy mirrors
+    /// with a self-signed or otherwise unverifiable certificate.
+    ///
     /// The FTP transfer is performed in binary mode to preserve file integrity.
     ///
     /// # Returns
@@ -221,6 +787,7 @@ impl Layer {
     /// - The URL is missing, invalid, or lacks necessary components such as a host or file name.
     /// - The temporary directory could not be created.
     /// - The FTP connection could not be established.
+    /// - The `ftps` TLS handshake fails, or the crate was built without the `ftps` feature (`FtpTlsError`).
     /// - Authentication with the FTP server failed.
     /// - The transfer type could not be set to binary mode.
     /// - The file could not be retrieved or written locally.
@@ -247,6 +814,7 @@ impl Layer {
         info!(target: "dosk8s_events", "Start FTP download from {url}.");
         let hostname = url.host_str().ok_or(SpecError::InvalidUrl)?;
         let port = url.port_or_known_default().unwrap_or(21);
+        let secure = url.scheme() == "ftps";
 
         let path = url.path();
         if path.is_empty() {
@@ -258,6 +826,25 @@ impl Layer {
         let mut ftp =
             FtpStream::connect((hostname, port)).map_err(|_| SpecError::FtpConnectionError)?;
 
+        if secure {
+            #[cfg(feature = "ftps")]
+            {
+                let mut builder =
+                    SslConnector::builder(SslMethod::tls()).map_err(|_| SpecError::FtpTlsError)?;
+                if self.ftps_accept_invalid_certs {
+                    builder.set_verify(SslVerifyMode::NONE);
+                }
+                let tls = builder.build();
+                ftp = ftp
+                    .into_secure(tls, hostname)
+                    .map_err(|_| SpecError::FtpTlsError)?;
+            }
+            #[cfg(not(feature = "ftps"))]
+            {
+                return Err(SpecError::FtpTlsError);
+            }
+        }
+
         let username = if url.username().is_empty() {
             "anonymous"
         } else {
@@ -295,29 +882,62 @@ impl Layer {
         Ok(tempfile)
     }
 
-    /// Validate the Layer's own zipfile
-    pub fn validate_zip_file(&self) -> Result<(), SpecError> {
-        if let Some(file) = &self.zipfile_path {
-            info!(target: "dosk8s_events", "Start validating ZIP file {file:?}");
-            let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
-            let reader = BufReader::new(zipfile);
-            self.validate_zip_stream(reader)?;
-        } else {
-            info!(target: "dosk8s_events", "ZIP file validation failed.");
-            return Err(SpecError::ZipFileNotSet);
+    /// Validates the Layer's downloaded archive, dispatching on its format (see
+    /// [`Self::detect_archive_format`]).
+    ///
+    /// ZIP archives get a full per-entry CRC32 check against each entry's recorded CRC32, run via
+    /// [`Self::validate_zip_stream`]. Formats the `zip` crate doesn't cover (tar, gzip, zstd,
+    /// and their tar combinations) have no equivalent per-entry checksum to compare against,
+    /// so every entry is instead read through to the end, which is enough to surface a
+    /// truncated download or a corrupt compression stream.
+    pub fn validate_archive(&self) -> Result<(), SpecError> {
+        let file = self.zipfile_path.as_ref().ok_or(SpecError::ZipFileNotSet)?;
+        let format = self.detect_archive_format()?;
+        info!(target: "dosk8s_events", "Start validating archive {file:?}");
+
+        match format {
+            ArchiveFormat::Zip => {
+                let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
+                self.validate_zip_stream(BufReader::new(zipfile))?;
+            }
+            ArchiveFormat::Tar => {
+                let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
+                Self::validate_tar(zipfile)?;
+            }
+            ArchiveFormat::TarGz => {
+                let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
+                Self::validate_tar(GzDecoder::new(zipfile))?;
+            }
+            ArchiveFormat::TarZstd => {
+                let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
+                let decoder = zstd::stream::read::Decoder::new(zipfile)
+                    .map_err(|_| SpecError::UnsupportedArchiveFormat)?;
+                Self::validate_tar(decoder)?;
+            }
+            ArchiveFormat::Gz => {
+                let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
+                Self::validate_decompresses(GzDecoder::new(zipfile))?;
+            }
+            ArchiveFormat::Zstd => {
+                let zipfile = File::open(file).map_err(|_| SpecError::FileOpenError)?;
+                let decoder = zstd::stream::read::Decoder::new(zipfile)
+                    .map_err(|_| SpecError::UnsupportedArchiveFormat)?;
+                Self::validate_decompresses(decoder)?;
+            }
         }
-        info!(target: "dosk8s_events", "Finish validating ZIP file.");
+
+        info!(target: "dosk8s_events", "Finish validating archive.");
         Ok(())
     }
 
-    /// Generalized implementation so that validation is properly testable
+    /// Generalized implementation so that ZIP validation is properly testable
     fn validate_zip_stream<R: Read + Seek>(&self, reader: R) -> Result<(), SpecError> {
         // ..when they have an actual zipfile set.
         let mut archive = ZipArchive::new(reader).map_err(|_| SpecError::FileOpenError)?;
 
         // Loop over all files in the archive
         for i in 0..archive.len() {
-            let mut file = archive.by_index(i).map_err(|_| SpecError::ZipFileCorrupt)?;
+            let mut file = Self::open_zip_entry(&mut archive, i, &self.zip_password)?;
 
             // We can't CRC-check a directory
             if file.is_dir() {
@@ -326,6 +946,8 @@ impl Layer {
 
             let expected_crc = file.crc32();
             let mut buffer = Vec::new();
+            // Encrypted entries are decrypted by `open_zip_entry` above, so this always
+            // reads the plaintext and the CRC check below runs on decrypted bytes.
             file.read_to_end(&mut buffer)
                 .map_err(|_| SpecError::ZipFileCorrupt)?;
 
@@ -337,6 +959,27 @@ impl Layer {
         }
         Ok(())
     }
+
+    /// Reads every entry of a tar archive through to the end without a CRC check, since tar
+    /// carries no per-entry checksum of its own; this still catches a truncated archive or,
+    /// when wrapped in a gzip/zstd decoder, a corrupt compression stream.
+    fn validate_tar<R: Read>(reader: R) -> Result<(), SpecError> {
+        let mut archive = tar::Archive::new(reader);
+        for entry in archive.entries().map_err(|_| SpecError::ZipFileCorrupt)? {
+            let mut entry = entry.map_err(|_| SpecError::ZipFileCorrupt)?;
+            std::io::copy(&mut entry, &mut std::io::sink())
+                .map_err(|_| SpecError::ZipFileCorrupt)?;
+        }
+        Ok(())
+    }
+
+    /// Reads a raw (non-tar) compressed stream through to the end, which is enough to catch a
+    /// truncated file or a corrupt compression stream since neither gzip nor zstd expose a
+    /// separate per-entry checksum for a single-file payload like this.
+    fn validate_decompresses<R: Read>(mut reader: R) -> Result<(), SpecError> {
+        std::io::copy(&mut reader, &mut std::io::sink()).map_err(|_| SpecError::ZipFileCorrupt)?;
+        Ok(())
+    }
 }
 
 impl fmt::Display for Layer {
@@ -383,3 +1026,27 @@ impl fmt::Display for Layer {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_entry_path_rejects_parent_dir_components() {
+        let staging_root = Path::new("/tmp/staging");
+        assert!(Layer::resolve_entry_path(staging_root, "../../etc/passwd").is_err());
+    }
+
+    #[test]
+    fn resolve_entry_path_rejects_absolute_paths() {
+        let staging_root = Path::new("/tmp/staging");
+        assert!(Layer::resolve_entry_path(staging_root, "/etc/passwd").is_err());
+    }
+
+    #[test]
+    fn resolve_entry_path_accepts_a_normal_relative_entry() {
+        let staging_root = Path::new("/tmp/staging");
+        let target = Layer::resolve_entry_path(staging_root, "games/game.exe").unwrap();
+        assert_eq!(target, staging_root.join("games/game.exe"));
+    }
+}
@@ -1,10 +1,30 @@
+use byte_unit::Byte;
 use serde::Deserialize;
 
+use crate::error::SpecError;
+
 #[derive(Debug, Deserialize)]
 pub struct Metadata {
     pub(crate) application: String,
     pub(crate) developer: String,
     pub(crate) diskspace: String,
     pub(crate) genres: Vec<String>,
-    pub(crate) year: String
+    pub(crate) year: String,
+}
+
+impl Metadata {
+    /// Parses `diskspace` (e.g. `"360K"`, `"1.2M"`, or a raw byte count) into
+    /// the number of bytes it specifies.
+    ///
+    /// # Errors
+    ///
+    /// Returns [`SpecError::InvalidDiskSpaceString`] if the string cannot be parsed.
+    pub fn diskspace_bytes(&self) -> Result<u64, SpecError> {
+        const IGNORE_CASE: bool = true;
+        let amount = Byte::parse_str(&self.diskspace, IGNORE_CASE)
+            .map_err(|_| SpecError::InvalidDiskSpaceString)?;
+        amount
+            .try_into()
+            .map_err(|_| SpecError::InvalidDiskSpaceString)
+    }
 }
@@ -1,5 +1,5 @@
 use config::{Config, File, FileFormat};
-use metadata::Metadata;
+pub use metadata::Metadata;
 
 use crate::error::SpecError;
 use layer::Layer;
@@ -37,6 +37,10 @@ impl Manifest {
         &self.layers
     }
 
+    pub fn metadata(&self) -> Option<&Metadata> {
+        self.metadata.as_ref()
+    }
+
     pub fn layer(&self, name: &str) -> Option<&Layer> {
         self.layers.get(name)
     }
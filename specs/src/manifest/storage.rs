@@ -7,12 +7,16 @@ use crate::error::SpecError;
 #[derive(Debug)]
 pub enum FileSystemType {
     Fat12,
+    Fat16,
+    Fat32,
 }
 
 impl fmt::Display for FileSystemType {
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
         match self {
             FileSystemType::Fat12 => Ok(write!(f, "FAT12")?),
+            FileSystemType::Fat16 => Ok(write!(f, "FAT16")?),
+            FileSystemType::Fat32 => Ok(write!(f, "FAT32")?),
         }
     }
 }
@@ -34,6 +38,8 @@ impl FromStr for FileSystemType {
     fn from_str(input: &str) -> Result<Self, SpecError> {
         match input.to_lowercase().as_str() {
             "fat12" | "fat 12" => Ok(FileSystemType::Fat12),
+            "fat16" | "fat 16" => Ok(FileSystemType::Fat16),
+            "fat32" | "fat 32" => Ok(FileSystemType::Fat32),
             _ => Err(SpecError::InvalidFileSystemType),
         }
     }
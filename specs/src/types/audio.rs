@@ -0,0 +1,1354 @@
+use serde::de::{self, Deserializer};
+use serde::ser::SerializeStruct;
+use serde::{Deserialize, Serialize};
+use std::sync::{Mutex, OnceLock};
+use std::{fmt, str::FromStr};
+
+use crate::error::SpecError;
+
+/// The factory-default hardware resources a card ships jumpered (or auto-configures)
+/// to, as an `AudioDeviceProfile` reports them.
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub struct ResourceDefaults {
+    pub io: Option<u16>,
+    pub dma_low: Option<u8>,
+    pub dma_high: Option<u8>,
+    pub irq_low: Option<u8>,
+    pub irq_high: Option<u8>,
+}
+
+/// The hardware resources a card can legally be configured to, as an
+/// `AudioDeviceProfile` reports them.
+///
+/// An empty `Vec` means the profile doesn't constrain that resource, not that no
+/// value is legal; see `AudioDevice::validate`.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ResourceLimits {
+    pub io: Vec<u16>,
+    pub dma_low: Vec<u8>,
+    pub dma_high: Vec<u8>,
+    pub irq_low: Vec<u8>,
+    pub irq_high: Vec<u8>,
+}
+
+/// A card's identity and hardware-resource characteristics, as consulted by
+/// `AudioDeviceType::from_str`/`Display` and `AudioDevice::new`/`validate`.
+///
+/// Implement this for a card that isn't one of the crate's built-in variants and
+/// hand it to `register_profile` to make it addressable by name the same way a
+/// built-in card is, without touching this crate.
+pub trait AudioDeviceProfile: Send + Sync {
+    /// The name `Display` prints and `FromStr` accepts.
+    fn canonical_name(&self) -> &str;
+
+    /// Additional names `FromStr` accepts for this card, case-insensitively.
+    fn aliases(&self) -> &[&str];
+
+    /// The resources `AudioDevice::new` seeds a freshly created instance with.
+    fn default_resources(&self) -> ResourceDefaults;
+
+    /// The resources this card can legally be configured to, consulted by
+    /// `AudioDevice::validate`.
+    fn valid_resources(&self) -> ResourceLimits;
+}
+
+/// Represents a specific type of audio device typically found in MS-DOS-compatible PC systems
+/// manufactured between 1980 and 1996.
+///
+/// This enum provides a type-safe way to handle device identification and configuration. Some
+/// effort was made to span the gamut of relevant hardware. Not *everything* that was ever released
+/// is included here, but a card missing from the built-in set (TurtleBeach, Ensoniq, ...)
+/// no longer needs an enum variant: implement `AudioDeviceProfile` for it and call
+/// `register_profile`, and `Custom` carries it by name from then on.
+#[derive(Clone, Debug, PartialEq)]
+#[allow(clippy::upper_case_acronyms)]
+pub enum AudioDeviceType {
+    /// Standard PC speaker (beeper)
+    Bleeper,
+    /// AdLib FM synthesis card (Yamaha OPL2)
+    AdLib,
+    /// Creative Music System (CMS / Game Blaster)
+    CMS,
+    /// Sound Blaster 1.0
+    SB10,
+    /// Sound Blaster 1.5
+    SB15,
+    /// Sound Blaster 2.0
+    SB20,
+    /// Sound Blaster Pro
+    SBPRO,
+    /// Sound Blaster Pro 2
+    SBPRO2,
+    /// Sound Blaster 16
+    SB16,
+    /// Sound Blaster AWE32
+    SBAWE32,
+    /// Roland MT-32 (LA synthesis module)
+    MT32,
+    /// Roland LAPC-I (internal MT-32 compatible sound card)
+    LAPC1,
+    /// Roland MPU-401 MIDI interface
+    MPU401,
+    /// Roland SC-55 Sound Canvas
+    SC55,
+    /// Roland SCC-1 (internal SC-55-based sound card)
+    SCC1,
+    /// Covox Speech Thing (parallel port audio device)
+    COVOX,
+    /// Gravis Ultrasound
+    GUS,
+    /// Gravis Ultrasound MAX
+    GUSMAX,
+    /// Tandy 1000 / IBM PCjr
+    Tandy,
+    /// ESS AudioDrive ES688 (8-bit, Sound Blaster Pro compatible)
+    ES688,
+    /// ESS AudioDrive ES1688 (8-bit, Sound Blaster Pro compatible)
+    ES1688,
+    /// ESS AudioDrive ES1868 (16-bit, Sound Blaster 16 compatible)
+    ES1868,
+    /// Avance Logic ALS-100 (Sound Blaster 16 compatible)
+    ALS100,
+    /// Avance Logic ALS-200 (Sound Blaster 16 compatible)
+    ALS200,
+    /// Media Vision Pro AudioSpectrum 16 (Sound Blaster 2.0 compatible)
+    PAS16,
+    /// C-Media CMI8330 (Sound Blaster 16 compatible)
+    CMI8330,
+    /// Aztech Sound Galaxy (Sound Blaster Pro compatible)
+    SoundGalaxy,
+    /// A card registered at runtime through `register_profile`, carried by its
+    /// profile's canonical name rather than a compiled-in variant.
+    Custom(String),
+}
+
+/// One built-in card's identity and hardware-resource characteristics: the single
+/// table `Display`, `FromStr`, `AudioDevice::new`, and `AudioDevice::validate` all
+/// consult, via the registry, instead of each having their own hard-coded arms.
+struct BuiltinEntry {
+    variant: AudioDeviceType,
+    canonical: &'static str,
+    short_name: &'static str,
+    aliases: &'static [&'static str],
+    default_resources: ResourceDefaults,
+    valid_io: &'static [u16],
+    valid_dma_low: &'static [u8],
+    valid_dma_high: &'static [u8],
+    valid_irq_low: &'static [u8],
+    valid_irq_high: &'static [u8],
+}
+
+const NO_DEFAULTS: ResourceDefaults = ResourceDefaults {
+    io: None,
+    dma_low: None,
+    dma_high: None,
+    irq_low: None,
+    irq_high: None,
+};
+
+/// The documented, jumper-selectable I/O bases, DMA channels, and IRQ lines, and
+/// factory-default resource assignment, for every built-in card. See
+/// `BuiltinEntry` and `ResourceLimits`/`ResourceDefaults`.
+const BUILTINS: &[BuiltinEntry] = &[
+    BuiltinEntry {
+        variant: AudioDeviceType::Bleeper,
+        canonical: "PC Speaker",
+        short_name: "bleeper",
+        aliases: &["bleeper", "speaker", "pcspeaker", "pc speaker"],
+        default_resources: NO_DEFAULTS,
+        valid_io: &[],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::Tandy,
+        canonical: "Tandy 1000 / IBM PCjr",
+        short_name: "tandy",
+        aliases: &["tandy", "tandy1000", "tandy 1000", "pcjr", "pc jr"],
+        default_resources: NO_DEFAULTS,
+        valid_io: &[],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::AdLib,
+        canonical: "AdLib",
+        short_name: "adlib",
+        aliases: &["adlib"],
+        default_resources: ResourceDefaults {
+            io: Some(0x388),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x388],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::CMS,
+        canonical: "CMS / Game Blaster",
+        short_name: "cms",
+        aliases: &["cms", "game blaster", "gameblaster"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x220, 0x240],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SB10,
+        canonical: "Sound Blaster 1.0",
+        short_name: "sb10",
+        aliases: &["sb10"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(7),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SB15,
+        canonical: "Sound Blaster 1.5",
+        short_name: "sb15",
+        aliases: &["sb15"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(7),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SB20,
+        canonical: "Sound Blaster 2.0",
+        short_name: "sb20",
+        aliases: &["sb20"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(7),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SBPRO,
+        canonical: "Sound Blaster Pro",
+        short_name: "sbpro",
+        aliases: &["sbpro"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(5),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SBPRO2,
+        canonical: "Sound Blaster Pro 2",
+        short_name: "sbpro2",
+        aliases: &["sbpro2", "sbpro20"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(5),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SB16,
+        canonical: "Sound Blaster 16",
+        short_name: "sb16",
+        aliases: &["sb16"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            dma_high: Some(5),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[5, 6, 7],
+        valid_irq_low: &[2, 5, 7, 9, 10, 11, 12],
+        valid_irq_high: &[2, 5, 7, 9, 10, 11, 12],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SBAWE32,
+        canonical: "Sound Blaster AWE32",
+        short_name: "sbawe32",
+        aliases: &["sbawe32", "awe32"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            dma_high: Some(5),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[5, 6, 7],
+        valid_irq_low: &[2, 5, 7, 9, 10, 11, 12],
+        valid_irq_high: &[2, 5, 7, 9, 10, 11, 12],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::MT32,
+        canonical: "Roland MT-32",
+        short_name: "mt32",
+        aliases: &["mt32", "mt-32"],
+        default_resources: ResourceDefaults {
+            io: Some(0x330),
+            irq_low: Some(2),
+            irq_high: Some(9),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x300, 0x310, 0x320, 0x330, 0x340, 0x350, 0x360],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 4, 5, 7, 9, 10, 15],
+        valid_irq_high: &[2, 3, 4, 5, 7, 9, 10, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::LAPC1,
+        canonical: "Roland LAPC-I",
+        short_name: "lapc1",
+        aliases: &["lapc1", "lapci", "lapc-1", "lapc-i"],
+        default_resources: ResourceDefaults {
+            io: Some(0x330),
+            irq_low: Some(2),
+            irq_high: Some(9),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x300, 0x310, 0x320, 0x330, 0x340, 0x350, 0x360],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 4, 5, 7, 9, 10, 15],
+        valid_irq_high: &[2, 3, 4, 5, 7, 9, 10, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::MPU401,
+        canonical: "Roland MPU-401",
+        short_name: "mpu401",
+        aliases: &["mpu401", "mpu-401"],
+        default_resources: ResourceDefaults {
+            io: Some(0x330),
+            irq_low: Some(2),
+            irq_high: Some(9),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x300, 0x310, 0x320, 0x330, 0x340, 0x350, 0x360],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 4, 5, 7, 9, 10, 15],
+        valid_irq_high: &[2, 3, 4, 5, 7, 9, 10, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SC55,
+        canonical: "Roland SC-55",
+        short_name: "sc55",
+        aliases: &["sc55", "sc-55"],
+        default_resources: ResourceDefaults {
+            io: Some(0x330),
+            irq_low: Some(2),
+            irq_high: Some(9),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x300, 0x310, 0x320, 0x330, 0x340, 0x350, 0x360],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 4, 5, 7, 9, 10, 15],
+        valid_irq_high: &[2, 3, 4, 5, 7, 9, 10, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SCC1,
+        canonical: "Roland SCC-1",
+        short_name: "scc1",
+        aliases: &["scc1", "scc-1"],
+        default_resources: ResourceDefaults {
+            io: Some(0x330),
+            irq_low: Some(2),
+            irq_high: Some(9),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x300, 0x310, 0x320, 0x330, 0x340, 0x350, 0x360],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 4, 5, 7, 9, 10, 15],
+        valid_irq_high: &[2, 3, 4, 5, 7, 9, 10, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::COVOX,
+        canonical: "Covox Speech Thing",
+        short_name: "covox",
+        aliases: &["covox", "disney"],
+        default_resources: ResourceDefaults {
+            io: Some(0x378),
+            irq_low: Some(7),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x278, 0x378, 0x3BC],
+        valid_dma_low: &[],
+        valid_dma_high: &[],
+        valid_irq_low: &[],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::GUS,
+        canonical: "Gravis Ultrasound",
+        short_name: "gus",
+        aliases: &["gus", "ultrasound"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(5),
+            dma_high: Some(7),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260],
+        valid_dma_low: &[1, 3, 5, 6, 7],
+        valid_dma_high: &[1, 3, 5, 6, 7],
+        valid_irq_low: &[2, 3, 5, 7, 9, 11, 12, 15],
+        valid_irq_high: &[2, 3, 5, 7, 9, 11, 12, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::GUSMAX,
+        canonical: "Gravis Ultrasound MAX",
+        short_name: "gusmax",
+        aliases: &["gusmax", "ultrasoundmax", "ultrasound max"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(5),
+            dma_high: Some(7),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260],
+        valid_dma_low: &[1, 3, 5, 6, 7],
+        valid_dma_high: &[1, 3, 5, 6, 7],
+        valid_irq_low: &[2, 3, 5, 7, 9, 11, 12, 15],
+        valid_irq_high: &[2, 3, 5, 7, 9, 11, 12, 15],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::ES688,
+        canonical: "ESS AudioDrive ES688",
+        short_name: "es688",
+        aliases: &["es688"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(5),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::ES1688,
+        canonical: "ESS AudioDrive ES1688",
+        short_name: "es1688",
+        aliases: &["es1688"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(5),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::ES1868,
+        canonical: "ESS AudioDrive ES1868",
+        short_name: "es1868",
+        aliases: &["es1868"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            dma_high: Some(5),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[5, 6, 7],
+        valid_irq_low: &[2, 5, 7, 9, 10, 11, 12],
+        valid_irq_high: &[2, 5, 7, 9, 10, 11, 12],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::ALS100,
+        canonical: "Avance Logic ALS-100",
+        short_name: "als100",
+        aliases: &["als100", "als-100"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            dma_high: Some(5),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[5, 6, 7],
+        valid_irq_low: &[2, 5, 7, 9, 10, 11, 12],
+        valid_irq_high: &[2, 5, 7, 9, 10, 11, 12],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::ALS200,
+        canonical: "Avance Logic ALS-200",
+        short_name: "als200",
+        aliases: &["als200", "als-200"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            dma_high: Some(5),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[5, 6, 7],
+        valid_irq_low: &[2, 5, 7, 9, 10, 11, 12],
+        valid_irq_high: &[2, 5, 7, 9, 10, 11, 12],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::PAS16,
+        canonical: "Media Vision Pro AudioSpectrum 16",
+        short_name: "pas16",
+        aliases: &["pas16"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(7),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::CMI8330,
+        canonical: "C-Media CMI8330",
+        short_name: "cmi8330",
+        aliases: &["cmi8330"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            dma_high: Some(5),
+            irq_low: Some(5),
+            irq_high: Some(11),
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[5, 6, 7],
+        valid_irq_low: &[2, 5, 7, 9, 10, 11, 12],
+        valid_irq_high: &[2, 5, 7, 9, 10, 11, 12],
+    },
+    BuiltinEntry {
+        variant: AudioDeviceType::SoundGalaxy,
+        canonical: "Aztech Sound Galaxy",
+        short_name: "soundgalaxy",
+        aliases: &["soundgalaxy", "sound galaxy"],
+        default_resources: ResourceDefaults {
+            io: Some(0x220),
+            dma_low: Some(1),
+            irq_low: Some(5),
+            ..NO_DEFAULTS
+        },
+        valid_io: &[0x210, 0x220, 0x230, 0x240, 0x250, 0x260, 0x280],
+        valid_dma_low: &[0, 1, 3],
+        valid_dma_high: &[],
+        valid_irq_low: &[2, 3, 5, 7, 10],
+        valid_irq_high: &[],
+    },
+];
+
+/// An `AudioDeviceProfile` backed by one of the compiled-in `BUILTINS` entries.
+struct BuiltinProfile(&'static BuiltinEntry);
+
+impl AudioDeviceProfile for BuiltinProfile {
+    fn canonical_name(&self) -> &str {
+        self.0.canonical
+    }
+
+    fn aliases(&self) -> &[&str] {
+        self.0.aliases
+    }
+
+    fn default_resources(&self) -> ResourceDefaults {
+        self.0.default_resources
+    }
+
+    fn valid_resources(&self) -> ResourceLimits {
+        ResourceLimits {
+            io: self.0.valid_io.to_vec(),
+            dma_low: self.0.valid_dma_low.to_vec(),
+            dma_high: self.0.valid_dma_high.to_vec(),
+            irq_low: self.0.valid_irq_low.to_vec(),
+            irq_high: self.0.valid_irq_high.to_vec(),
+        }
+    }
+}
+
+fn builtin_profiles() -> Vec<Box<dyn AudioDeviceProfile>> {
+    BUILTINS
+        .iter()
+        .map(|entry| Box::new(BuiltinProfile(entry)) as Box<dyn AudioDeviceProfile>)
+        .collect()
+}
+
+static REGISTRY: OnceLock<Mutex<Vec<Box<dyn AudioDeviceProfile>>>> = OnceLock::new();
+
+fn with_registry<R>(f: impl FnOnce(&[Box<dyn AudioDeviceProfile>]) -> R) -> R {
+    let registry = REGISTRY.get_or_init(|| Mutex::new(builtin_profiles()));
+    let profiles = registry
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    f(&profiles)
+}
+
+/// Adds a card to the audio-device registry, so `AudioDeviceType::from_str` and
+/// `AudioDevice::new` can find it by name the same way they find a built-in card,
+/// without this crate needing a dedicated enum variant or match arm for it.
+///
+/// A registered profile never shadows a built-in: if its name or an alias collides
+/// with a built-in card, the built-in is still what `from_str` resolves to.
+pub fn register_profile(profile: Box<dyn AudioDeviceProfile>) {
+    let registry = REGISTRY.get_or_init(|| Mutex::new(builtin_profiles()));
+    registry
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner())
+        .push(profile);
+}
+
+/// The name this device resolves to in the registry: a built-in's canonical name,
+/// or the name a `Custom` device carries directly.
+fn name_of(device: &AudioDeviceType) -> String {
+    match device {
+        AudioDeviceType::Custom(name) => name.clone(),
+        other => BUILTINS
+            .iter()
+            .find(|entry| &entry.variant == other)
+            .map(|entry| entry.canonical.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+/// The short, lowercase token `Serialize` writes and `FromStr` accepts back, e.g.
+/// `"sb16"` for `AudioDeviceType::SB16`. A `Custom` device has no separate short
+/// form, so it round-trips through the same name it was registered under.
+fn short_name_of(device: &AudioDeviceType) -> String {
+    match device {
+        AudioDeviceType::Custom(name) => name.clone(),
+        other => BUILTINS
+            .iter()
+            .find(|entry| &entry.variant == other)
+            .map(|entry| entry.short_name.to_string())
+            .unwrap_or_default(),
+    }
+}
+
+impl fmt::Display for AudioDeviceType {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", name_of(self))
+    }
+}
+
+impl fmt::Display for AudioDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.device)?;
+
+        let mut parts = Vec::new();
+        if let Some(io) = self.io {
+            parts.push(format!("IO={:#x}", io));
+        }
+        if let Some(dma) = self.dma_low {
+            parts.push(format!("DMA={}", dma));
+        }
+        if let Some(dma_hi) = self.dma_high {
+            parts.push(format!("DMA_HI={}", dma_hi));
+        }
+        if let Some(irq) = self.irq_low {
+            parts.push(format!("IRQ={}", irq));
+        }
+        if let Some(irq_hi) = self.irq_high {
+            parts.push(format!("IRQ_HI={}", irq_hi));
+        }
+
+        if !parts.is_empty() {
+            write!(f, " [{}]", parts.join(", "))?;
+        }
+
+        Ok(())
+    }
+}
+
+impl FromStr for AudioDeviceType {
+    type Err = SpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let needle = s.trim().to_lowercase();
+
+        if let Some(entry) = BUILTINS
+            .iter()
+            .find(|entry| {
+                entry.aliases.contains(&needle.as_str())
+                    || entry.canonical.eq_ignore_ascii_case(&needle)
+            })
+        {
+            return Ok(entry.variant.clone());
+        }
+
+        let custom_name = with_registry(|profiles| {
+            profiles
+                .iter()
+                .find(|profile| {
+                    profile.canonical_name().eq_ignore_ascii_case(&needle)
+                        || profile
+                            .aliases()
+                            .iter()
+                            .any(|alias| alias.eq_ignore_ascii_case(&needle))
+                })
+                .map(|profile| profile.canonical_name().to_string())
+        });
+
+        match custom_name {
+            Some(name) => Ok(AudioDeviceType::Custom(name)),
+            None => Err(SpecError::InvalidAudioDevice(s.to_string())),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for AudioDeviceType {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        AudioDeviceType::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for AudioDeviceType {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.serialize_str(&short_name_of(self))
+    }
+}
+
+/// The Yamaha FM synthesizer chip behind a device's music output, if any.
+///
+/// Games pick their rendering path off this distinction rather than the device
+/// identity directly: `DualOPL2` and `OPL3` both offer stereo FM, but only `OPL3`
+/// supports its extended 4-operator voices.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FmChip {
+    /// No FM synthesizer (e.g. the PC speaker, or a MIDI-only interface).
+    None,
+    /// Yamaha YM3812, mono FM only.
+    OPL2,
+    /// Two YM3812 chips, one per stereo channel.
+    DualOPL2,
+    /// Yamaha YMF262, stereo with extended 4-operator voices.
+    OPL3,
+}
+
+/// Represents a fully configured instance of an audio device in a system.
+///
+/// This struct associates a specific `AudioDeviceType` with optional hardware
+/// resource assignments (I/O port address, DMA channel, and IRQ line).
+///
+/// Some devices may require only an I/O port, while others might also need DMA and IRQ lines.
+#[derive(Debug, PartialEq, Deserialize)]
+pub struct AudioDevice {
+    device: AudioDeviceType,
+    #[serde(default)]
+    io: Option<u16>,
+    #[serde(default)]
+    dma_low: Option<u8>,
+    #[serde(default)]
+    dma_high: Option<u8>,
+    #[serde(default)]
+    irq_low: Option<u8>,
+    #[serde(default)]
+    irq_high: Option<u8>,
+}
+
+impl Serialize for AudioDevice {
+    /// Serializes only the fields that differ from `AudioDevice::new(self.device)`'s
+    /// factory defaults, so a device that hasn't been touched beyond `new`/`merge`
+    /// round-trips back to just its `device` line, and a manifest built from
+    /// `deserialize` -> `merge` -> `serialize` stays minimal and stable.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let defaults = AudioDevice::new(self.device.clone());
+        let overridden = [
+            self.io != defaults.io,
+            self.dma_low != defaults.dma_low,
+            self.dma_high != defaults.dma_high,
+            self.irq_low != defaults.irq_low,
+            self.irq_high != defaults.irq_high,
+        ];
+
+        let mut state = serializer
+            .serialize_struct("AudioDevice", 1 + overridden.iter().filter(|x| **x).count())?;
+        state.serialize_field("device", &self.device)?;
+        if overridden[0] {
+            state.serialize_field("io", &self.io)?;
+        }
+        if overridden[1] {
+            state.serialize_field("dma_low", &self.dma_low)?;
+        }
+        if overridden[2] {
+            state.serialize_field("dma_high", &self.dma_high)?;
+        }
+        if overridden[3] {
+            state.serialize_field("irq_low", &self.irq_low)?;
+        }
+        if overridden[4] {
+            state.serialize_field("irq_high", &self.irq_high)?;
+        }
+        state.end()
+    }
+}
+
+impl AudioDevice {
+    /// Creates a new `AudioDevice` instance for the given `AudioDeviceType`.
+    ///
+    /// Hardware resource assignments (I/O port, DMA, IRQ) are seeded from the
+    /// device's registered `AudioDeviceProfile` (see `register_profile`), the
+    /// documented factory defaults from the original manufacturer's documentation
+    /// for a built-in card.
+    ///
+    /// # Arguments
+    ///
+    /// * `device` - The type of audio device.
+    pub fn new(device: AudioDeviceType) -> Self {
+        let name = name_of(&device);
+        let defaults = with_registry(|profiles| {
+            profiles
+                .iter()
+                .find(|profile| profile.canonical_name().eq_ignore_ascii_case(&name))
+                .map(|profile| profile.default_resources())
+        })
+        .unwrap_or_default();
+
+        AudioDevice {
+            device,
+            io: defaults.io,
+            dma_low: defaults.dma_low,
+            dma_high: defaults.dma_high,
+            irq_low: defaults.irq_low,
+            irq_high: defaults.irq_high,
+        }
+    }
+
+    // Merge method for overriding defaults with deserialized values
+    pub fn merge(self, defaults: AudioDevice) -> Self {
+        AudioDevice {
+            device: self.device, // always take the device from the deserialized data
+            io: self.io.or(defaults.io()),
+            dma_low: self.dma_low.or(defaults.dma_low()),
+            dma_high: self.dma_high.or(defaults.dma_high()),
+            irq_low: self.irq_low.or(defaults.irq_low()),
+            irq_high: self.irq_high.or(defaults.irq_high()),
+        }
+    }
+
+    /// Returns a reference to the `AudioDeviceType` of this device.
+    pub fn device_type(&self) -> &AudioDeviceType {
+        &self.device
+    }
+
+    /// Sets the I/O port address for this device.
+    ///
+    /// # Arguments
+    ///
+    /// * `io` - The I/O port address (in hexadecimal, e.g., `0x220`).
+    pub fn set_io(&mut self, io: u16) {
+        self.io = Some(io);
+    }
+
+    /// Sets the DMA channel number for this device.
+    ///
+    /// # Arguments
+    ///
+    /// * `dma` - The DMA channel number (typically 0–7).
+    pub fn set_dma_low(&mut self, dma: u8) {
+        self.dma_low = Some(dma);
+    }
+
+    /// Set the high (16-bit) DMA channel number for this device.
+    ///
+    /// # Arguments
+    /// * `dma` - The DMA channel number (typically 0-7).
+    pub fn set_dma_high(&mut self, dma: u8) {
+        self.dma_high = Some(dma);
+    }
+
+    /// Sets the IRQ line number for this device.
+    ///
+    /// # Arguments
+    ///
+    /// * `irq` - The IRQ line number (typically 0–7).
+    pub fn set_irq_low(&mut self, irq: u8) {
+        self.irq_low = Some(irq);
+    }
+
+    /// Sets the 16-bit IRQ line number for this device.
+    ///
+    /// # Arguments
+    ///
+    /// * `irq` - The IRQ line number (typically 8-15)
+    pub fn set_irq_high(&mut self, irq: u8) {
+        self.irq_high = Some(irq);
+    }
+
+    /// Convenience method to set all hardware resources for this device at once.
+    ///
+    /// # Arguments
+    ///
+    /// * `io` - The I/O port address.
+    /// * `dma` - The DMA channel number.
+    /// * `irq` - The IRQ line number.
+    pub fn configure(&mut self, io: u16, dma_low: u8, dma_high: u8, irq_low: u8, irq_high: u8) {
+        self.set_io(io);
+        self.set_dma_low(dma_low);
+        self.set_dma_high(dma_high);
+        self.set_irq_low(irq_low);
+        self.set_irq_high(irq_high);
+    }
+
+    /// Returns the configured I/O port address, if any.
+    pub fn io(&self) -> Option<u16> {
+        self.io
+    }
+
+    /// Returns the configured DMA channel number, if any.
+    pub fn dma_low(&self) -> Option<u8> {
+        self.dma_low
+    }
+
+    /// Returns the configured DMA channel (16-bit), if any.
+    pub fn dma_high(&self) -> Option<u8> {
+        self.dma_high
+    }
+
+    /// Returns the configured IRQ line number, if any.
+    pub fn irq_low(&self) -> Option<u8> {
+        self.irq_low
+    }
+
+    /// Returns the configured 16-bit IRQ line number, if any.
+    pub fn irq_high(&self) -> Option<u8> {
+        self.irq_high
+    }
+
+    /// Checks every configured resource against this device's registered
+    /// `AudioDeviceProfile`: the documented, jumper-selectable I/O bases, DMA
+    /// channels, and IRQ lines a real card of this model could actually be set to.
+    ///
+    /// A device whose type has no registered profile (or whose profile leaves a
+    /// resource's `ResourceLimits` list empty) places no constraint on that
+    /// resource.
+    ///
+    /// # Errors
+    ///
+    /// Returns `SpecError::UnsupportedAudioResource` naming the offending resource
+    /// and device if a configured value isn't in the device's allowed set.
+    pub fn validate(&self) -> Result<(), SpecError> {
+        let name = name_of(&self.device);
+        let limits = with_registry(|profiles| {
+            profiles
+                .iter()
+                .find(|profile| profile.canonical_name().eq_ignore_ascii_case(&name))
+                .map(|profile| profile.valid_resources())
+        })
+        .unwrap_or_default();
+
+        check_allowed(self.io, &limits.io, "I/O port", self)?;
+        check_allowed(self.dma_low, &limits.dma_low, "DMA channel", self)?;
+        check_allowed(self.dma_high, &limits.dma_high, "high DMA channel", self)?;
+        check_allowed(self.irq_low, &limits.irq_low, "IRQ", self)?;
+        check_allowed(self.irq_high, &limits.irq_high, "high IRQ", self)?;
+        Ok(())
+    }
+
+    /// The genuine Sound Blaster model this device presents itself as in hardware
+    /// register compatibility, or `None` if the device isn't an SB-compatible clone.
+    ///
+    /// Downstream tooling can use this to configure a clone for its SB-compatible
+    /// fallback mode (e.g. by building an `AudioDevice` from the returned type)
+    /// instead of whatever native mode it also supports.
+    pub fn sb_emulation(&self) -> Option<AudioDeviceType> {
+        match self.device {
+            AudioDeviceType::ES688 | AudioDeviceType::ES1688 | AudioDeviceType::SoundGalaxy => {
+                Some(AudioDeviceType::SBPRO)
+            }
+            AudioDeviceType::ES1868
+            | AudioDeviceType::ALS100
+            | AudioDeviceType::ALS200
+            | AudioDeviceType::CMI8330 => Some(AudioDeviceType::SB16),
+            AudioDeviceType::PAS16 => Some(AudioDeviceType::SB20),
+            _ => None,
+        }
+    }
+
+    /// The numeric `SET BLASTER` card-type code (`T`) for this device, or `None` if
+    /// the device isn't Sound Blaster compatible and the variable has no meaning
+    /// for it.
+    fn blaster_type_code(&self) -> Option<u8> {
+        match self.device {
+            AudioDeviceType::SB10 | AudioDeviceType::SB15 | AudioDeviceType::SB20 => Some(1),
+            AudioDeviceType::SBPRO => Some(2),
+            AudioDeviceType::SBPRO2 => Some(3),
+            AudioDeviceType::SB16 | AudioDeviceType::SBAWE32 => Some(6),
+            _ => None,
+        }
+    }
+
+    /// Renders this device as the canonical `SET BLASTER=...` environment string DOS
+    /// programs probe for, e.g. `SET BLASTER=A220 I5 D1 H5 P330 T6`.
+    ///
+    /// Returns `None` for devices that aren't Sound Blaster compatible, since the
+    /// variable has no meaning for them. Resources that aren't configured (such as
+    /// `H`, the high DMA channel, on anything before the SB16) are left out of the
+    /// string entirely rather than emitted as zero.
+    pub fn blaster_env(&self) -> Option<String> {
+        let type_code = self.blaster_type_code()?;
+        let io = self.io?;
+
+        let mut fields = vec![format!("A{:X}", io)];
+        if let Some(irq) = self.irq_low {
+            fields.push(format!("I{irq}"));
+        }
+        if let Some(dma) = self.dma_low {
+            fields.push(format!("D{dma}"));
+        }
+        if let Some(dma_high) = self.dma_high {
+            fields.push(format!("H{dma_high}"));
+        }
+        // The SB16/AWE32's onboard MPU-401 conventionally sits at 0x330.
+        if type_code == 6 {
+            fields.push("P330".to_string());
+        }
+        fields.push(format!("T{type_code}"));
+
+        Some(format!("SET BLASTER={}", fields.join(" ")))
+    }
+
+    /// Renders this device as the canonical `SET ULTRASND=...` environment string
+    /// Gravis Ultrasound-aware DOS programs probe for, e.g.
+    /// `SET ULTRASND=220,5,7,5,11` (port, dma_low, dma_high, irq_low, irq_high).
+    ///
+    /// Returns `None` for devices that aren't a Gravis Ultrasound, or for one that
+    /// isn't fully configured: unlike `blaster_env`, every field is mandatory in the
+    /// real `ULTRASND` string, so a partially configured GUS has no valid rendering.
+    pub fn ultrasnd_env(&self) -> Option<String> {
+        match self.device {
+            AudioDeviceType::GUS | AudioDeviceType::GUSMAX => (),
+            _ => return None,
+        }
+
+        Some(format!(
+            "SET ULTRASND={:X},{},{},{},{}",
+            self.io?, self.dma_low?, self.dma_high?, self.irq_low?, self.irq_high?
+        ))
+    }
+
+    /// Renders this device as the `SET MIDI=...` environment string resident MPU-401
+    /// patch drivers (e.g. Sierra's MIDPAK) probe for, e.g.
+    /// `SET MIDI=SYNTH:1 MAP:G MPU:330`.
+    ///
+    /// Returns `None` for devices with no standalone MPU-401 interface, or without a
+    /// configured I/O port. The SB16/AWE32's onboard MPU-401 is reported through
+    /// `blaster_env`'s `P330` field instead, since that's the string DOS programs
+    /// actually probe for it.
+    pub fn midi_env(&self) -> Option<String> {
+        match self.device {
+            AudioDeviceType::MT32
+            | AudioDeviceType::LAPC1
+            | AudioDeviceType::MPU401
+            | AudioDeviceType::SC55
+            | AudioDeviceType::SCC1 => (),
+            _ => return None,
+        }
+
+        Some(format!("SET MIDI=SYNTH:1 MAP:G MPU:{:X}", self.io?))
+    }
+
+    /// The Yamaha FM synthesizer chip this device's music output is rendered
+    /// through, or `FmChip::None` if it has no FM chip at all (e.g. the GUS, or a
+    /// MIDI-only interface).
+    pub fn fm_chip(&self) -> FmChip {
+        match self.device {
+            AudioDeviceType::AdLib
+            | AudioDeviceType::SB10
+            | AudioDeviceType::SB15
+            | AudioDeviceType::SB20
+            | AudioDeviceType::ES688
+            | AudioDeviceType::ES1688
+            | AudioDeviceType::PAS16
+            | AudioDeviceType::SoundGalaxy => FmChip::OPL2,
+            AudioDeviceType::SBPRO => FmChip::DualOPL2,
+            AudioDeviceType::SBPRO2
+            | AudioDeviceType::SB16
+            | AudioDeviceType::SBAWE32
+            | AudioDeviceType::ES1868
+            | AudioDeviceType::ALS100
+            | AudioDeviceType::ALS200
+            | AudioDeviceType::CMI8330 => FmChip::OPL3,
+            _ => FmChip::None,
+        }
+    }
+
+    /// The I/O port this device's FM chip is mirrored at, or `None` if it has no FM
+    /// chip.
+    ///
+    /// Every OPL-equipped card mirrors its FM chip at the standard AdLib address
+    /// `0x388` regardless of its own base port (the AdLib itself lives only there),
+    /// so software that only knows to probe `0x388` still finds the synth.
+    pub fn fm_io(&self) -> Option<u16> {
+        match self.fm_chip() {
+            FmChip::None => None,
+            _ => Some(0x388),
+        }
+    }
+
+    /// The DOSBox `[sblaster]` `sbtype=` token for this device: `sb1`, `sbpro1`,
+    /// `sbpro2`, `sb16` or `gus`. Returns `None` for devices DOSBox's Sound
+    /// Blaster/GUS emulation can't represent.
+    fn dosbox_sbtype(&self) -> Option<&'static str> {
+        match self.device {
+            AudioDeviceType::SB10 | AudioDeviceType::SB15 | AudioDeviceType::SB20 => Some("sb1"),
+            AudioDeviceType::SBPRO => Some("sbpro1"),
+            AudioDeviceType::SBPRO2 => Some("sbpro2"),
+            AudioDeviceType::SB16 | AudioDeviceType::SBAWE32 => Some("sb16"),
+            AudioDeviceType::GUS | AudioDeviceType::GUSMAX => Some("gus"),
+            _ => None,
+        }
+    }
+
+    /// Renders this device as a DOSBox-style configuration fragment: a `[sblaster]`
+    /// block, and a `[midi]` block for anything with an MPU-401 interface.
+    ///
+    /// `sbtype` is `none` for devices DOSBox can't emulate as a Sound Blaster or
+    /// GUS, in which case the rest of the `[sblaster]` fields are left out. Fields
+    /// that aren't configured on this device (`sbbase`, `irq`, `dma`, `hdma`) are
+    /// likewise left out rather than written as zero.
+    pub fn dosbox_config(&self) -> String {
+        let sbtype = self.dosbox_sbtype().unwrap_or("none");
+        let mut lines = vec!["[sblaster]".to_string(), format!("sbtype={sbtype}")];
+
+        if sbtype != "none" {
+            if let Some(io) = self.io {
+                lines.push(format!("sbbase={:x}", io));
+            }
+            if let Some(irq) = self.irq_low {
+                lines.push(format!("irq={irq}"));
+            }
+            if let Some(dma) = self.dma_low {
+                lines.push(format!("dma={dma}"));
+            }
+            if let Some(dma_high) = self.dma_high {
+                lines.push(format!("hdma={dma_high}"));
+            }
+        }
+
+        let mpu401 = match self.device {
+            AudioDeviceType::SB16 | AudioDeviceType::SBAWE32 => Some("intelligent"),
+            AudioDeviceType::MT32
+            | AudioDeviceType::LAPC1
+            | AudioDeviceType::MPU401
+            | AudioDeviceType::SC55
+            | AudioDeviceType::SCC1 => Some("uart"),
+            _ => None,
+        };
+        if let Some(mode) = mpu401 {
+            lines.push(String::new());
+            lines.push("[midi]".to_string());
+            lines.push(format!("mpu401={mode}"));
+        }
+
+        lines.join("\n")
+    }
+
+    /// The I/O port range this device's type occupies, anchored at its configured
+    /// base port, or `None` if the device has no I/O port configured or doesn't
+    /// claim a port range at all (e.g. the PC speaker).
+    ///
+    /// Spans follow the conventional port windows these cards decode on real
+    /// hardware (e.g. an SB16 decodes a 16-port window at its base, an AdLib or
+    /// MPU-401 only two), so partial overlaps between two devices are caught even
+    /// when their base ports differ.
+    fn io_range(&self) -> Option<(u16, u16)> {
+        let io = self.io?;
+        let span: u16 = match self.device {
+            AudioDeviceType::Bleeper | AudioDeviceType::Tandy | AudioDeviceType::Custom(_) => {
+                return None
+            }
+            AudioDeviceType::SB10
+            | AudioDeviceType::SB15
+            | AudioDeviceType::SB20
+            | AudioDeviceType::SBPRO
+            | AudioDeviceType::SBPRO2
+            | AudioDeviceType::SB16
+            | AudioDeviceType::SBAWE32
+            | AudioDeviceType::GUS
+            | AudioDeviceType::GUSMAX
+            | AudioDeviceType::ES688
+            | AudioDeviceType::ES1688
+            | AudioDeviceType::ES1868
+            | AudioDeviceType::ALS100
+            | AudioDeviceType::ALS200
+            | AudioDeviceType::PAS16
+            | AudioDeviceType::CMI8330
+            | AudioDeviceType::SoundGalaxy => 16,
+            AudioDeviceType::AdLib
+            | AudioDeviceType::CMS
+            | AudioDeviceType::MT32
+            | AudioDeviceType::LAPC1
+            | AudioDeviceType::MPU401
+            | AudioDeviceType::SC55
+            | AudioDeviceType::SCC1 => 2,
+            AudioDeviceType::COVOX => 1,
+        };
+        Some((io, io + span - 1))
+    }
+}
+
+impl FromStr for AudioDevice {
+    type Err = SpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let devicetype = AudioDeviceType::from_str(s)?;
+        Ok(AudioDevice::new(devicetype))
+    }
+}
+
+fn check_allowed<T: PartialEq + fmt::Display + Copy>(
+    value: Option<T>,
+    allowed: &[T],
+    label: &str,
+    device: &AudioDevice,
+) -> Result<(), SpecError> {
+    let Some(value) = value else {
+        return Ok(());
+    };
+    if allowed.is_empty() || allowed.contains(&value) {
+        return Ok(());
+    }
+    Err(SpecError::UnsupportedAudioResource(format!(
+        "{} {} is not valid for {}",
+        label,
+        value,
+        device.device_type()
+    )))
+}
+
+/// Checks every pair of `devices` for a claimed IRQ line, DMA channel, or
+/// overlapping I/O port window they both claim, the same way a hardware driver
+/// would reject two cards fighting over the same resource.
+///
+/// Devices are compared in the order given, and the first collision found is
+/// reported; this doesn't exhaustively list every conflict in a spec with more
+/// than one.
+///
+/// # Errors
+///
+/// Returns `SpecError::AudioResourceConflict` naming the two conflicting devices
+/// and the resource they both claim.
+pub fn check_resource_conflicts(devices: &[AudioDevice]) -> Result<(), SpecError> {
+    for (i, a) in devices.iter().enumerate() {
+        for b in &devices[i + 1..] {
+            if let Some(irq) = shared_value(irqs(a), irqs(b)) {
+                return Err(conflict(a, b, &format!("IRQ {irq}")));
+            }
+            if let Some(dma) = shared_value(dmas(a), dmas(b)) {
+                return Err(conflict(a, b, &format!("DMA channel {dma}")));
+            }
+            if let (Some(a_range), Some(b_range)) = (a.io_range(), b.io_range()) {
+                if let Some((start, end)) = overlap(a_range, b_range) {
+                    return Err(conflict(
+                        a,
+                        b,
+                        &format!("I/O range {:#06x}-{:#06x}", start, end),
+                    ));
+                }
+            }
+        }
+    }
+    Ok(())
+}
+
+fn irqs(device: &AudioDevice) -> Vec<u8> {
+    device.irq_low.into_iter().chain(device.irq_high).collect()
+}
+
+fn dmas(device: &AudioDevice) -> Vec<u8> {
+    device.dma_low.into_iter().chain(device.dma_high).collect()
+}
+
+fn shared_value(a: Vec<u8>, b: Vec<u8>) -> Option<u8> {
+    a.into_iter().find(|value| b.contains(value))
+}
+
+fn overlap(a: (u16, u16), b: (u16, u16)) -> Option<(u16, u16)> {
+    let start = a.0.max(b.0);
+    let end = a.1.min(b.1);
+    (start <= end).then_some((start, end))
+}
+
+fn conflict(a: &AudioDevice, b: &AudioDevice, resource: &str) -> SpecError {
+    SpecError::AudioResourceConflict(format!(
+        "{} and {} both claim {}",
+        a.device_type(),
+        b.device_type(),
+        resource
+    ))
+}
@@ -1,13 +1,204 @@
-use serde::Deserialize;
+use serde::{Deserialize, Serialize};
 
 use crate::error::SpecError;
 use std::fmt;
 use std::str::FromStr;
 
-#[derive(Copy, Clone, Debug, Deserialize)]
+/// The company that manufactured a [`CpuFamily`], surfaced by [`CpuFamily::vendor`] so a
+/// manifest `Layer` can constrain an install to parts from one maker — for instance an OS
+/// that only ran correctly on genuine Intel 386DX steppings, not its Cyrix/AMD/UMC/IBM
+/// clones.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum CpuVendor {
+    Intel,
+    AMD,
+    Cyrix,
+    NEC,
+    UMC,
+    IBM,
+    TexasInstruments,
+}
+
+impl fmt::Display for CpuVendor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            CpuVendor::Intel => "Intel",
+            CpuVendor::AMD => "AMD",
+            CpuVendor::Cyrix => "Cyrix",
+            CpuVendor::NEC => "NEC",
+            CpuVendor::UMC => "UMC",
+            CpuVendor::IBM => "IBM",
+            CpuVendor::TexasInstruments => "Texas Instruments",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A discrete math coprocessor a pre-486 system could be fitted with, external to the
+/// CPU package itself.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Coprocessor {
+    I8087,
+    I80287,
+    I80387,
+}
+
+impl fmt::Display for Coprocessor {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            Coprocessor::I8087 => "Intel 8087",
+            Coprocessor::I80287 => "Intel 80287",
+            Coprocessor::I80387 => "Intel 80387",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+/// A CPU's floating-point math capability: no hardware support at all, software
+/// emulation (the usual fallback on an FPU-less 486SX-class part), an FPU integrated
+/// into the CPU die itself (486DX and later), or a discrete external coprocessor
+/// (8087/80287/80387) socketed alongside an older CPU.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq)]
+pub enum Fpu {
+    None,
+    Emulated,
+    Integrated,
+    External(Coprocessor),
+}
+
+/// Concrete capabilities DOS and protected-mode software actually probed for, as opposed
+/// to the model name: whether protected mode, virtual-8086 mode, 32-bit operation, or
+/// `CPUID` are available. Derived from a [`CpuFamily`] by [`CpuFamily::features`], with
+/// [`Cpu::features`] applying any [`Cpu::cpuid_override`] on top — early 486 steppings
+/// shipped both with and without `CPUID`, so the family alone can't always tell them apart.
+#[derive(Copy, Clone, Debug, Default, Deserialize, Serialize, PartialEq, Eq)]
+pub struct CpuFeatures {
+    protected_mode: bool,
+    virtual_8086: bool,
+    addr32: bool,
+    cpuid: bool,
+}
+
+impl CpuFeatures {
+    /// No capabilities at all — the baseline for an 8086/8088-class part, and handy for
+    /// `static` tables like `OsSupport::required_cpu_features` where `CpuFeatures::default()`
+    /// can't be used because derived `Default` impls aren't `const fn`.
+    pub const fn none() -> Self {
+        CpuFeatures {
+            protected_mode: false,
+            virtual_8086: false,
+            addr32: false,
+            cpuid: false,
+        }
+    }
+
+    pub fn has_protected_mode(&self) -> bool {
+        self.protected_mode
+    }
+
+    pub fn has_virtual_8086(&self) -> bool {
+        self.virtual_8086
+    }
+
+    pub fn is_32bit(&self) -> bool {
+        self.addr32
+    }
+
+    pub fn has_cpuid(&self) -> bool {
+        self.cpuid
+    }
+
+    /// Whether every capability `required` asks for is also present here. Lets an
+    /// `OsSupport` entry or a manifest [`crate::manifest::Layer`] require a capability
+    /// directly (e.g. "needs protected mode") instead of enumerating every qualifying
+    /// [`CpuFamily`] by hand.
+    pub fn satisfies(&self, required: &CpuFeatures) -> bool {
+        (!required.protected_mode || self.protected_mode)
+            && (!required.virtual_8086 || self.virtual_8086)
+            && (!required.addr32 || self.addr32)
+            && (!required.cpuid || self.cpuid)
+    }
+}
+
+/// A CPU clock speed, stored as hundredths of a MHz so period-correct speeds like the
+/// original IBM PC's 4.77 MHz are representable exactly, unlike a whole-number MHz value.
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq, Eq, PartialOrd, Ord)]
+#[serde(try_from = "String", into = "String")]
+pub struct ClockRate(u16);
+
+impl ClockRate {
+    /// Builds a `ClockRate` directly from a count of hundredths of a MHz, e.g. `477` for
+    /// 4.77 MHz.
+    pub fn from_hundredths_mhz(hundredths: u16) -> Self {
+        ClockRate(hundredths)
+    }
+
+    pub fn as_hundredths_mhz(&self) -> u16 {
+        self.0
+    }
+}
+
+impl fmt::Display for ClockRate {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}.{:02} MHz", self.0 / 100, self.0 % 100)
+    }
+}
+
+impl FromStr for ClockRate {
+    type Err = SpecError;
+
+    /// Parses either a bare whole-number speed like `"33"` or a fractional one like
+    /// `"4.77"`, both in MHz.
+    fn from_str(input: &str) -> Result<Self, SpecError> {
+        let input = input.trim().trim_end_matches("MHz").trim_end_matches("Mhz").trim();
+        let (whole, frac) = match input.split_once('.') {
+            Some((whole, frac)) => (whole, frac),
+            None => (input, ""),
+        };
+        let whole: u32 = whole
+            .parse()
+            .map_err(|_| SpecError::InvalidClockRate(input.to_string()))?;
+        let frac: u32 = match frac.len() {
+            0 => 0,
+            1 => frac
+                .parse::<u32>()
+                .map_err(|_| SpecError::InvalidClockRate(input.to_string()))?
+                * 10,
+            2 => frac
+                .parse()
+                .map_err(|_| SpecError::InvalidClockRate(input.to_string()))?,
+            _ => return Err(SpecError::InvalidClockRate(input.to_string())),
+        };
+        let hundredths = whole
+            .checked_mul(100)
+            .and_then(|whole| whole.checked_add(frac))
+            .and_then(|hundredths| u16::try_from(hundredths).ok())
+            .ok_or_else(|| SpecError::InvalidClockRate(input.to_string()))?;
+        Ok(ClockRate(hundredths))
+    }
+}
+
+impl TryFrom<String> for ClockRate {
+    type Error = SpecError;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        ClockRate::from_str(&value)
+    }
+}
+
+impl From<ClockRate> for String {
+    fn from(value: ClockRate) -> Self {
+        value.to_string()
+    }
+}
+
+#[derive(Copy, Clone, Debug, Deserialize, Serialize)]
 pub struct Cpu {
     family: CpuFamily,
-    clock: u8,
+    clock: ClockRate,
+    fpu: Fpu,
+    #[serde(default)]
+    cpuid_override: Option<bool>,
 }
 
 impl Cpu {
@@ -15,10 +206,53 @@ impl Cpu {
         &self.family
     }
 
-    pub fn clock(&self) -> u8 {
+    pub fn clock(&self) -> ClockRate {
         self.clock
     }
 
+    pub fn fpu(&self) -> Fpu {
+        self.fpu
+    }
+
+    /// Marks this specific CPU as a "pre-`CPUID`" or "`CPUID`-capable" part, overriding
+    /// whatever [`CpuFamily::features`] would otherwise assume — early 486 steppings
+    /// existed both ways, so the family alone isn't always enough. Pass `None` to go
+    /// back to trusting the family default.
+    pub fn set_cpuid_override(&mut self, has_cpuid: Option<bool>) {
+        self.cpuid_override = has_cpuid;
+    }
+
+    pub fn cpuid_override(&self) -> Option<bool> {
+        self.cpuid_override
+    }
+
+    /// This CPU's actual feature set: [`CpuFamily::features`] for [`Cpu::family`], with
+    /// `CPUID` support replaced by [`Cpu::cpuid_override`] when one has been set.
+    pub fn features(&self) -> CpuFeatures {
+        let mut features = self.family.features();
+        if let Some(cpuid) = self.cpuid_override {
+            features.cpuid = cpuid;
+        }
+        features
+    }
+
+    /// Configures this CPU's floating-point unit, rejecting any combination that isn't
+    /// physically possible for [`Cpu::family`] — an integrated FPU on a 486SX, an 80387
+    /// on an 8088, and so on. See [`CpuFamily::allows_fpu`].
+    pub fn set_fpu(&mut self, fpu: Fpu) -> Result<(), SpecError> {
+        if !self.family.allows_fpu(fpu) {
+            return Err(SpecError::IncompatibleFpu);
+        }
+        self.fpu = fpu;
+        Ok(())
+    }
+
+    /// Whether this CPU can do floating-point math at all, whether through real
+    /// hardware (integrated or external) or software emulation.
+    pub fn has_fpu(&self) -> bool {
+        self.fpu != Fpu::None
+    }
+
     /// Set the clock rate for your CPU. We provide a lot of leeway here, but
     /// you won't be allowed to do the physically impossible. Every CPU family
     /// has a minimum and maximum clock rate that you must respect.
@@ -26,7 +260,7 @@ impl Cpu {
     /// So yes, you can set a 27 MHz 386 and we won't complain, even if no such
     /// thing ever officially existed. But you won't be able to push it over 50 MHz
     /// into pure fantasy territory — for that, you'll need a proper 486.
-    pub fn set_clock(&mut self, clock: u8) -> Result<(), SpecError> {
+    pub fn set_clock(&mut self, clock: ClockRate) -> Result<(), SpecError> {
         if clock < self.family.min_clock() {
             return Err(SpecError::ClockTooLow);
         }
@@ -58,7 +292,12 @@ impl FromStr for Cpu {
     fn from_str(input: &str) -> Result<Self, SpecError> {
         let family = CpuFamily::from_str(input)?;
         let clock = family.default_clock();
-        Ok(Cpu { family, clock })
+        Ok(Cpu {
+            family,
+            clock,
+            fpu: Fpu::None,
+            cpuid_override: None,
+        })
     }
 }
 
@@ -67,7 +306,7 @@ impl FromStr for Cpu {
 /// These CPU families correspond to processors commonly used in older DOS-compatible systems.
 /// Each variant of this enum represents a different CPU model, including various Intel and NEC
 /// processors that were widely used in PCs from the 1980s and 1990s.
-#[derive(Copy, Clone, Debug, Deserialize, PartialEq)]
+#[derive(Copy, Clone, Debug, Deserialize, Serialize, PartialEq)]
 pub enum CpuFamily {
     /// Intel 8086 CPU, a 16-bit processor that introduced the x86 architecture.
     #[serde(rename = "8086")]
@@ -150,67 +389,214 @@ pub enum CpuFamily {
         alias = "i80486dx4"
     )]
     I80486DX4,
+
+    /// Cyrix Cx486SLC, a 386-pin-compatible upgrade part with an on-chip cache but no FPU,
+    /// socketed into 386SX-class boards as a drop-in speed upgrade.
+    #[serde(rename = "486slc", alias = "cx486slc", alias = "cyrix486slc")]
+    Cyrix486SLC,
+
+    /// AMD Am486SX, AMD's second-sourced clone of the Intel 80486SX with no integrated FPU.
+    #[serde(rename = "am486sx", alias = "amd486sx")]
+    AMDAm486SX,
+
+    /// AMD Élan SC410, an embedded 486SX-compatible SoC with no integrated FPU, commonly
+    /// found in industrial and point-of-sale systems.
+    #[serde(rename = "elansc410", alias = "sc410", alias = "amdelansc410")]
+    AMDElanSC410,
+
+    /// UMC U5S, UMC's clone of the Intel 80486SX with no integrated FPU.
+    #[serde(rename = "u5s", alias = "umcu5s")]
+    UMCU5S,
+
+    /// UMC U5D, UMC's clone of the Intel 80486DX with an integrated FPU.
+    #[serde(rename = "u5d", alias = "umcu5d")]
+    UMCU5D,
+
+    /// IBM 486 (Blue Lightning-class), IBM's in-house 486SX-compatible part with no
+    /// integrated FPU.
+    #[serde(rename = "ibm486", alias = "ibm486slc2")]
+    IBM486,
 }
 
 impl CpuFamily {
-    /// [TODO] These default clocks are not yet correct!
-    /// The '4' in XT and older CPU classes should be read as 4.77 but using a float
-    /// here serves no real purpose as a .77Mhz. deviation won't be relevant for what we do.
-    pub fn default_clock(&self) -> u8 {
+    /// The speed a part of this family stock-shipped at, including the original IBM
+    /// PC/XT's authentic 4.77 MHz for the 8086/8088 — now representable exactly since
+    /// [`ClockRate`] stores hundredths of a MHz rather than a whole-number `u8`.
+    pub fn default_clock(&self) -> ClockRate {
+        use ClockRate as C;
         match self {
-            CpuFamily::I80186 => 4,
-            CpuFamily::I80286 => 8,
-            CpuFamily::I80386DX => 25,
-            CpuFamily::I80386SX => 16,
-            CpuFamily::I80486DX => 33,
-            CpuFamily::I80486DX2 => 50,
-            CpuFamily::I80486DX4 => 100,
-            CpuFamily::I80486SL => 33,
-            CpuFamily::I80486SX => 25,
-            CpuFamily::I80486SX2 => 50,
-            CpuFamily::I8086 => 4,
-            CpuFamily::I8088 => 4,
-            CpuFamily::NECV20 => 8,
-            CpuFamily::NECV30 => 8,
+            CpuFamily::I80186 => C::from_hundredths_mhz(400),
+            CpuFamily::I80286 => C::from_hundredths_mhz(800),
+            CpuFamily::I80386DX => C::from_hundredths_mhz(2500),
+            CpuFamily::I80386SX => C::from_hundredths_mhz(1600),
+            CpuFamily::I80486DX => C::from_hundredths_mhz(3300),
+            CpuFamily::I80486DX2 => C::from_hundredths_mhz(5000),
+            CpuFamily::I80486DX4 => C::from_hundredths_mhz(10000),
+            CpuFamily::I80486SL => C::from_hundredths_mhz(3300),
+            CpuFamily::I80486SX => C::from_hundredths_mhz(2500),
+            CpuFamily::I80486SX2 => C::from_hundredths_mhz(5000),
+            CpuFamily::I8086 => C::from_hundredths_mhz(477),
+            CpuFamily::I8088 => C::from_hundredths_mhz(477),
+            CpuFamily::NECV20 => C::from_hundredths_mhz(800),
+            CpuFamily::NECV30 => C::from_hundredths_mhz(800),
+            CpuFamily::Cyrix486SLC => C::from_hundredths_mhz(3300),
+            CpuFamily::AMDAm486SX => C::from_hundredths_mhz(3300),
+            CpuFamily::AMDElanSC410 => C::from_hundredths_mhz(3300),
+            CpuFamily::UMCU5S => C::from_hundredths_mhz(3300),
+            CpuFamily::UMCU5D => C::from_hundredths_mhz(3300),
+            CpuFamily::IBM486 => C::from_hundredths_mhz(3300),
         }
     }
 
     /// [TODO] These clock rates are not yet correct!
-    pub fn min_clock(&self) -> u8 {
+    pub fn min_clock(&self) -> ClockRate {
+        use ClockRate as C;
+        match self {
+            CpuFamily::I80186 => C::from_hundredths_mhz(400),
+            CpuFamily::I80286 => C::from_hundredths_mhz(600),
+            CpuFamily::I80386DX => C::from_hundredths_mhz(1600),
+            CpuFamily::I80386SX => C::from_hundredths_mhz(1200),
+            CpuFamily::I80486DX => C::from_hundredths_mhz(2000),
+            CpuFamily::I80486DX2 => C::from_hundredths_mhz(4000),
+            CpuFamily::I80486DX4 => C::from_hundredths_mhz(3300),
+            CpuFamily::I80486SL => C::from_hundredths_mhz(1600),
+            CpuFamily::I80486SX => C::from_hundredths_mhz(1600),
+            CpuFamily::I80486SX2 => C::from_hundredths_mhz(3300),
+            CpuFamily::I8086 => C::from_hundredths_mhz(477),
+            CpuFamily::I8088 => C::from_hundredths_mhz(477),
+            CpuFamily::NECV20 => C::from_hundredths_mhz(400),
+            CpuFamily::NECV30 => C::from_hundredths_mhz(400),
+            CpuFamily::Cyrix486SLC => C::from_hundredths_mhz(2000),
+            CpuFamily::AMDAm486SX => C::from_hundredths_mhz(1600),
+            CpuFamily::AMDElanSC410 => C::from_hundredths_mhz(2500),
+            CpuFamily::UMCU5S => C::from_hundredths_mhz(1600),
+            CpuFamily::UMCU5D => C::from_hundredths_mhz(2000),
+            CpuFamily::IBM486 => C::from_hundredths_mhz(2000),
+        }
+    }
+
+    pub fn max_clock(&self) -> ClockRate {
+        use ClockRate as C;
+        match self {
+            CpuFamily::I80186 => C::from_hundredths_mhz(1600),
+            CpuFamily::I80286 => C::from_hundredths_mhz(3300),
+            CpuFamily::I80386DX => C::from_hundredths_mhz(5000),
+            CpuFamily::I80386SX => C::from_hundredths_mhz(5000),
+            CpuFamily::I80486DX => C::from_hundredths_mhz(9000),
+            CpuFamily::I80486DX2 => C::from_hundredths_mhz(10000),
+            CpuFamily::I80486DX4 => C::from_hundredths_mhz(13300),
+            CpuFamily::I80486SL => C::from_hundredths_mhz(9000),
+            CpuFamily::I80486SX => C::from_hundredths_mhz(5000),
+            CpuFamily::I80486SX2 => C::from_hundredths_mhz(10000),
+            CpuFamily::I8086 => C::from_hundredths_mhz(1600),
+            CpuFamily::I8088 => C::from_hundredths_mhz(800),
+            CpuFamily::NECV20 => C::from_hundredths_mhz(1600),
+            CpuFamily::NECV30 => C::from_hundredths_mhz(1600),
+            CpuFamily::Cyrix486SLC => C::from_hundredths_mhz(5000),
+            CpuFamily::AMDAm486SX => C::from_hundredths_mhz(4000),
+            CpuFamily::AMDElanSC410 => C::from_hundredths_mhz(6600),
+            CpuFamily::UMCU5S => C::from_hundredths_mhz(4000),
+            CpuFamily::UMCU5D => C::from_hundredths_mhz(4000),
+            CpuFamily::IBM486 => C::from_hundredths_mhz(5000),
+        }
+    }
+
+    /// The company that manufactured this CPU family.
+    pub fn vendor(&self) -> CpuVendor {
+        match self {
+            CpuFamily::I8086
+            | CpuFamily::I8088
+            | CpuFamily::I80186
+            | CpuFamily::I80286
+            | CpuFamily::I80386SX
+            | CpuFamily::I80386DX
+            | CpuFamily::I80486SL
+            | CpuFamily::I80486SX
+            | CpuFamily::I80486SX2
+            | CpuFamily::I80486DX
+            | CpuFamily::I80486DX2
+            | CpuFamily::I80486DX4 => CpuVendor::Intel,
+            CpuFamily::NECV20 | CpuFamily::NECV30 => CpuVendor::NEC,
+            CpuFamily::Cyrix486SLC => CpuVendor::Cyrix,
+            CpuFamily::AMDAm486SX | CpuFamily::AMDElanSC410 => CpuVendor::AMD,
+            CpuFamily::UMCU5S | CpuFamily::UMCU5D => CpuVendor::UMC,
+            CpuFamily::IBM486 => CpuVendor::IBM,
+        }
+    }
+
+    /// Whether `fpu` is physically possible for this family: an 8086/8088/NEC clone or
+    /// 80186 only ever took an external 8087, an 80286 an external 80287, and an
+    /// 80386SX/DX an external 80387; the 486SX/SX2/SL shipped with no FPU at all and
+    /// relied on software emulation, while the 486DX/DX2/DX4 had one integrated and
+    /// can't take anything else.
+    pub fn allows_fpu(&self, fpu: Fpu) -> bool {
+        use Coprocessor::*;
         match self {
-            CpuFamily::I80186 => 4,
-            CpuFamily::I80286 => 6,
-            CpuFamily::I80386DX => 16,
-            CpuFamily::I80386SX => 12,
-            CpuFamily::I80486DX => 20,
-            CpuFamily::I80486DX2 => 40,
-            CpuFamily::I80486DX4 => 33,
-            CpuFamily::I80486SL => 16,
-            CpuFamily::I80486SX => 16,
-            CpuFamily::I80486SX2 => 33,
-            CpuFamily::I8086 => 4,
-            CpuFamily::I8088 => 4,
-            CpuFamily::NECV20 => 4,
-            CpuFamily::NECV30 => 4,
+            CpuFamily::I8086
+            | CpuFamily::I8088
+            | CpuFamily::NECV20
+            | CpuFamily::NECV30
+            | CpuFamily::I80186 => matches!(fpu, Fpu::None | Fpu::External(I8087)),
+            CpuFamily::I80286 => matches!(fpu, Fpu::None | Fpu::External(I80287)),
+            CpuFamily::I80386SX | CpuFamily::I80386DX => {
+                matches!(fpu, Fpu::None | Fpu::External(I80387))
+            }
+            CpuFamily::I80486SX
+            | CpuFamily::I80486SX2
+            | CpuFamily::I80486SL
+            | CpuFamily::Cyrix486SLC
+            | CpuFamily::AMDAm486SX
+            | CpuFamily::AMDElanSC410
+            | CpuFamily::UMCU5S
+            | CpuFamily::IBM486 => matches!(fpu, Fpu::None | Fpu::Emulated),
+            CpuFamily::I80486DX | CpuFamily::I80486DX2 | CpuFamily::I80486DX4 => {
+                matches!(fpu, Fpu::Integrated)
+            }
+            CpuFamily::UMCU5D => matches!(fpu, Fpu::Integrated),
         }
     }
 
-    pub fn max_clock(&self) -> u8 {
+    /// The concrete capabilities a stock part of this family shipped with: protected
+    /// mode arrived with the 286, virtual-8086 mode and 32-bit operation with the 386,
+    /// and `CPUID` not until the DX4-era 486s — the 486SX/DX/DX2 generation (Intel and
+    /// its clones alike) predates it. Call [`Cpu::features`] instead of this directly if
+    /// you have a specific [`Cpu`]; a pre-CPUID 486DX can be told apart from a
+    /// `CPUID`-capable one of the same family via [`Cpu::set_cpuid_override`].
+    pub fn features(&self) -> CpuFeatures {
         match self {
-            CpuFamily::I80186 => 16,
-            CpuFamily::I80286 => 33,
-            CpuFamily::I80386DX => 50,
-            CpuFamily::I80386SX => 50,
-            CpuFamily::I80486DX => 90,
-            CpuFamily::I80486DX2 => 100,
-            CpuFamily::I80486DX4 => 133,
-            CpuFamily::I80486SL => 90,
-            CpuFamily::I80486SX => 50,
-            CpuFamily::I80486SX2 => 100,
-            CpuFamily::I8086 => 16,
-            CpuFamily::I8088 => 8,
-            CpuFamily::NECV20 => 16,
-            CpuFamily::NECV30 => 16,
+            CpuFamily::I8086
+            | CpuFamily::I8088
+            | CpuFamily::NECV20
+            | CpuFamily::NECV30
+            | CpuFamily::I80186 => CpuFeatures::default(),
+            CpuFamily::I80286 => CpuFeatures {
+                protected_mode: true,
+                ..Default::default()
+            },
+            CpuFamily::I80386SX
+            | CpuFamily::I80386DX
+            | CpuFamily::I80486SL
+            | CpuFamily::I80486SX
+            | CpuFamily::I80486SX2
+            | CpuFamily::I80486DX
+            | CpuFamily::I80486DX2
+            | CpuFamily::Cyrix486SLC
+            | CpuFamily::AMDAm486SX
+            | CpuFamily::AMDElanSC410
+            | CpuFamily::UMCU5S
+            | CpuFamily::UMCU5D
+            | CpuFamily::IBM486 => CpuFeatures {
+                protected_mode: true,
+                virtual_8086: true,
+                addr32: true,
+                cpuid: false,
+            },
+            CpuFamily::I80486DX4 => CpuFeatures {
+                protected_mode: true,
+                virtual_8086: true,
+                addr32: true,
+                cpuid: true,
+            },
         }
     }
 }
@@ -235,6 +621,12 @@ impl fmt::Display for CpuFamily {
             CpuFamily::I80486DX => "Intel 80486DX",
             CpuFamily::I80486DX2 => "Intel 80486DX2",
             CpuFamily::I80486DX4 => "Intel 80486DX4",
+            CpuFamily::Cyrix486SLC => "Cyrix Cx486SLC",
+            CpuFamily::AMDAm486SX => "AMD Am486SX",
+            CpuFamily::AMDElanSC410 => "AMD Elan SC410",
+            CpuFamily::UMCU5S => "UMC U5S",
+            CpuFamily::UMCU5D => "UMC U5D",
+            CpuFamily::IBM486 => "IBM 486",
         };
         write!(f, "{}", label)
     }
@@ -277,6 +669,12 @@ impl FromStr for CpuFamily {
             } // DX is the default when a bare 486 is given
             "I80486DX2" | "80486DX2" | "486DX2" => Ok(CpuFamily::I80486DX2),
             "I80486DX4" | "80486DX4" | "486DX4" => Ok(CpuFamily::I80486DX4),
+            "486SLC" | "CX486SLC" | "CYRIX486SLC" => Ok(CpuFamily::Cyrix486SLC),
+            "AM486SX" | "AMD486SX" => Ok(CpuFamily::AMDAm486SX),
+            "ELANSC410" | "SC410" | "AMDELANSC410" => Ok(CpuFamily::AMDElanSC410),
+            "U5S" | "UMCU5S" => Ok(CpuFamily::UMCU5S),
+            "U5D" | "UMCU5D" => Ok(CpuFamily::UMCU5D),
+            "IBM486" | "IBM486SLC2" => Ok(CpuFamily::IBM486),
             _ => Err(SpecError::InvalidCpu),
         }
     }
@@ -288,6 +686,6 @@ impl fmt::Display for Cpu {
     /// This implementation formats each CPU type into a human-readable string that represents
     /// the full name of the processor, e.g., "Intel 8086" or "Intel 80486DX".
     fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
-        write!(f, "{} at {}Mhz.", self.family, self.clock)
+        write!(f, "{} at {}.", self.family, self.clock)
     }
 }
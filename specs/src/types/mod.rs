@@ -0,0 +1,5 @@
+pub mod audio;
+pub mod cpu;
+pub mod storage;
+mod tests;
+pub mod video;
@@ -0,0 +1,5 @@
+/// Floppy media types are a system-wide hardware concept, not specific to
+/// hardware specs, so the canonical definition lives in `common`. Re-exported
+/// here so callers can reach it alongside the other hardware part types in
+/// [`crate::types`].
+pub use common::storage::Floppy;
@@ -0,0 +1,160 @@
+#[cfg(test)]
+mod tests {
+    use std::str::FromStr;
+
+    use crate::types::cpu::{ClockRate, Coprocessor, Cpu, CpuFamily, CpuFeatures, CpuVendor, Fpu};
+
+    #[test]
+    fn set_fpu_accepts_matching_external_coprocessor() {
+        let mut cpu = Cpu::from_str("80286").unwrap();
+        assert!(cpu.set_fpu(Fpu::External(Coprocessor::I80287)).is_ok());
+        assert!(cpu.has_fpu());
+    }
+
+    #[test]
+    fn set_fpu_rejects_wrong_external_coprocessor() {
+        let mut cpu = Cpu::from_str("8088").unwrap();
+        assert!(cpu.set_fpu(Fpu::External(Coprocessor::I80387)).is_err());
+    }
+
+    #[test]
+    fn set_fpu_rejects_integrated_on_486sx() {
+        let mut cpu = Cpu::from_str("486sx").unwrap();
+        assert!(cpu.set_fpu(Fpu::Integrated).is_err());
+        assert!(cpu.set_fpu(Fpu::Emulated).is_ok());
+    }
+
+    #[test]
+    fn set_fpu_requires_integrated_on_486dx() {
+        let mut cpu = Cpu::from_str("486dx").unwrap();
+        assert!(cpu.set_fpu(Fpu::None).is_err());
+        assert!(cpu.set_fpu(Fpu::Integrated).is_ok());
+    }
+
+    #[test]
+    fn fresh_cpu_has_no_fpu() {
+        let cpu = Cpu::from_str("386dx").unwrap();
+        assert!(!cpu.has_fpu());
+    }
+
+    #[test]
+    fn clone_families_parse_and_report_their_vendor() {
+        assert_eq!(CpuFamily::from_str("486slc").unwrap().vendor(), CpuVendor::Cyrix);
+        assert_eq!(CpuFamily::from_str("am486sx").unwrap().vendor(), CpuVendor::AMD);
+        assert_eq!(CpuFamily::from_str("u5s").unwrap().vendor(), CpuVendor::UMC);
+        assert_eq!(CpuFamily::from_str("ibm486").unwrap().vendor(), CpuVendor::IBM);
+    }
+
+    #[test]
+    fn umc_u5d_requires_integrated_fpu_like_an_intel_dx() {
+        let mut cpu = Cpu::from_str("u5d").unwrap();
+        assert!(cpu.set_fpu(Fpu::Emulated).is_err());
+        assert!(cpu.set_fpu(Fpu::Integrated).is_ok());
+    }
+
+    #[test]
+    fn pre_protected_mode_parts_have_no_features() {
+        let cpu = Cpu::from_str("8088").unwrap();
+        let features = cpu.features();
+        assert!(!features.has_protected_mode());
+        assert!(!features.has_virtual_8086());
+        assert!(!features.is_32bit());
+        assert!(!features.has_cpuid());
+    }
+
+    #[test]
+    fn a_286_only_gets_protected_mode() {
+        let cpu = Cpu::from_str("286").unwrap();
+        let features = cpu.features();
+        assert!(features.has_protected_mode());
+        assert!(!features.has_virtual_8086());
+        assert!(!features.is_32bit());
+    }
+
+    #[test]
+    fn a_386dx_gets_protected_mode_v86_and_32bit_but_no_cpuid() {
+        let cpu = Cpu::from_str("386dx").unwrap();
+        let features = cpu.features();
+        assert!(features.has_protected_mode());
+        assert!(features.has_virtual_8086());
+        assert!(features.is_32bit());
+        assert!(!features.has_cpuid());
+    }
+
+    #[test]
+    fn a_stock_486dx_has_no_cpuid_but_a_dx4_does() {
+        assert!(!Cpu::from_str("486dx").unwrap().features().has_cpuid());
+        assert!(Cpu::from_str("486dx4").unwrap().features().has_cpuid());
+    }
+
+    #[test]
+    fn cpuid_override_distinguishes_two_486dx_parts() {
+        let mut early = Cpu::from_str("486dx").unwrap();
+        let mut later = Cpu::from_str("486dx").unwrap();
+        later.set_cpuid_override(Some(true));
+
+        assert!(!early.features().has_cpuid());
+        assert!(later.features().has_cpuid());
+
+        later.set_cpuid_override(None);
+        assert!(!later.features().has_cpuid());
+        assert_eq!(early.features(), later.features());
+    }
+
+    #[test]
+    fn features_satisfies_is_a_capability_subset_check() {
+        let needs_protected_mode = Cpu::from_str("286").unwrap().features();
+
+        assert!(needs_protected_mode.satisfies(&CpuFeatures::none()));
+        assert!(Cpu::from_str("386dx")
+            .unwrap()
+            .features()
+            .satisfies(&needs_protected_mode));
+        assert!(!Cpu::from_str("8088")
+            .unwrap()
+            .features()
+            .satisfies(&needs_protected_mode));
+    }
+
+    #[test]
+    fn clock_rate_parses_fractional_and_whole_mhz() {
+        assert_eq!(
+            ClockRate::from_str("4.77").unwrap(),
+            ClockRate::from_hundredths_mhz(477)
+        );
+        assert_eq!(
+            ClockRate::from_str("33").unwrap(),
+            ClockRate::from_hundredths_mhz(3300)
+        );
+    }
+
+    #[test]
+    fn clock_rate_rejects_a_whole_mhz_value_that_overflows_hundredths() {
+        assert!(ClockRate::from_str("700").is_err());
+        assert!(ClockRate::from_str("655.36").is_err());
+    }
+
+    #[test]
+    fn clock_rate_displays_as_mhz() {
+        assert_eq!(ClockRate::from_hundredths_mhz(477).to_string(), "4.77 MHz");
+        assert_eq!(ClockRate::from_hundredths_mhz(3300).to_string(), "33.00 MHz");
+    }
+
+    #[test]
+    fn an_8088_defaults_to_the_authentic_xt_clock() {
+        let cpu = Cpu::from_str("8088").unwrap();
+        assert_eq!(cpu.clock(), ClockRate::from_hundredths_mhz(477));
+    }
+
+    #[test]
+    fn set_clock_respects_family_bounds_precisely() {
+        let mut cpu = Cpu::from_str("386dx").unwrap();
+        assert!(cpu
+            .set_clock(ClockRate::from_hundredths_mhz(1500))
+            .is_err());
+        assert!(cpu
+            .set_clock(ClockRate::from_hundredths_mhz(4000))
+            .is_ok());
+        assert_eq!(cpu.clock(), ClockRate::from_hundredths_mhz(4000));
+    }
+}
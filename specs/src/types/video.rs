@@ -0,0 +1,69 @@
+use std::fmt;
+use std::str::FromStr;
+
+use serde::de::{self, Deserializer};
+use serde::{Deserialize, Serialize, Serializer};
+
+use crate::error::SpecError;
+
+/// A video adapter found in MS-DOS-compatible PC systems.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum VideoDevice {
+    /// IBM Monochrome Display Adapter.
+    MDA,
+    /// Hercules Graphics Card (MDA-compatible with an added graphics mode).
+    HGC,
+    /// IBM Color Graphics Adapter.
+    CGA,
+    /// IBM Enhanced Graphics Adapter.
+    EGA,
+    /// IBM/VESA Video Graphics Array.
+    VGA,
+}
+
+impl fmt::Display for VideoDevice {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let label = match self {
+            VideoDevice::MDA => "MDA",
+            VideoDevice::HGC => "HGC",
+            VideoDevice::CGA => "CGA",
+            VideoDevice::EGA => "EGA",
+            VideoDevice::VGA => "VGA",
+        };
+        write!(f, "{}", label)
+    }
+}
+
+impl FromStr for VideoDevice {
+    type Err = SpecError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s.trim().to_uppercase().as_str() {
+            "MDA" => Ok(VideoDevice::MDA),
+            "HGC" | "HERCULES" => Ok(VideoDevice::HGC),
+            "CGA" => Ok(VideoDevice::CGA),
+            "EGA" => Ok(VideoDevice::EGA),
+            "VGA" => Ok(VideoDevice::VGA),
+            _ => Err(SpecError::InvalidVideoDevice),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for VideoDevice {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        let s: String = Deserialize::deserialize(deserializer)?;
+        VideoDevice::from_str(&s).map_err(de::Error::custom)
+    }
+}
+
+impl Serialize for VideoDevice {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.serialize_str(&self.to_string())
+    }
+}